@@ -8,6 +8,8 @@ export default {
   },
   moduleNameMapper: {
     "^@/(.*)$": "<rootDir>/src/$1",
+    // wasm-pack 的 nodejs 目标在导入时同步加载 .wasm，由 npm run build:wasm 生成
+    "pkg/timer_calculation$": "<rootDir>/src/wasm/pkg-node/timer_calculation.js",
     "\\.(css|less|scss|sass)$": "identity-obj-proxy",
     "\\.(jpg|jpeg|png|gif|eot|otf|webp|svg|ttf|woff|woff2|mp4|webm|wav|mp3|m4a|aac|oga)$": "jest-transform-stub"
   },
@@ -0,0 +1,177 @@
+// Tauri 命令
+// 暴露给前端的命令：数据库命令与 services/database.ts 中的同名方法对应，计时器命令对应 services/backendTimer.ts，
+// 托盘与全局快捷键命令供 components/Desktop 下的 SystemTray.tsx、GlobalShortcuts.tsx 使用
+
+use std::collections::HashMap;
+
+use tauri::{AppHandle, State};
+
+use crate::database::{DailyStats, Database, DatabaseStats, FocusSession, NewFocusSession};
+use crate::global_shortcuts::{self, ShortcutState};
+use crate::shortcuts::{self, ShortcutBinding, ShortcutStatus};
+use crate::timer::{TimerService, TimerTick};
+use crate::timer_core::{CyclePolicy, TimerPhase};
+use crate::timer_settings::{self, TimerMode, TimerSettings};
+use crate::tray::{self, TrayMenuItem};
+
+fn to_message(error: rusqlite::Error) -> String {
+  format!("database error: {}", error)
+}
+
+#[tauri::command]
+pub fn save_focus_session(db: State<'_, Database>, session: NewFocusSession) -> Result<i64, String> {
+  db.save_focus_session(&session).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn get_focus_sessions_by_date_range(
+  db: State<'_, Database>,
+  start_date: String,
+  end_date: String,
+) -> Result<Vec<FocusSession>, String> {
+  db.get_focus_sessions_by_date_range(&start_date, &end_date).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn get_daily_stats(db: State<'_, Database>, start_date: String, end_date: String) -> Result<Vec<DailyStats>, String> {
+  db.get_daily_stats(&start_date, &end_date).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn update_session_efficiency(db: State<'_, Database>, session_id: i64, efficiency: f64) -> Result<bool, String> {
+  db.update_session_efficiency(session_id, efficiency).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn save_setting(db: State<'_, Database>, key: String, value: String) -> Result<(), String> {
+  db.save_setting(&key, &value).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn get_setting(db: State<'_, Database>, key: String) -> Result<Option<String>, String> {
+  db.get_setting(&key).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn get_all_settings(db: State<'_, Database>) -> Result<HashMap<String, String>, String> {
+  db.get_all_settings().map_err(to_message)
+}
+
+#[tauri::command]
+pub fn cleanup_old_data(db: State<'_, Database>, before_date: String) -> Result<usize, String> {
+  db.cleanup_old_data(&before_date).map_err(to_message)
+}
+
+#[tauri::command]
+pub fn get_database_stats(db: State<'_, Database>) -> Result<DatabaseStats, String> {
+  db.get_database_stats().map_err(to_message)
+}
+
+#[tauri::command]
+pub fn timer_status(timer: State<'_, TimerService>) -> TimerTick {
+  timer.status()
+}
+
+#[tauri::command]
+pub fn timer_start(timer: State<'_, TimerService>) -> TimerTick {
+  timer.start()
+}
+
+#[tauri::command]
+pub fn timer_pause(timer: State<'_, TimerService>) -> TimerTick {
+  timer.pause()
+}
+
+#[tauri::command]
+pub fn timer_skip(timer: State<'_, TimerService>) -> TimerTick {
+  timer.skip()
+}
+
+#[tauri::command]
+pub fn timer_reset(timer: State<'_, TimerService>) -> TimerTick {
+  timer.reset()
+}
+
+#[tauri::command]
+pub fn timer_micro_break(timer: State<'_, TimerService>) -> TimerTick {
+  timer.micro_break()
+}
+
+#[tauri::command]
+pub fn timer_switch_phase(
+  timer: State<'_, TimerService>,
+  phase: TimerPhase,
+  duration: Option<u32>,
+) -> Result<TimerTick, String> {
+  timer.switch_phase(phase, duration)
+}
+
+/// 切换计时模式并保存，新模式按已保存的设置从一个完整的专注段开始
+#[tauri::command]
+pub fn timer_switch_mode(
+  db: State<'_, Database>,
+  timer: State<'_, TimerService>,
+  mode: TimerMode,
+) -> Result<TimerTick, String> {
+  let settings = TimerSettings { mode, ..timer_settings::load(&db) };
+  timer_settings::save(&db, &settings).map_err(to_message)?;
+  Ok(timer.switch_mode(&settings))
+}
+
+#[tauri::command]
+pub fn timer_update_settings(
+  db: State<'_, Database>,
+  timer: State<'_, TimerService>,
+  settings: TimerSettings,
+) -> Result<TimerTick, String> {
+  timer_settings::save(&db, &settings).map_err(to_message)?;
+  Ok(timer.update_settings(&settings))
+}
+
+#[tauri::command]
+pub fn timer_use_preset(timer: State<'_, TimerService>, preset: Option<String>) -> Result<TimerTick, String> {
+  timer.use_preset(preset.as_deref())
+}
+
+#[tauri::command]
+pub fn timer_set_policy(timer: State<'_, TimerService>, policy: CyclePolicy) -> TimerTick {
+  timer.set_policy(policy)
+}
+
+#[tauri::command]
+pub fn set_tray_menu(app: AppHandle, items: Vec<TrayMenuItem>) -> Result<(), String> {
+  tray::set_extra_items(&app, &items).map_err(|e| e.to_string())
+}
+
+/// 传入 null 恢复为随计时刷新的提示
+#[tauri::command]
+pub fn update_tray_tooltip(app: AppHandle, tooltip: Option<String>) -> Result<(), String> {
+  tray::set_tooltip(&app, tooltip).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_global_shortcuts(state: State<'_, ShortcutState>) -> Vec<ShortcutStatus> {
+  state.statuses()
+}
+
+/// 修改快捷键绑定并立即重新注册；只需传入改动的绑定，其余沿用已保存的设置
+#[tauri::command]
+pub fn set_global_shortcuts(
+  app: AppHandle,
+  db: State<'_, Database>,
+  bindings: Vec<ShortcutBinding>,
+) -> Result<Vec<ShortcutStatus>, String> {
+  let mut merged = shortcuts::load(&db);
+  merged.extend(bindings);
+  let bindings = shortcuts::normalize(merged);
+  shortcuts::validate(&bindings)?;
+  shortcuts::save(&db, &bindings).map_err(to_message)?;
+  Ok(global_shortcuts::apply(&app, &bindings))
+}
+
+#[tauri::command]
+pub fn reset_global_shortcuts(app: AppHandle, db: State<'_, Database>) -> Result<Vec<ShortcutStatus>, String> {
+  let bindings = shortcuts::default_bindings();
+  shortcuts::save(&db, &bindings).map_err(to_message)?;
+  Ok(global_shortcuts::apply(&app, &bindings))
+}
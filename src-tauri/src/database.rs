@@ -0,0 +1,328 @@
+// 本地数据库
+// 应用数据目录下的 SQLite 文件，保存专注会话与应用设置；接口与前端 services/database.ts 一一对应
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+
+use crate::migrations::{self, MigrationError, MigrationReport, MIGRATIONS};
+
+pub const DATABASE_FILE: &str = "focusflow.db";
+
+/// 一次专注会话，字段名与前端 `FocusSession` 一致
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FocusSession {
+  pub id: i64,
+  // 会话所属日期，YYYY-MM-DD
+  pub date: String,
+  pub focus_duration: i64,
+  pub break_duration: i64,
+  pub micro_breaks: i64,
+  pub efficiency_score: f64,
+  pub created_at: String,
+}
+
+/// 新会话，由数据库分配 id 与创建时间
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NewFocusSession {
+  pub date: String,
+  pub focus_duration: i64,
+  pub break_duration: i64,
+  #[serde(default)]
+  pub micro_breaks: i64,
+  #[serde(default)]
+  pub efficiency_score: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyStats {
+  pub date: String,
+  pub total_focus_time: i64,
+  pub total_break_time: i64,
+  pub total_micro_breaks: i64,
+  pub average_efficiency: f64,
+  pub session_count: i64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DatabaseStats {
+  pub total_sessions: i64,
+  pub total_focus_time: i64,
+  pub total_break_time: i64,
+  pub total_micro_breaks: i64,
+  pub average_efficiency: f64,
+  pub first_session_date: Option<String>,
+  pub last_session_date: Option<String>,
+  // 平均效率最高的一天
+  pub best_day: Option<DailyStats>,
+}
+
+pub type DbResult<T> = rusqlite::Result<T>;
+
+/// 作为 Tauri 托管状态共享的数据库连接
+pub struct Database {
+  conn: Mutex<Connection>,
+}
+
+impl Database {
+  /// 打开数据库文件并执行待执行的迁移，同时返回迁移结果
+  pub fn open(path: &Path) -> Result<(Database, MigrationReport), MigrationError> {
+    Database::init(Connection::open(path)?, Some(path))
+  }
+
+  pub fn open_in_memory() -> Result<Database, MigrationError> {
+    Database::init(Connection::open_in_memory()?, None).map(|(db, _)| db)
+  }
+
+  fn init(mut conn: Connection, path: Option<&Path>) -> Result<(Database, MigrationReport), MigrationError> {
+    // 先确认结构版本：更新版本程序创建的数据库不做任何改动，包括切换日志模式
+    migrations::check_version(&conn, MIGRATIONS)?;
+    conn.execute_batch(
+      "PRAGMA journal_mode = WAL;
+       PRAGMA foreign_keys = ON;",
+    )?;
+    let report = migrations::migrate(&mut conn, path, MIGRATIONS)?;
+    Ok((Database { conn: Mutex::new(conn) }, report))
+  }
+
+  // 某次调用 panic 后连接本身仍然可用，不让锁中毒波及之后的请求
+  fn conn(&self) -> MutexGuard<'_, Connection> {
+    self.conn.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  /// 保存会话，返回新记录的 id
+  pub fn save_focus_session(&self, session: &NewFocusSession) -> DbResult<i64> {
+    let conn = self.conn();
+    conn.execute(
+      "INSERT INTO focus_sessions (date, focus_duration, break_duration, micro_breaks, efficiency_score)
+       VALUES (?1, ?2, ?3, ?4, ?5)",
+      params![
+        session.date,
+        session.focus_duration,
+        session.break_duration,
+        session.micro_breaks,
+        session.efficiency_score
+      ],
+    )?;
+    Ok(conn.last_insert_rowid())
+  }
+
+  /// `start_date` 到 `end_date`（含）之间的会话，最新的在前
+  pub fn get_focus_sessions_by_date_range(&self, start_date: &str, end_date: &str) -> DbResult<Vec<FocusSession>> {
+    let conn = self.conn();
+    let mut stmt = conn.prepare(
+      "SELECT id, date, focus_duration, break_duration, micro_breaks, efficiency_score, created_at
+       FROM focus_sessions
+       WHERE date BETWEEN ?1 AND ?2
+       ORDER BY created_at DESC, id DESC",
+    )?;
+    let rows = stmt.query_map(params![start_date, end_date], focus_session_from_row)?;
+    rows.collect()
+  }
+
+  /// 按日期汇总 `start_date` 到 `end_date`（含）之间的会话，最近的日期在前
+  pub fn get_daily_stats(&self, start_date: &str, end_date: &str) -> DbResult<Vec<DailyStats>> {
+    let conn = self.conn();
+    let mut stmt = conn.prepare(
+      "SELECT date,
+              COALESCE(SUM(focus_duration), 0),
+              COALESCE(SUM(break_duration), 0),
+              COALESCE(SUM(micro_breaks), 0),
+              COALESCE(AVG(efficiency_score), 0),
+              COUNT(*)
+       FROM focus_sessions
+       WHERE date BETWEEN ?1 AND ?2
+       GROUP BY date
+       ORDER BY date DESC",
+    )?;
+    let rows = stmt.query_map(params![start_date, end_date], daily_stats_from_row)?;
+    rows.collect()
+  }
+
+  /// 更新会话的效率评分，会话不存在时返回 false
+  pub fn update_session_efficiency(&self, session_id: i64, efficiency: f64) -> DbResult<bool> {
+    let updated = self.conn().execute(
+      "UPDATE focus_sessions SET efficiency_score = ?1 WHERE id = ?2",
+      params![efficiency, session_id],
+    )?;
+    Ok(updated > 0)
+  }
+
+  pub fn save_setting(&self, key: &str, value: &str) -> DbResult<()> {
+    self.conn().execute(
+      "INSERT INTO app_settings (key, value, updated_at) VALUES (?1, ?2, CURRENT_TIMESTAMP)
+       ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+      params![key, value],
+    )?;
+    Ok(())
+  }
+
+  pub fn get_setting(&self, key: &str) -> DbResult<Option<String>> {
+    self
+      .conn()
+      .query_row("SELECT value FROM app_settings WHERE key = ?1", params![key], |row| row.get(0))
+      .optional()
+  }
+
+  pub fn get_all_settings(&self) -> DbResult<HashMap<String, String>> {
+    let conn = self.conn();
+    let mut stmt = conn.prepare("SELECT key, value FROM app_settings")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+  }
+
+  /// 删除 `before_date` 之前的会话，返回删除的条数
+  pub fn cleanup_old_data(&self, before_date: &str) -> DbResult<usize> {
+    self.conn().execute("DELETE FROM focus_sessions WHERE date < ?1", params![before_date])
+  }
+
+  pub fn get_database_stats(&self) -> DbResult<DatabaseStats> {
+    let conn = self.conn();
+    let mut stats = conn.query_row(
+      "SELECT COUNT(*),
+              COALESCE(SUM(focus_duration), 0),
+              COALESCE(SUM(break_duration), 0),
+              COALESCE(SUM(micro_breaks), 0),
+              COALESCE(AVG(efficiency_score), 0),
+              MIN(date),
+              MAX(date)
+       FROM focus_sessions",
+      [],
+      |row| {
+        Ok(DatabaseStats {
+          total_sessions: row.get(0)?,
+          total_focus_time: row.get(1)?,
+          total_break_time: row.get(2)?,
+          total_micro_breaks: row.get(3)?,
+          average_efficiency: row.get(4)?,
+          first_session_date: row.get(5)?,
+          last_session_date: row.get(6)?,
+          best_day: None,
+        })
+      },
+    )?;
+    stats.best_day = conn
+      .query_row(
+        "SELECT date,
+                SUM(focus_duration),
+                SUM(break_duration),
+                SUM(micro_breaks),
+                AVG(efficiency_score),
+                COUNT(*)
+         FROM focus_sessions
+         GROUP BY date
+         ORDER BY AVG(efficiency_score) DESC, SUM(focus_duration) DESC
+         LIMIT 1",
+        [],
+        daily_stats_from_row,
+      )
+      .optional()?;
+    Ok(stats)
+  }
+}
+
+fn focus_session_from_row(row: &Row<'_>) -> rusqlite::Result<FocusSession> {
+  Ok(FocusSession {
+    id: row.get(0)?,
+    date: row.get(1)?,
+    focus_duration: row.get(2)?,
+    break_duration: row.get(3)?,
+    micro_breaks: row.get::<_, Option<i64>>(4)?.unwrap_or(0),
+    efficiency_score: row.get::<_, Option<f64>>(5)?.unwrap_or(0.0),
+    created_at: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+  })
+}
+
+fn daily_stats_from_row(row: &Row<'_>) -> rusqlite::Result<DailyStats> {
+  Ok(DailyStats {
+    date: row.get(0)?,
+    total_focus_time: row.get(1)?,
+    total_break_time: row.get(2)?,
+    total_micro_breaks: row.get(3)?,
+    average_efficiency: row.get(4)?,
+    session_count: row.get(5)?,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn session(date: &str, focus: i64, efficiency: f64) -> NewFocusSession {
+    NewFocusSession {
+      date: date.to_string(),
+      focus_duration: focus,
+      break_duration: 5,
+      micro_breaks: 2,
+      efficiency_score: efficiency,
+    }
+  }
+
+  #[test]
+  fn sessions_round_trip_and_aggregate() {
+    let db = Database::open_in_memory().unwrap();
+    let first = db.save_focus_session(&session("2024-01-14", 25, 80.0)).unwrap();
+    db.save_focus_session(&session("2024-01-15", 50, 60.0)).unwrap();
+    db.save_focus_session(&session("2024-01-15", 25, 90.0)).unwrap();
+
+    let sessions = db.get_focus_sessions_by_date_range("2024-01-15", "2024-01-15").unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert!(sessions.iter().all(|s| s.date == "2024-01-15" && !s.created_at.is_empty()));
+
+    let daily = db.get_daily_stats("2024-01-01", "2024-01-31").unwrap();
+    assert_eq!(daily.len(), 2);
+    assert_eq!((daily[0].date.as_str(), daily[0].total_focus_time, daily[0].session_count), ("2024-01-15", 75, 2));
+    assert!((daily[0].average_efficiency - 75.0).abs() < 1e-9);
+
+    assert!(db.update_session_efficiency(first, 95.0).unwrap());
+    assert!(!db.update_session_efficiency(9_999, 95.0).unwrap());
+    let stats = db.get_database_stats().unwrap();
+    assert_eq!(stats.total_sessions, 3);
+    assert_eq!(stats.total_micro_breaks, 6);
+    assert_eq!(stats.first_session_date.as_deref(), Some("2024-01-14"));
+    assert_eq!(stats.best_day.map(|d| d.date), Some("2024-01-14".to_string()));
+
+    assert_eq!(db.cleanup_old_data("2024-01-15").unwrap(), 1);
+    assert_eq!(db.get_database_stats().unwrap().total_sessions, 2);
+  }
+
+  #[test]
+  fn settings_are_upserted() {
+    let db = Database::open_in_memory().unwrap();
+    assert_eq!(db.get_setting("theme").unwrap(), None);
+    db.save_setting("theme", "dark").unwrap();
+    db.save_setting("theme", "light").unwrap();
+    assert_eq!(db.get_setting("theme").unwrap().as_deref(), Some("light"));
+    assert_eq!(db.get_all_settings().unwrap().len(), 1);
+
+    let empty = db.get_database_stats().unwrap();
+    assert_eq!(empty, DatabaseStats::default());
+  }
+
+  #[test]
+  fn newer_schema_is_left_untouched() {
+    let path = std::env::temp_dir().join(format!("focusflow-newer-schema-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let conn = Connection::open(&path).unwrap();
+    conn
+      .execute_batch(
+        "CREATE TABLE schema_version (version INTEGER PRIMARY KEY, description TEXT NOT NULL);
+         INSERT INTO schema_version (version, description) VALUES (99, 'future');",
+      )
+      .unwrap();
+    drop(conn);
+
+    let error = Database::open(&path).err().unwrap();
+    assert!(matches!(error, MigrationError::NewerSchema { found: 99, .. }));
+    // WAL 模式写在文件里，没有切换时重新打开仍是默认的 delete
+    let conn = Connection::open(&path).unwrap();
+    let mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+    drop(conn);
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(mode, "delete");
+  }
+}
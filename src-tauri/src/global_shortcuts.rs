@@ -0,0 +1,77 @@
+// 全局快捷键注册
+// 按 shortcuts.rs 中的绑定向系统注册快捷键并分发动作。某个快捷键注册失败（如已被其他程序占用）
+// 不影响其他快捷键和应用启动；每个绑定的结果保存在 ShortcutState 中，由 get_global_shortcuts 等命令返回给前端
+
+use std::sync::{Mutex, MutexGuard};
+
+use tauri::{AppHandle, GlobalShortcutManager, Manager};
+
+use crate::shortcuts::{ShortcutAction, ShortcutBinding, ShortcutStatus};
+use crate::timer::TimerService;
+use crate::tray;
+
+pub const SHOW_STATS_EVENT: &str = "shortcut://show-stats";
+
+/// 已注册的快捷键及最近一次注册结果，作为 Tauri 托管状态
+#[derive(Default)]
+pub struct ShortcutState {
+  statuses: Mutex<Vec<ShortcutStatus>>,
+}
+
+impl ShortcutState {
+  pub fn statuses(&self) -> Vec<ShortcutStatus> {
+    lock(&self.statuses).clone()
+  }
+}
+
+/// 注销之前注册的快捷键，再按 `bindings` 重新注册，返回每个绑定的结果
+pub fn apply(app: &AppHandle, bindings: &[ShortcutBinding]) -> Vec<ShortcutStatus> {
+  let state = app.state::<ShortcutState>();
+  let mut statuses = lock(&state.statuses);
+  let mut manager = app.global_shortcut_manager();
+
+  for status in statuses.iter().filter(|status| status.registered) {
+    let _ = manager.unregister(&status.accelerator);
+  }
+
+  *statuses = bindings
+    .iter()
+    .map(|binding| {
+      let mut status = ShortcutStatus::unregistered(binding);
+      if binding.enabled {
+        let handle = app.clone();
+        let action = binding.action;
+        match manager.register(&binding.accelerator, move || dispatch(&handle, action)) {
+          Ok(()) => status.registered = true,
+          Err(error) => status.error = Some(error.to_string()),
+        }
+      }
+      status
+    })
+    .collect();
+  statuses.clone()
+}
+
+fn dispatch(app: &AppHandle, action: ShortcutAction) {
+  let timer = app.state::<TimerService>();
+  match action {
+    ShortcutAction::ToggleWindow => tray::toggle_window(app),
+    ShortcutAction::StartPause => {
+      timer.toggle();
+    }
+    ShortcutAction::Skip => {
+      timer.skip();
+    }
+    ShortcutAction::MicroBreak => {
+      timer.micro_break();
+    }
+    ShortcutAction::ShowStats => {
+      tray::show_window(app);
+      let _ = app.emit_all(SHOW_STATS_EVENT, ());
+    }
+  }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
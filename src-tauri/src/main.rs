@@ -3,36 +3,110 @@
   windows_subsystem = "windows"
 )]
 
+mod commands;
+mod database;
+mod global_shortcuts;
+mod migrations;
+mod shortcuts;
+mod timer;
+// 与 wasm 模块共用的计时核心，后端只用到其中一部分
+#[allow(dead_code)]
+#[path = "../../src/wasm/timer_core/mod.rs"]
+mod timer_core;
+mod timer_settings;
+mod tray;
+
+use tauri::api::dialog::{MessageDialogBuilder, MessageDialogKind};
 use tauri::{
-  Manager, GlobalShortcutManager, WindowEvent
+  Manager, RunEvent, WindowEvent
 };
 use window_shadows::set_shadow;
 
+use database::{Database, DATABASE_FILE};
+use migrations::MigrationError;
+use global_shortcuts::ShortcutState;
+use timer::{TimerService, NOTICE_EVENT, TICK_EVENT, TRANSITION_EVENT};
+use timer_core::CyclePolicy;
+use tray::TrayState;
+
+/// 数据库无法打开时托管的标记：主窗口已关闭，提示对话框关闭前不退出
+struct DatabaseUnavailable;
+
 fn main() {
   tauri::Builder::default()
+    .manage(TrayState::default())
+    .manage(ShortcutState::default())
+    .system_tray(tray::build())
+    .on_system_tray_event(tray::handle_event)
     .setup(|app| {
       let window = app.get_window("main").unwrap();
 
       #[cfg(any(windows, target_os = "macos"))]
       set_shadow(&window, true).expect("Failed to set window shadow");
 
-      // 注册全局快捷键
-      let app_handle = app.handle();
-      let mut shortcut_manager = app.global_shortcut_manager();
-
-      // Ctrl+Shift+F 显示/隐藏窗口
-      shortcut_manager
-        .register("CmdOrCtrl+Shift+F", move || {
-          if let Some(window) = app_handle.get_window("main") {
-            if window.is_visible().unwrap_or(false) {
-              let _ = window.hide();
-            } else {
-              let _ = window.show();
-              let _ = window.set_focus();
-            }
-          }
-        })
-        .unwrap();
+      // 后端计时器，状态变化广播给所有窗口与托盘
+      let timer = TimerService::new(CyclePolicy::default());
+      let emitter = app.handle();
+      timer.spawn(move |update| {
+        for transition in update.transitions {
+          let _ = emitter.emit_all(TRANSITION_EVENT, transition);
+        }
+        for notice in update.notices {
+          let _ = emitter.emit_all(NOTICE_EVENT, notice);
+        }
+        let _ = emitter.emit_all(TICK_EVENT, &update.tick);
+        tray::update(&emitter, &update.tick);
+      });
+      app.manage(timer);
+
+      // 打开应用数据目录下的数据库并执行迁移
+      let data_dir = app
+        .path_resolver()
+        .app_data_dir()
+        .ok_or("failed to resolve app data directory")?;
+      std::fs::create_dir_all(&data_dir)?;
+      let (database, report) = match Database::open(&data_dir.join(DATABASE_FILE)) {
+        Ok(opened) => opened,
+        // 数据库由更新版本的程序创建：不改动数据，提示用户升级后退出。
+        // 关闭主窗口而不只是隐藏，页面不会再调用依赖数据库的命令
+        Err(MigrationError::NewerSchema { found, supported }) => {
+          app.manage(DatabaseUnavailable);
+          window.close()?;
+          let handle = app.handle();
+          MessageDialogBuilder::new(
+            "FocusFlow",
+            format!(
+              "数据库由更新版本的 FocusFlow 创建（结构版本 {}，当前版本支持 {}），请升级 FocusFlow 后再打开。",
+              found, supported
+            ),
+          )
+          .kind(MessageDialogKind::Error)
+          .show(move |_| handle.exit(1));
+          return Ok(());
+        }
+        Err(error) => return Err(error.into()),
+      };
+      if let Some(backup) = &report.backup {
+        MessageDialogBuilder::new(
+          "FocusFlow",
+          format!(
+            "数据库已从结构版本 {} 升级到 {}，升级前的数据已备份到 {}。",
+            report.from,
+            report.to,
+            backup.display()
+          ),
+        )
+        .parent(&window)
+        .kind(MessageDialogKind::Info)
+        .show(|_| {});
+      }
+      // 计时器按保存的模式与设置运行
+      app.state::<TimerService>().switch_mode(&timer_settings::load(&database));
+      app.manage(database);
+
+      // 按保存的绑定注册全局快捷键；注册失败只报告给前端，不影响启动
+      let bindings = shortcuts::load(&app.state::<Database>());
+      global_shortcuts::apply(&app.handle(), &bindings);
 
       // 处理窗口关闭事件
       let window_clone = window.clone();
@@ -44,6 +118,40 @@ fn main() {
 
       Ok(())
     })
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .invoke_handler(tauri::generate_handler![
+      commands::save_focus_session,
+      commands::get_focus_sessions_by_date_range,
+      commands::get_daily_stats,
+      commands::update_session_efficiency,
+      commands::save_setting,
+      commands::get_setting,
+      commands::get_all_settings,
+      commands::cleanup_old_data,
+      commands::get_database_stats,
+      commands::timer_status,
+      commands::timer_start,
+      commands::timer_pause,
+      commands::timer_skip,
+      commands::timer_reset,
+      commands::timer_micro_break,
+      commands::timer_switch_phase,
+      commands::timer_switch_mode,
+      commands::timer_update_settings,
+      commands::timer_use_preset,
+      commands::timer_set_policy,
+      commands::set_tray_menu,
+      commands::update_tray_tooltip,
+      commands::get_global_shortcuts,
+      commands::set_global_shortcuts,
+      commands::reset_global_shortcuts
+    ])
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|app, event| {
+      if let RunEvent::ExitRequested { api, .. } = event {
+        if app.try_state::<DatabaseUnavailable>().is_some() {
+          api.prevent_exit();
+        }
+      }
+    });
 }
\ No newline at end of file
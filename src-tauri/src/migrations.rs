@@ -0,0 +1,260 @@
+// 数据库迁移
+// 按编号顺序执行的结构变更，已执行到的版本记录在 schema_version 表中。
+// 升级前备份数据库文件，每个迁移在独立事务中执行，遇到比当前程序更新的数据库时拒绝打开
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// 一个迁移。`version` 从 1 开始连续递增，发布后不得修改，只能追加新的迁移
+#[derive(Clone, Copy, Debug)]
+pub struct Migration {
+  pub version: u32,
+  pub description: &'static str,
+  pub sql: &'static str,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+  // 与早期 `CREATE TABLE IF NOT EXISTS` 建出的结构相同，旧数据库可直接接管
+  Migration {
+    version: 1,
+    description: "focus sessions and app settings",
+    sql: "CREATE TABLE IF NOT EXISTS focus_sessions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            date TEXT NOT NULL,
+            focus_duration INTEGER NOT NULL,
+            break_duration INTEGER NOT NULL,
+            micro_breaks INTEGER DEFAULT 0,
+            efficiency_score REAL DEFAULT 0,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+          );
+
+          CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+          );
+
+          CREATE INDEX IF NOT EXISTS idx_sessions_date ON focus_sessions(date);
+          CREATE INDEX IF NOT EXISTS idx_sessions_created_at ON focus_sessions(created_at);",
+  },
+];
+
+#[derive(Debug)]
+pub enum MigrationError {
+  Sqlite(rusqlite::Error),
+  /// 数据库由更新版本的程序创建
+  NewerSchema { found: u32, supported: u32 },
+  /// 升级前备份失败，数据库未做任何改动
+  Backup { path: PathBuf, error: rusqlite::Error },
+  /// 某个迁移执行失败，已回滚到执行前的版本
+  Failed { version: u32, error: rusqlite::Error },
+}
+
+impl fmt::Display for MigrationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      MigrationError::Sqlite(error) => write!(f, "database error: {}", error),
+      MigrationError::NewerSchema { found, supported } => write!(
+        f,
+        "database schema version {} is newer than supported version {}; please update FocusFlow",
+        found, supported
+      ),
+      MigrationError::Backup { path, error } => {
+        write!(f, "failed to back up database to {}: {}", path.display(), error)
+      }
+      MigrationError::Failed { version, error } => write!(f, "migration {} failed: {}", version, error),
+    }
+  }
+}
+
+impl std::error::Error for MigrationError {}
+
+impl From<rusqlite::Error> for MigrationError {
+  fn from(error: rusqlite::Error) -> Self {
+    MigrationError::Sqlite(error)
+  }
+}
+
+/// 一次迁移的结果
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationReport {
+  pub from: u32,
+  pub to: u32,
+  // 升级前的备份文件；无需升级或数据库为新建时为 None
+  pub backup: Option<PathBuf>,
+}
+
+/// 当前结构版本，尚未迁移过的数据库为 0
+pub fn schema_version(conn: &Connection) -> rusqlite::Result<u32> {
+  let exists: Option<i64> = conn
+    .query_row(
+      "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_version'",
+      [],
+      |row| row.get(0),
+    )
+    .optional()?;
+  if exists.is_none() {
+    return Ok(0);
+  }
+  conn.query_row("SELECT COALESCE(MAX(version), 0) FROM schema_version", [], |row| row.get(0))
+}
+
+/// 当前结构版本；数据库由更新版本的程序创建时返回 `NewerSchema`
+pub fn check_version(conn: &Connection, migrations: &[Migration]) -> Result<u32, MigrationError> {
+  let current = schema_version(conn)?;
+  let latest = migrations.last().map_or(0, |m| m.version);
+  if current > latest {
+    return Err(MigrationError::NewerSchema { found: current, supported: latest });
+  }
+  Ok(current)
+}
+
+/// 把数据库升级到 `migrations` 的最新版本。`path` 为数据库文件路径，
+/// 有待执行的迁移且库中已有数据时先备份到同目录下的 `<文件名>.v<版本>.bak`
+pub fn migrate(conn: &mut Connection, path: Option<&Path>, migrations: &[Migration]) -> Result<MigrationReport, MigrationError> {
+  let current = check_version(conn, migrations)?;
+  let latest = migrations.last().map_or(0, |m| m.version);
+  if current == latest {
+    return Ok(MigrationReport { from: current, to: current, backup: None });
+  }
+
+  let backup = match path {
+    Some(path) if has_user_tables(conn)? => Some(backup(conn, path, current)?),
+    _ => None,
+  };
+
+  conn.execute_batch(
+    "CREATE TABLE IF NOT EXISTS schema_version (
+       version INTEGER PRIMARY KEY,
+       description TEXT NOT NULL,
+       applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
+     );",
+  )?;
+  for migration in migrations.iter().filter(|m| m.version > current) {
+    apply(conn, migration).map_err(|error| MigrationError::Failed { version: migration.version, error })?;
+  }
+  Ok(MigrationReport { from: current, to: latest, backup })
+}
+
+fn apply(conn: &mut Connection, migration: &Migration) -> rusqlite::Result<()> {
+  let tx = conn.transaction()?;
+  tx.execute_batch(migration.sql)?;
+  tx.execute(
+    "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+    params![migration.version, migration.description],
+  )?;
+  tx.commit()
+}
+
+fn has_user_tables(conn: &Connection) -> rusqlite::Result<bool> {
+  let count: i64 = conn.query_row(
+    "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    [],
+    |row| row.get(0),
+  )?;
+  Ok(count > 0)
+}
+
+fn backup(conn: &Connection, path: &Path, version: u32) -> Result<PathBuf, MigrationError> {
+  let mut name = path.file_name().unwrap_or_default().to_os_string();
+  name.push(format!(".v{}.bak", version));
+  let target = path.with_file_name(name);
+  // VACUUM INTO 不会覆盖已有文件；同版本的旧备份已过时
+  let _ = std::fs::remove_file(&target);
+  conn
+    .execute("VACUUM INTO ?1", params![target.to_string_lossy()])
+    .map_err(|error| MigrationError::Backup { path: target.clone(), error })?;
+  Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const NEXT: Migration = Migration {
+    version: 2,
+    description: "session notes",
+    sql: "ALTER TABLE focus_sessions ADD COLUMN notes TEXT;",
+  };
+
+  // 测试用的临时目录，离开作用域时（包括断言失败时）删除
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str) -> TempDir {
+      let dir = std::env::temp_dir().join(format!("focusflow-migrations-{}-{}", name, std::process::id()));
+      let _ = std::fs::remove_dir_all(&dir);
+      std::fs::create_dir_all(&dir).unwrap();
+      TempDir(dir)
+    }
+
+    fn db(&self) -> PathBuf {
+      self.0.join("focusflow.db")
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = std::fs::remove_dir_all(&self.0);
+    }
+  }
+
+  #[test]
+  fn fresh_database_migrates_to_latest() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let report = migrate(&mut conn, None, MIGRATIONS).unwrap();
+    assert_eq!((report.from, report.to, report.backup), (0, 1, None));
+    assert_eq!(schema_version(&conn).unwrap(), 1);
+    // 再次执行无事可做
+    assert_eq!(migrate(&mut conn, None, MIGRATIONS).unwrap().from, 1);
+  }
+
+  #[test]
+  fn upgrade_backs_up_existing_data() {
+    let dir = TempDir::new("upgrade");
+    let path = dir.db();
+    let mut conn = Connection::open(&path).unwrap();
+    migrate(&mut conn, Some(&path), MIGRATIONS).unwrap();
+    conn
+      .execute("INSERT INTO focus_sessions (date, focus_duration, break_duration) VALUES ('2024-01-15', 25, 5)", [])
+      .unwrap();
+
+    let report = migrate(&mut conn, Some(&path), &[MIGRATIONS[0], NEXT]).unwrap();
+    assert_eq!((report.from, report.to), (1, 2));
+    let backup = report.backup.unwrap();
+    let saved = Connection::open(&backup).unwrap();
+    assert_eq!(schema_version(&saved).unwrap(), 1);
+    let count: i64 = saved.query_row("SELECT COUNT(*) FROM focus_sessions", [], |row| row.get(0)).unwrap();
+    assert_eq!(count, 1);
+    conn.execute("UPDATE focus_sessions SET notes = 'standup'", []).unwrap();
+  }
+
+  #[test]
+  fn failed_migration_rolls_back() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrate(&mut conn, None, MIGRATIONS).unwrap();
+    let broken = Migration {
+      version: 2,
+      description: "broken",
+      sql: "CREATE TABLE tags (name TEXT); INSERT INTO missing VALUES (1);",
+    };
+    let error = migrate(&mut conn, None, &[MIGRATIONS[0], broken]).unwrap_err();
+    assert!(matches!(error, MigrationError::Failed { version: 2, .. }));
+    assert_eq!(schema_version(&conn).unwrap(), 1);
+    let tags: Option<i64> = conn
+      .query_row("SELECT 1 FROM sqlite_master WHERE name = 'tags'", [], |row| row.get(0))
+      .optional()
+      .unwrap();
+    assert_eq!(tags, None);
+  }
+
+  #[test]
+  fn refuses_newer_schema() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrate(&mut conn, None, &[MIGRATIONS[0], NEXT]).unwrap();
+    let error = migrate(&mut conn, None, MIGRATIONS).unwrap_err();
+    assert!(matches!(error, MigrationError::NewerSchema { found: 2, supported: 1 }));
+  }
+}
@@ -0,0 +1,329 @@
+// 全局快捷键绑定
+// 每个动作绑定一个快捷键，以 JSON 保存在设置表中；注册与分发见 global_shortcuts.rs
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{Database, DbResult};
+
+pub const SETTINGS_KEY: &str = "global_shortcuts";
+
+// 加速键的修饰键与按键名对照 tao 0.16.11（Tauri 1.8 使用的版本）的 `accelerator.rs`
+// 与 `KeyCode::from_str`，不区分大小写；升级 Tauri 时需要重新核对
+const NAMED_KEYS: &[&str] = &[
+  "BACKQUOTE", "BACKSLASH", "BRACKETLEFT", "BRACKETRIGHT", "COMMA", "PLUS", "PERIOD", "QUOTE", "SEMICOLON", "SLASH",
+  "BACKSPACE", "CAPSLOCK", "CONTEXTMENU", "ENTER", "SPACE", "TAB", "CONVERT", "INSERT", "DELETE", "END", "HELP", "HOME",
+  "PAGEDOWN", "PAGEUP", "DOWN", "ARROWDOWN", "UP", "ARROWUP", "LEFT", "ARROWLEFT", "RIGHT", "ARROWRIGHT", "NUMLOCK",
+  "NUMADD", "NUMPADADD", "NUMBACKSPACE", "NUMPADBACKSPACE", "NUMCLEAR", "NUMPADCLEAR", "NUMCOMMA", "NUMPADCOMMA",
+  "NUMDIVIDE", "NUMPADDIVIDE", "NUMSUBSTRACT", "NUMPADSUBSTRACT", "NUMENTER", "NUMPADENTER", "ESC", "ESCAPE", "FN",
+  "FNLOCK", "PRINTSCREEN", "SCROLLLOCK", "PAUSE", "VOLUMEMUTE", "VOLUMEDOWN", "VOLUMEUP", "MEDIANEXTTRACK",
+  "MEDIAPREVIOUSTRACK", "MEDIAPLAYPAUSE", "LAUNCHMAIL", "SUSPEND",
+];
+
+const ALT: u8 = 1;
+const CONTROL: u8 = 1 << 1;
+const SUPER: u8 = 1 << 2;
+const SHIFT: u8 = 1 << 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ShortcutAction {
+  ToggleWindow,
+  StartPause,
+  Skip,
+  MicroBreak,
+  ShowStats,
+}
+
+impl ShortcutAction {
+  pub const ALL: [ShortcutAction; 5] = [
+    ShortcutAction::ToggleWindow,
+    ShortcutAction::StartPause,
+    ShortcutAction::Skip,
+    ShortcutAction::MicroBreak,
+    ShortcutAction::ShowStats,
+  ];
+
+  pub fn default_accelerator(self) -> &'static str {
+    match self {
+      ShortcutAction::ToggleWindow => "CmdOrCtrl+Shift+F",
+      ShortcutAction::StartPause => "CmdOrCtrl+Shift+Space",
+      ShortcutAction::Skip => "CmdOrCtrl+Shift+S",
+      ShortcutAction::MicroBreak => "CmdOrCtrl+Shift+M",
+      ShortcutAction::ShowStats => "CmdOrCtrl+Shift+D",
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+  pub action: ShortcutAction,
+  // Tauri 加速键格式，如 `CmdOrCtrl+Shift+F`
+  pub accelerator: String,
+  #[serde(default = "enabled_by_default")]
+  pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+  true
+}
+
+impl ShortcutBinding {
+  pub fn default_for(action: ShortcutAction) -> ShortcutBinding {
+    ShortcutBinding {
+      action,
+      accelerator: action.default_accelerator().to_string(),
+      enabled: true,
+    }
+  }
+}
+
+/// 一个绑定的注册结果，`error` 为系统拒绝注册的原因（如已被其他程序占用）
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutStatus {
+  pub action: ShortcutAction,
+  pub accelerator: String,
+  pub enabled: bool,
+  pub registered: bool,
+  pub error: Option<String>,
+}
+
+impl ShortcutStatus {
+  pub fn unregistered(binding: &ShortcutBinding) -> ShortcutStatus {
+    ShortcutStatus {
+      action: binding.action,
+      accelerator: binding.accelerator.clone(),
+      enabled: binding.enabled,
+      registered: false,
+      error: None,
+    }
+  }
+}
+
+pub fn default_bindings() -> Vec<ShortcutBinding> {
+  ShortcutAction::ALL.iter().map(|&action| ShortcutBinding::default_for(action)).collect()
+}
+
+/// 每个动作恰好一个绑定，按 `ShortcutAction::ALL` 排列：缺少的动作取默认值，重复的动作以最后一个为准
+pub fn normalize(bindings: Vec<ShortcutBinding>) -> Vec<ShortcutBinding> {
+  ShortcutAction::ALL
+    .iter()
+    .map(|&action| {
+      let binding = bindings.iter().rev().find(|b| b.action == action).cloned();
+      let mut binding = binding.unwrap_or_else(|| ShortcutBinding::default_for(action));
+      binding.accelerator = binding.accelerator.trim().to_string();
+      binding
+    })
+    .collect()
+}
+
+/// 解析后的加速键：修饰键集合加主键的规范名称，写法不同的同一组合解析结果相等
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Accelerator {
+  modifiers: u8,
+  key: String,
+}
+
+fn modifier(token: &str) -> Option<u8> {
+  match token.to_ascii_uppercase().as_str() {
+    "OPTION" | "ALT" => Some(ALT),
+    "CONTROL" | "CTRL" => Some(CONTROL),
+    "COMMAND" | "CMD" | "SUPER" => Some(SUPER),
+    "SHIFT" => Some(SHIFT),
+    "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
+      Some(if cfg!(target_os = "macos") { SUPER } else { CONTROL })
+    }
+    _ => None,
+  }
+}
+
+// 单个字母、数字或符号，F1-F35，小键盘数字，以及 NAMED_KEYS 中的按键
+fn is_key(token: &str) -> bool {
+  let mut chars = token.chars();
+  if let (Some(c), None) = (chars.next(), chars.next()) {
+    return c.is_ascii_alphanumeric() || r"`[],=-.'\;/".contains(c);
+  }
+  let numpad = token.strip_prefix("NUMPAD").or_else(|| token.strip_prefix("NUM"));
+  let function = token.strip_prefix('F').filter(|n| !n.starts_with('0')).and_then(|n| n.parse::<u8>().ok());
+  NAMED_KEYS.contains(&token)
+    || matches!(numpad, Some(n) if n.len() == 1 && n.as_bytes()[0].is_ascii_digit())
+    || matches!(function, Some(1..=35))
+}
+
+// 主键的规范名称：同一按键的不同写法取同一个名称。`\` 在 tao 中是 IntlBackslash，与 BACKSLASH 不是同一个键
+fn key_name(token: &str) -> Option<String> {
+  let token = token.to_ascii_uppercase();
+  if !is_key(&token) {
+    return None;
+  }
+  let name = match token.as_str() {
+    "`" => "BACKQUOTE",
+    "[" => "BRACKETLEFT",
+    "]" => "BRACKETRIGHT",
+    "," => "COMMA",
+    "." => "PERIOD",
+    "'" => "QUOTE",
+    ";" => "SEMICOLON",
+    "/" => "SLASH",
+    "ESCAPE" => "ESC",
+    other => {
+      let other = other.strip_prefix("ARROW").unwrap_or(other);
+      return Some(match other.strip_prefix("NUMPAD") {
+        Some(rest) => format!("NUM{}", rest),
+        None => other.to_string(),
+      });
+    }
+  };
+  Some(name.to_string())
+}
+
+/// 按 Tauri 的加速键格式解析：若干修饰键加一个主键，以 `+` 连接，如 `CmdOrCtrl+Shift+F`
+pub fn parse_accelerator(accelerator: &str) -> Result<Accelerator, String> {
+  let tokens: Vec<_> = accelerator.split('+').map(str::trim).collect();
+  if tokens.iter().any(|token| token.is_empty()) {
+    return Err(format!("\"{}\" has an empty key", accelerator));
+  }
+  let (key, modifiers) = tokens.split_last().expect("split always yields a token");
+  let mut mask = 0;
+  for token in modifiers {
+    mask |= modifier(token).ok_or_else(|| format!("\"{}\" in \"{}\" is not a modifier", token, accelerator))?;
+  }
+  let key = key_name(key).ok_or_else(|| format!("\"{}\" in \"{}\" is not a key", key, accelerator))?;
+  Ok(Accelerator { modifiers: mask, key })
+}
+
+/// 启用的绑定必须是有效的加速键，也不能与其他启用的绑定是同一组合（如 `Ctrl+Shift+F` 与 `shift+control+f`）
+pub fn validate(bindings: &[ShortcutBinding]) -> Result<(), String> {
+  let enabled: Vec<_> = bindings.iter().filter(|b| b.enabled).collect();
+  let mut parsed: Vec<Accelerator> = Vec::with_capacity(enabled.len());
+  for binding in &enabled {
+    if binding.accelerator.is_empty() {
+      return Err(format!("shortcut for {:?} is empty", binding.action));
+    }
+    let accelerator = parse_accelerator(&binding.accelerator)
+      .map_err(|error| format!("shortcut for {:?} is invalid: {}", binding.action, error))?;
+    if let Some(i) = parsed.iter().position(|other| *other == accelerator) {
+      return Err(format!(
+        "{} is bound to both {:?} and {:?}",
+        binding.accelerator, enabled[i].action, binding.action
+      ));
+    }
+    parsed.push(accelerator);
+  }
+  Ok(())
+}
+
+/// 读取保存的绑定；未保存或无法读取、解析时使用默认绑定，保存新绑定时会整体覆盖
+pub fn load(db: &Database) -> Vec<ShortcutBinding> {
+  let bindings = db
+    .get_setting(SETTINGS_KEY)
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok());
+  normalize(bindings.unwrap_or_default())
+}
+
+pub fn save(db: &Database, bindings: &[ShortcutBinding]) -> DbResult<()> {
+  let json = serde_json::to_string(bindings).expect("shortcut bindings are always serializable");
+  db.save_setting(SETTINGS_KEY, &json)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn binding(action: ShortcutAction, accelerator: &str) -> ShortcutBinding {
+    ShortcutBinding {
+      action,
+      accelerator: accelerator.to_string(),
+      enabled: true,
+    }
+  }
+
+  #[test]
+  fn normalize_fills_defaults_in_order() {
+    let bindings = normalize(vec![
+      binding(ShortcutAction::Skip, " Alt+S "),
+      binding(ShortcutAction::ToggleWindow, "Alt+F"),
+      binding(ShortcutAction::ToggleWindow, "Alt+W"),
+    ]);
+    let actions: Vec<_> = bindings.iter().map(|b| b.action).collect();
+    assert_eq!(actions, ShortcutAction::ALL);
+    assert_eq!(bindings[0].accelerator, "Alt+W");
+    assert_eq!(bindings[2].accelerator, "Alt+S");
+    assert_eq!(bindings[1], ShortcutBinding::default_for(ShortcutAction::StartPause));
+    assert!(validate(&default_bindings()).is_ok());
+  }
+
+  #[test]
+  fn validate_rejects_duplicates_and_empty() {
+    let mut bindings = default_bindings();
+    bindings[1].accelerator = "cmdorctrl+shift+f".to_string();
+    assert!(validate(&bindings).unwrap_err().contains("ToggleWindow"));
+
+    // 禁用的绑定不参与检查
+    bindings[1].enabled = false;
+    assert!(validate(&bindings).is_ok());
+    bindings[2].accelerator.clear();
+    assert!(validate(&bindings).is_err());
+  }
+
+  #[test]
+  fn validate_compares_parsed_accelerators() {
+    let mut bindings = default_bindings();
+    bindings[0].accelerator = "Ctrl+Shift+F".to_string();
+    bindings[1].accelerator = "Shift + control+f".to_string();
+    assert!(validate(&bindings).unwrap_err().contains("ToggleWindow"));
+
+    // CmdOrCtrl 在 macOS 上是 Command，其他平台上是 Ctrl
+    bindings[1].accelerator = "CmdOrCtrl+Shift+F".to_string();
+    assert_eq!(validate(&bindings).is_err(), !cfg!(target_os = "macos"));
+
+    bindings[1].accelerator = "Alt+ArrowUp".to_string();
+    bindings[2].accelerator = "Option+Up".to_string();
+    assert!(validate(&bindings).is_err());
+    bindings[2].accelerator = "Alt+Numpad1".to_string();
+    bindings[3].accelerator = "Alt+Num1".to_string();
+    assert!(validate(&bindings).is_err());
+
+    // `\` 与 Backslash 在 tao 中是两个不同的键
+    bindings[2].accelerator = "Alt+\\".to_string();
+    bindings[3].accelerator = "Alt+Backslash".to_string();
+    assert!(validate(&bindings).is_ok());
+  }
+
+  #[test]
+  fn validate_parses_accelerators() {
+    for accelerator in ["CmdOrCtrl+Shift+Space", "super+ctrl+SHIFT+alt+Up", "Alt+F12", "F5", "Ctrl+Numpad3", "Ctrl+/"] {
+      assert!(parse_accelerator(accelerator).is_ok(), "{}", accelerator);
+    }
+    for accelerator in ["Ctrl+Shift", "Ctrl+Foo", "Ctrl++F", "F+Ctrl", "Ctrl+Shift+C+A", "Ctrl+F36", "Hyper+F"] {
+      assert!(parse_accelerator(accelerator).is_err(), "{}", accelerator);
+    }
+
+    let mut bindings = default_bindings();
+    bindings[2].accelerator = "Ctrl+Shift+Skip".to_string();
+    assert!(validate(&bindings).unwrap_err().contains("Skip"));
+  }
+
+  #[test]
+  fn bindings_round_trip_through_settings() {
+    let db = Database::open_in_memory().unwrap();
+    assert_eq!(load(&db), default_bindings());
+
+    let mut bindings = default_bindings();
+    bindings[3] = ShortcutBinding {
+      action: ShortcutAction::MicroBreak,
+      accelerator: "Alt+M".to_string(),
+      enabled: false,
+    };
+    save(&db, &bindings).unwrap();
+    assert_eq!(load(&db), bindings);
+
+    db.save_setting(SETTINGS_KEY, "not json").unwrap();
+    assert_eq!(load(&db), default_bindings());
+    db.save_setting(SETTINGS_KEY, r#"[{"action":"skip","accelerator":"Alt+K"}]"#).unwrap();
+    assert_eq!(load(&db)[2], binding(ShortcutAction::Skip, "Alt+K"));
+  }
+}
@@ -0,0 +1,732 @@
+// 后端计时器
+// 计时在 Tauri 进程中进行，不受窗口隐藏时 webview 定时器节流的影响；
+// 计时线程在每次显示变化和阶段切换时通过回调广播状态，所有窗口与托盘共用同一份数据
+
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+use chrono::TimeZone as _;
+use serde::Serialize;
+
+use crate::timer_core::{
+  Clock, ClockEvent, CountMode, CyclePolicy, DayCalendar, MicroBreakScheduler, MonotonicClock, Program, RunStatus,
+  SmartScheduler, TickPrecision, TimeZone, Timer, TimerEvent, TimerPhase, TimerSnapshot, Visibility, PRESETS,
+};
+use crate::timer_settings::{TimerMode, TimerSettings};
+
+pub const TICK_EVENT: &str = "timer://tick";
+pub const TRANSITION_EVENT: &str = "timer://transition";
+pub const NOTICE_EVENT: &str = "timer://notice";
+
+// 循环策略不给出微休息时长，与前端默认设置一致
+pub const MICRO_BREAK_DURATION: u32 = 3 * 60;
+
+// 系统时区，智能模式按本地小时调整专注时长
+struct LocalZone;
+
+impl TimeZone for LocalZone {
+  fn offset_minutes(&self, utc_ms: i64) -> i32 {
+    chrono::Local
+      .timestamp_millis_opt(utc_ms)
+      .single()
+      .map_or(0, |time| time.offset().local_minus_utc() / 60)
+  }
+}
+
+fn local_hour(wall_ms: u64) -> u8 {
+  DayCalendar::new(LocalZone, 0).local_hour(wall_ms as i64)
+}
+
+/// 广播给前端的计时状态，每次显示变化时发送一次
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerTick {
+  pub state: TimerPhase,
+  pub running: bool,
+  // 显示的秒数：倒计时为剩余，正计时为已计时
+  pub time: u32,
+  pub formatted_time: String,
+  pub remaining: u32,
+  pub duration: u32,
+  pub progress: f64,
+  pub cycle: u32,
+  pub cycles_per_set: u32,
+  pub remaining_ms: u64,
+  // 预计结束时间（Unix 毫秒），暂停期间随之后移
+  pub ends_at: u64,
+  pub overtime_ms: u64,
+  pub mode: TimerMode,
+  // 正在使用的计时方案，按循环策略运行时为 None
+  pub preset: Option<&'static str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransitionReason {
+  /// 阶段倒计时归零后自动进入下一阶段
+  Completed,
+  Skipped,
+  /// 当前阶段从头开始
+  Reset,
+  /// 手动切换到指定阶段
+  Switched,
+  /// 到达计划的微休息或强制休息时间
+  Scheduled,
+}
+
+/// 一次阶段切换
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerTransition {
+  pub from: TimerPhase,
+  pub to: TimerPhase,
+  pub duration: u32,
+  pub reason: TransitionReason,
+}
+
+/// 不改变阶段的计时提醒
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TimerNotice {
+  /// 正计时的专注段到达目标时长，继续计时
+  TargetReached,
+  /// 超时记录中每隔一段时间提醒一次，`overtime` 为已超时的秒数
+  OvertimeReminder { overtime: u32 },
+  /// 系统时间被调整，`delta_ms` 为正表示向后调
+  ClockJump { delta_ms: i64 },
+  /// 系统从休眠中恢复
+  Suspended { gap_ms: u64 },
+}
+
+/// 一次刷新的结果：最新状态，以及此前发生的阶段切换与提醒
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimerUpdate {
+  pub tick: TimerTick,
+  pub transitions: Vec<TimerTransition>,
+  pub notices: Vec<TimerNotice>,
+}
+
+struct Inner<C: Clock> {
+  timer: Timer<C>,
+  mode: TimerMode,
+  preset: Option<&'static str>,
+  // 智能模式下由调度器决定下一阶段及时长
+  smart: Option<SmartScheduler>,
+  // 为每个专注段安排微休息，心流模式或关闭微休息时为 None
+  micro_breaks: Option<MicroBreakScheduler>,
+  micro_break_duration: u32,
+  // 微休息期间暂停的专注段及其是否在运行，微休息结束后从暂停处继续
+  suspended_focus: Option<(TimerSnapshot, bool)>,
+  // 命令改变了状态，计时线程应立即广播而不是等到下一次刷新
+  dirty: bool,
+  // 尚未广播的阶段切换与提醒
+  transitions: Vec<TimerTransition>,
+  notices: Vec<TimerNotice>,
+}
+
+impl<C: Clock + Clone> Inner<C> {
+  fn tick(&mut self) -> TimerTick {
+    let calc = self.timer.update();
+    TimerTick {
+      state: calc.state,
+      running: calc.status == RunStatus::Running,
+      time: calc.time,
+      formatted_time: calc.formatted_time,
+      remaining: calc.remaining,
+      duration: self.timer.duration(),
+      progress: calc.progress,
+      cycle: calc.cycle,
+      cycles_per_set: calc.cycles_per_set,
+      remaining_ms: calc.remaining_ms,
+      ends_at: calc.ends_at,
+      overtime_ms: calc.overtime_ms,
+      mode: self.mode,
+      preset: self.preset,
+    }
+  }
+
+  // 刷新计时并处理期间产生的事件：到点的阶段切换直接执行，其余转为提醒。
+  // 同一批事件中已经切换过阶段时，剩下的阶段事件属于旧阶段，不再处理
+  fn refresh(&mut self) -> TimerUpdate {
+    self.timer.update();
+    let mut switched = false;
+    for event in self.timer.drain_events() {
+      match event {
+        TimerEvent::Completed { .. } | TimerEvent::MicroBreakDue { .. } | TimerEvent::ForcedBreakDue if switched => {}
+        TimerEvent::Completed { state } => {
+          // 开启超时记录的专注段归零后继续计时，由超时提醒通知
+          if !(state == TimerPhase::Focus && self.timer.overtime_policy().enabled()) {
+            self.advance(TransitionReason::Completed);
+            switched = true;
+          }
+        }
+        TimerEvent::MicroBreakDue { duration } => {
+          self.start_micro_break(duration, TransitionReason::Scheduled);
+          switched = true;
+        }
+        TimerEvent::ForcedBreakDue => {
+          let duration = self.duration_for(TimerPhase::ForcedBreak);
+          self.switch(TransitionReason::Scheduled, |timer| timer.reset(duration, TimerPhase::ForcedBreak));
+          switched = true;
+        }
+        TimerEvent::TargetReached => self.notices.push(TimerNotice::TargetReached),
+        TimerEvent::OvertimeReminder { overtime } => self.notices.push(TimerNotice::OvertimeReminder { overtime }),
+        TimerEvent::ClockJumpDetected(ClockEvent::ClockJump { delta_ms }) => {
+          self.notices.push(TimerNotice::ClockJump { delta_ms })
+        }
+        TimerEvent::ClockJumpDetected(ClockEvent::SuspendGap { gap_ms }) => {
+          self.notices.push(TimerNotice::Suspended { gap_ms })
+        }
+        // 阶段开始与切换已由 transition 表达，暂停与继续体现在 tick 中
+        TimerEvent::Started { .. } | TimerEvent::StateChanged { .. } | TimerEvent::Paused | TimerEvent::Resumed => {}
+      }
+    }
+    self.dirty = false;
+    TimerUpdate {
+      tick: self.tick(),
+      transitions: std::mem::take(&mut self.transitions),
+      notices: std::mem::take(&mut self.notices),
+    }
+  }
+
+  // 指定阶段的默认时长：智能模式按当前小时计算，其余按循环策略
+  fn duration_for(&self, phase: TimerPhase) -> u32 {
+    match (&self.smart, phase) {
+      (_, TimerPhase::MicroBreak) => self.micro_break_duration,
+      (Some(smart), phase) => smart.duration_for(phase, local_hour(self.timer.clock().wall_ms())),
+      (None, phase) => self.timer.policy().duration_for(phase),
+    }
+  }
+
+  // 结束当前阶段：微休息回到暂停的专注段；智能模式（未使用计时方案时）由调度器决定下一阶段，否则取计时器的建议
+  fn advance(&mut self, reason: TransitionReason) {
+    if self.resume_focus(reason) {
+      return;
+    }
+    let next = match (&mut self.smart, self.preset) {
+      (Some(smart), None) => {
+        let hour = local_hour(self.timer.clock().wall_ms());
+        Some(smart.complete(self.timer.state(), self.timer.duration(), hour))
+      }
+      _ => None,
+    };
+    self.switch(reason, |timer| match next {
+      Some(next) => timer.reset(next.duration, next.phase),
+      None => {
+        timer.advance();
+      }
+    });
+  }
+
+  // 暂停专注段开始微休息；不在专注中时直接开始微休息
+  fn start_micro_break(&mut self, duration: u32, reason: TransitionReason) {
+    let suspended = (self.timer.state() == TimerPhase::Focus).then(|| {
+      let running = self.timer.status() == RunStatus::Running;
+      self.timer.pause();
+      (self.timer.snapshot(), running)
+    });
+    self.switch(reason, |timer| timer.reset(duration, TimerPhase::MicroBreak));
+    if suspended.is_some() {
+      self.suspended_focus = suspended;
+    }
+  }
+
+  // 微休息结束时恢复暂停的专注段，剩余时间与微休息安排不变；没有暂停的专注段时返回 false
+  fn resume_focus(&mut self, reason: TransitionReason) -> bool {
+    if self.timer.state() != TimerPhase::MicroBreak {
+      return false;
+    }
+    let (snapshot, running) = match self.suspended_focus.take() {
+      Some(suspended) => suspended,
+      None => return false,
+    };
+    let from = self.timer.state();
+    let mut timer = Timer::restore(self.timer.clock().clone(), snapshot);
+    // 微休息期间可能更新了设置，以当前设置为准
+    timer.set_policy(self.timer.policy());
+    timer.set_count_mode(self.timer.count_mode());
+    timer.set_flow_policy(self.timer.flow_policy());
+    if running {
+      timer.resume();
+    }
+    self.timer = timer;
+    self.transitions.push(TimerTransition {
+      from,
+      to: TimerPhase::Focus,
+      duration: self.timer.duration(),
+      reason,
+    });
+    true
+  }
+
+  // 切换计时模式，并按新模式的设置生成策略与调度器；计时方案随之取消
+  fn set_mode(&mut self, settings: &TimerSettings) {
+    let hour = local_hour(self.timer.clock().wall_ms());
+    let seed = self.timer.clock().wall_ms();
+    let smart = SmartScheduler::new(settings.smart.clone());
+    let (policy, micro_breaks) = match settings.mode {
+      TimerMode::Classic => (settings.classic.policy(), Some(settings.classic.micro_breaks())),
+      TimerMode::Smart => {
+        let break_duration = smart.duration_for(TimerPhase::Break, hour);
+        let policy = CyclePolicy {
+          focus_duration: smart.duration_for(TimerPhase::Focus, hour),
+          break_duration,
+          long_break_duration: break_duration,
+          long_break_interval: 0,
+          forced_break_threshold: settings.smart.forced_break_threshold * 60,
+          forced_break_duration: smart.duration_for(TimerPhase::ForcedBreak, hour),
+        };
+        (policy, settings.smart_micro_breaks())
+      }
+      TimerMode::Flow => (settings.flow.policy(), None),
+    };
+
+    self.mode = settings.mode;
+    self.preset = None;
+    self.timer.set_program(None);
+    self.timer.set_policy(policy);
+    self.timer.set_flow_policy(settings.flow.flow_policy());
+    self.timer.set_count_mode(match settings.mode {
+      TimerMode::Flow => CountMode::Up,
+      TimerMode::Classic | TimerMode::Smart => CountMode::Down,
+    });
+    self.micro_break_duration = match settings.mode {
+      TimerMode::Smart => smart.duration_for(TimerPhase::MicroBreak, hour),
+      TimerMode::Classic | TimerMode::Flow => settings.classic.micro_break_duration * 60,
+    };
+    self.micro_breaks = micro_breaks.map(|config| MicroBreakScheduler::new(config, seed));
+    self.smart = (settings.mode == TimerMode::Smart).then_some(smart);
+  }
+
+  // 进入专注段时按微休息设置安排本段的微休息；离开微休息时放弃暂停的专注段
+  fn switch<F>(&mut self, reason: TransitionReason, change: F)
+  where
+    F: FnOnce(&mut Timer<C>),
+  {
+    let from = self.timer.state();
+    change(&mut self.timer);
+    if self.timer.state() != TimerPhase::MicroBreak {
+      self.suspended_focus = None;
+    }
+    if self.timer.state() == TimerPhase::Focus {
+      if let Some(scheduler) = &mut self.micro_breaks {
+        self.timer.set_micro_breaks(scheduler.plan(self.timer.duration()));
+      }
+    }
+    self.transitions.push(TimerTransition {
+      from,
+      to: self.timer.state(),
+      duration: self.timer.duration(),
+      reason,
+    });
+  }
+
+  // 手动切换不改变运行状态：暂停中切换后仍然暂停
+  fn switch_keeping_status<F>(&mut self, reason: TransitionReason, change: F)
+  where
+    F: FnOnce(&mut Timer<C>),
+  {
+    let paused = self.timer.status() == RunStatus::Paused;
+    self.switch(reason, change);
+    if paused {
+      self.timer.pause();
+    }
+  }
+}
+
+struct Shared<C: Clock> {
+  inner: Mutex<Inner<C>>,
+  wake: Condvar,
+}
+
+/// 作为 Tauri 托管状态共享的计时器
+pub struct TimerService<C: Clock = MonotonicClock> {
+  shared: Arc<Shared<C>>,
+}
+
+impl TimerService<MonotonicClock> {
+  pub fn new(policy: CyclePolicy) -> TimerService<MonotonicClock> {
+    TimerService::with_clock(MonotonicClock::new(), policy)
+  }
+}
+
+impl<C: Clock + Clone + Send + 'static> TimerService<C> {
+  /// 以暂停状态停在一个完整的专注段开头
+  pub fn with_clock(clock: C, policy: CyclePolicy) -> TimerService<C> {
+    let mut timer = Timer::new(clock, policy.focus_duration, TimerPhase::Focus);
+    timer.set_policy(policy);
+    timer.pause();
+    timer.drain_events();
+    TimerService {
+      shared: Arc::new(Shared {
+        inner: Mutex::new(Inner {
+          timer,
+          mode: TimerMode::Classic,
+          preset: None,
+          smart: None,
+          micro_breaks: None,
+          micro_break_duration: MICRO_BREAK_DURATION,
+          suspended_focus: None,
+          dirty: false,
+          transitions: Vec::new(),
+          notices: Vec::new(),
+        }),
+        wake: Condvar::new(),
+      }),
+    }
+  }
+
+  /// 启动计时线程。`publish` 在每次显示变化时收到最新状态，以及此前发生的阶段切换与提醒
+  pub fn spawn<F>(&self, mut publish: F)
+  where
+    F: FnMut(TimerUpdate) + Send + 'static,
+  {
+    let shared = Arc::clone(&self.shared);
+    thread::spawn(move || loop {
+      let update = lock(&shared.inner).refresh();
+      // 广播时不持有锁，前端收到事件后可以立即调用命令
+      publish(update);
+
+      let inner = lock(&shared.inner);
+      let next = inner.timer.next_tick_ms(TickPrecision::Seconds, Visibility::Visible);
+      let idle = |inner: &mut Inner<C>| !inner.dirty;
+      // 暂停时显示不会变化，只等命令唤醒
+      match next {
+        Some(ms) => drop(shared.wake.wait_timeout_while(inner, Duration::from_millis(ms), idle)),
+        None => drop(shared.wake.wait_while(inner, idle)),
+      }
+    });
+  }
+
+  pub fn status(&self) -> TimerTick {
+    lock(&self.shared.inner).tick()
+  }
+
+  /// 开始或继续计时
+  pub fn start(&self) -> TimerTick {
+    self.apply(|inner| inner.timer.resume())
+  }
+
+  pub fn pause(&self) -> TimerTick {
+    self.apply(|inner| {
+      inner.timer.pause();
+    })
+  }
+
+  /// 运行中则暂停，否则开始；托盘与快捷键共用
+  pub fn toggle(&self) -> TimerTick {
+    self.apply(|inner| {
+      if inner.timer.status() == RunStatus::Running {
+        inner.timer.pause();
+      } else {
+        inner.timer.resume();
+      }
+    })
+  }
+
+  /// 立即开始一次微休息；专注中时专注段暂停，微休息结束或跳过后以剩余时间继续
+  pub fn micro_break(&self) -> TimerTick {
+    self.apply(|inner| {
+      let duration = inner.micro_break_duration;
+      inner.start_micro_break(duration, TransitionReason::Switched)
+    })
+  }
+
+  /// 结束当前阶段，进入建议的下一阶段
+  pub fn skip(&self) -> TimerTick {
+    self.apply(|inner| {
+      let paused = inner.timer.status() == RunStatus::Paused;
+      inner.advance(TransitionReason::Skipped);
+      if paused {
+        inner.timer.pause();
+      }
+    })
+  }
+
+  /// 当前阶段从头开始，并暂停在开头
+  pub fn reset(&self) -> TimerTick {
+    self.apply(|inner| {
+      inner.switch(TransitionReason::Reset, |timer| timer.reset(timer.duration(), timer.state()));
+      inner.timer.pause();
+    })
+  }
+
+  /// 切换到指定阶段；未给出时长时取当前模式下的时长
+  pub fn switch_phase(&self, phase: TimerPhase, duration: Option<u32>) -> Result<TimerTick, String> {
+    if phase == TimerPhase::Idle {
+      return Err("cannot switch to idle".to_string());
+    }
+    let duration = duration.unwrap_or_else(|| lock(&self.shared.inner).duration_for(phase));
+    if duration == 0 {
+      return Err("duration must be positive".to_string());
+    }
+    Ok(self.apply(|inner| {
+      inner.switch_keeping_status(TransitionReason::Switched, |timer| timer.reset(duration, phase))
+    }))
+  }
+
+  /// 按设置切换计时模式（经典、智能、心流），并暂停在新模式的一个完整专注段开头
+  pub fn switch_mode(&self, settings: &TimerSettings) -> TimerTick {
+    self.apply(|inner| {
+      inner.set_mode(settings);
+      let duration = inner.duration_for(TimerPhase::Focus);
+      inner.switch(TransitionReason::Switched, |timer| timer.reset(duration, TimerPhase::Focus));
+      inner.timer.pause();
+    })
+  }
+
+  /// 更新当前模式的设置，时长从下一次阶段切换开始生效
+  pub fn update_settings(&self, settings: &TimerSettings) -> TimerTick {
+    self.apply(|inner| inner.set_mode(settings))
+  }
+
+  /// 按内置计时方案运行，当前阶段按方案中的时长从头开始；`None` 恢复为循环策略
+  pub fn use_preset(&self, name: Option<&str>) -> Result<TimerTick, String> {
+    let preset = match name {
+      Some(name) => {
+        let (preset, _) = PRESETS
+          .iter()
+          .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+          .ok_or_else(|| format!("unknown preset: {}", name))?;
+        Some((*preset, Program::preset(preset).expect("built-in presets are valid")))
+      }
+      None => None,
+    };
+    Ok(self.apply(|inner| {
+      inner.timer.set_program(preset.as_ref().map(|(_, program)| program));
+      inner.preset = preset.map(|(name, _)| name);
+      let duration = match inner.timer.program().get(inner.timer.program_position()) {
+        Some(step) if step.phase == inner.timer.state() => step.duration,
+        _ => inner.timer.policy().duration_for(inner.timer.state()),
+      };
+      if duration > 0 {
+        inner.switch_keeping_status(TransitionReason::Switched, |timer| timer.reset(duration, timer.state()));
+      }
+    }))
+  }
+
+  /// 替换循环策略，从下一次阶段切换开始生效
+  pub fn set_policy(&self, policy: CyclePolicy) -> TimerTick {
+    self.apply(|inner| inner.timer.set_policy(policy))
+  }
+
+  // 修改状态后唤醒计时线程广播，并返回修改后的状态
+  fn apply<F>(&self, change: F) -> TimerTick
+  where
+    F: FnOnce(&mut Inner<C>),
+  {
+    let mut inner = lock(&self.shared.inner);
+    change(&mut inner);
+    inner.dirty = true;
+    self.shared.wake.notify_all();
+    inner.tick()
+  }
+}
+
+// 广播回调 panic 后计时状态本身仍然有效
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::timer_core::FakeClock;
+
+  fn service() -> (TimerService<FakeClock>, FakeClock) {
+    let clock = FakeClock::new(1_000_000);
+    (TimerService::with_clock(clock.clone(), CyclePolicy::pomodoro()), clock)
+  }
+
+  fn refresh(service: &TimerService<FakeClock>) -> TimerUpdate {
+    lock(&service.shared.inner).refresh()
+  }
+
+  fn settings(mode: TimerMode) -> TimerSettings {
+    let mut settings = TimerSettings { mode, ..TimerSettings::default() };
+    // 关闭按小时的调整，时长不随测试运行的时间变化
+    settings.smart.enable_circadian_optimization = false;
+    settings
+  }
+
+  #[test]
+  fn starts_paused_and_completes_into_break() {
+    let (service, clock) = service();
+    let tick = service.status();
+    assert_eq!((tick.state, tick.running, tick.time), (TimerPhase::Focus, false, 25 * 60));
+
+    service.start();
+    clock.advance(25 * 60 * 1000);
+    let TimerUpdate { tick, transitions, notices } = refresh(&service);
+    assert!(notices.is_empty());
+    assert_eq!(
+      transitions,
+      vec![TimerTransition {
+        from: TimerPhase::Focus,
+        to: TimerPhase::Break,
+        duration: 5 * 60,
+        reason: TransitionReason::Completed,
+      }]
+    );
+    assert_eq!((tick.state, tick.running, tick.remaining), (TimerPhase::Break, true, 5 * 60));
+    assert!(!lock(&service.shared.inner).dirty);
+  }
+
+  #[test]
+  fn manual_changes_keep_run_status() {
+    let (service, clock) = service();
+    let tick = service.skip();
+    assert_eq!((tick.state, tick.running), (TimerPhase::Break, false));
+
+    service.start();
+    clock.advance(60_000);
+    let tick = service.switch_phase(TimerPhase::MicroBreak, None).unwrap();
+    assert_eq!((tick.state, tick.running, tick.duration), (TimerPhase::MicroBreak, true, MICRO_BREAK_DURATION));
+    assert!(service.switch_phase(TimerPhase::Idle, None).is_err());
+
+    clock.advance(30_000);
+    assert!(!service.toggle().running);
+    let tick = service.reset();
+    assert_eq!((tick.state, tick.running, tick.time), (TimerPhase::MicroBreak, false, MICRO_BREAK_DURATION));
+
+    let reasons: Vec<_> = refresh(&service).transitions.into_iter().map(|t| t.reason).collect();
+    assert_eq!(reasons, vec![TransitionReason::Skipped, TransitionReason::Switched, TransitionReason::Reset]);
+  }
+
+  #[test]
+  fn presets_drive_durations() {
+    let (service, clock) = service();
+    let tick = service.use_preset(Some("52-17")).unwrap();
+    assert_eq!((tick.preset, tick.state, tick.time, tick.running), (Some("52-17"), TimerPhase::Focus, 52 * 60, false));
+    assert!(service.use_preset(Some("unknown")).is_err());
+
+    service.start();
+    clock.advance(52 * 60 * 1000);
+    let tick = refresh(&service).tick;
+    assert_eq!((tick.state, tick.remaining), (TimerPhase::Break, 17 * 60));
+
+    let tick = service.use_preset(None).unwrap();
+    assert_eq!((tick.preset, tick.state, tick.time), (None, TimerPhase::Break, 5 * 60));
+
+    service.pause();
+    let tick = service.micro_break();
+    assert_eq!((tick.state, tick.running, tick.time), (TimerPhase::MicroBreak, true, MICRO_BREAK_DURATION));
+  }
+
+  #[test]
+  fn switch_mode_loads_settings() {
+    let (service, clock) = service();
+    let mut classic = settings(TimerMode::Classic);
+    classic.classic.focus_duration = 30;
+    service.start();
+    let tick = service.switch_mode(&classic);
+    assert_eq!((tick.mode, tick.state, tick.running, tick.time), (TimerMode::Classic, TimerPhase::Focus, false, 30 * 60));
+
+    let tick = service.switch_mode(&settings(TimerMode::Smart));
+    assert_eq!((tick.mode, tick.time), (TimerMode::Smart, 90 * 60));
+    let tick = service.skip();
+    assert_eq!((tick.state, tick.duration), (TimerPhase::Break, 20 * 60));
+
+    // 心流模式的专注段正计时，结束时按实际专注时长建议休息
+    service.switch_mode(&settings(TimerMode::Flow));
+    service.start();
+    clock.advance(60 * 60 * 1000);
+    let tick = service.status();
+    assert_eq!((tick.mode, tick.state, tick.time), (TimerMode::Flow, TimerPhase::Focus, 60 * 60));
+    let tick = service.skip();
+    assert_eq!((tick.state, tick.duration), (TimerPhase::Break, 12 * 60));
+  }
+
+  #[test]
+  fn scheduled_events_switch_phases() {
+    let (service, clock) = service();
+    // 经典模式默认每专注 10 分钟一次 3 分钟微休息
+    service.switch_mode(&settings(TimerMode::Classic));
+    service.start();
+    refresh(&service);
+    clock.advance(10 * 60 * 1000);
+    let update = refresh(&service);
+    assert_eq!(
+      update.transitions,
+      vec![TimerTransition {
+        from: TimerPhase::Focus,
+        to: TimerPhase::MicroBreak,
+        duration: 3 * 60,
+        reason: TransitionReason::Scheduled,
+      }]
+    );
+
+    let mut smart = settings(TimerMode::Smart);
+    smart.smart.enable_micro_breaks = false;
+    smart.smart.forced_break_threshold = 60;
+    service.switch_mode(&smart);
+    service.start();
+    clock.advance(60 * 60 * 1000);
+    let update = refresh(&service);
+    assert_eq!(update.transitions.len(), 2);
+    assert_eq!(update.tick.state, TimerPhase::ForcedBreak);
+    assert_eq!(update.transitions[1].reason, TransitionReason::Scheduled);
+  }
+
+  #[test]
+  fn other_events_become_notices() {
+    let (service, clock) = service();
+    let mut flow = settings(TimerMode::Flow);
+    flow.flow.target_duration = 1;
+    service.switch_mode(&flow);
+    service.start();
+    refresh(&service);
+
+    clock.advance(60_000);
+    let update = refresh(&service);
+    assert_eq!((update.tick.state, update.tick.running), (TimerPhase::Focus, true));
+    assert!(update.transitions.is_empty());
+    assert_eq!(update.notices, vec![TimerNotice::TargetReached]);
+
+    clock.jump_wall(-10 * 60 * 1000);
+    assert_eq!(refresh(&service).notices, vec![TimerNotice::ClockJump { delta_ms: -10 * 60 * 1000 }]);
+    // 事件只转换一次
+    assert!(refresh(&service).notices.is_empty());
+
+    clock.suspend(10 * 60 * 1000);
+    assert_eq!(refresh(&service).notices, vec![TimerNotice::Suspended { gap_ms: 10 * 60 * 1000 }]);
+  }
+
+  #[test]
+  fn micro_break_keeps_the_focus_block() {
+    let (service, clock) = service();
+    service.start();
+    clock.advance(10 * 60 * 1000);
+    let tick = service.micro_break();
+    assert_eq!((tick.state, tick.running, tick.time), (TimerPhase::MicroBreak, true, MICRO_BREAK_DURATION));
+
+    clock.advance(MICRO_BREAK_DURATION as u64 * 1000);
+    let update = refresh(&service);
+    assert_eq!((update.tick.state, update.tick.running, update.tick.remaining), (TimerPhase::Focus, true, 15 * 60));
+    assert_eq!(
+      update.transitions.last(),
+      Some(&TimerTransition {
+        from: TimerPhase::MicroBreak,
+        to: TimerPhase::Focus,
+        duration: 25 * 60,
+        reason: TransitionReason::Completed,
+      })
+    );
+
+    // 跳过微休息同样回到专注段，暂停中开始的微休息结束后专注段仍然暂停
+    clock.advance(5 * 60 * 1000);
+    service.pause();
+    service.micro_break();
+    clock.advance(60_000);
+    let tick = service.skip();
+    assert_eq!((tick.state, tick.running, tick.remaining), (TimerPhase::Focus, false, 10 * 60));
+
+    // 微休息中切换到其他阶段后不再回到原专注段
+    service.micro_break();
+    service.switch_phase(TimerPhase::Break, None).unwrap();
+    service.micro_break();
+    let tick = service.skip();
+    assert_eq!((tick.state, tick.remaining), (TimerPhase::Focus, 25 * 60));
+  }
+}
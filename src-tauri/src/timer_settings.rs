@@ -0,0 +1,190 @@
+// 计时模式设置
+// 前端 UnifiedTimerSettings（types/unifiedTimer.ts）中与计时有关的部分，以 JSON 保存在设置表中；
+// 后端切换模式时按这里的设置生成循环策略、智能调度器与微休息安排，运行方式见 timer.rs
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{Database, DbResult};
+use crate::timer_core::{CyclePolicy, Distribution, FlowPolicy, MicroBreakConfig, SmartSettings};
+
+pub const SETTINGS_KEY: &str = "timer_settings";
+
+// 微休息与专注段开头、结尾保持的最小距离（分钟）
+const MICRO_BREAK_GUARD_MINUTES: u32 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerMode {
+  #[default]
+  Classic,
+  Smart,
+  /// 专注正计时，结束时按专注时长建议休息
+  Flow,
+}
+
+/// 经典模式设置，时长单位为分钟
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClassicSettings {
+  pub focus_duration: u32,
+  pub break_duration: u32,
+  pub micro_break_min_interval: u32,
+  pub micro_break_max_interval: u32,
+  pub micro_break_duration: u32,
+}
+
+impl Default for ClassicSettings {
+  fn default() -> Self {
+    ClassicSettings {
+      focus_duration: 25,
+      break_duration: 5,
+      micro_break_min_interval: 10,
+      micro_break_max_interval: 30,
+      micro_break_duration: 3,
+    }
+  }
+}
+
+impl ClassicSettings {
+  pub fn policy(&self) -> CyclePolicy {
+    CyclePolicy {
+      focus_duration: self.focus_duration.saturating_mul(60),
+      break_duration: self.break_duration.saturating_mul(60),
+      ..CyclePolicy::default()
+    }
+  }
+
+  /// 与前端经典模式一致，微休息按最小间隔安排
+  pub fn micro_breaks(&self) -> MicroBreakConfig {
+    let interval = self.micro_break_min_interval.saturating_mul(60);
+    MicroBreakConfig {
+      distribution: Distribution::Uniform { min: interval, max: interval },
+      min_duration: self.micro_break_duration.saturating_mul(60),
+      max_duration: self.micro_break_duration.saturating_mul(60),
+      guard: MICRO_BREAK_GUARD_MINUTES.saturating_mul(60),
+    }
+  }
+}
+
+/// 心流模式设置，时长单位为分钟
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FlowSettings {
+  // 专注软目标，到达后继续计时
+  pub target_duration: u32,
+  pub break_ratio: f64,
+  pub min_break_duration: u32,
+  pub max_break_duration: u32,
+}
+
+impl Default for FlowSettings {
+  fn default() -> Self {
+    FlowSettings {
+      target_duration: 50,
+      break_ratio: 0.2,
+      min_break_duration: 5,
+      max_break_duration: 30,
+    }
+  }
+}
+
+impl FlowSettings {
+  /// 专注段时长取软目标；休息时长由 `flow_policy` 按实际专注时长决定，这里的只是下限
+  pub fn policy(&self) -> CyclePolicy {
+    CyclePolicy {
+      focus_duration: self.target_duration.saturating_mul(60),
+      break_duration: self.min_break_duration.saturating_mul(60),
+      ..CyclePolicy::default()
+    }
+  }
+
+  pub fn flow_policy(&self) -> FlowPolicy {
+    FlowPolicy {
+      break_ratio: self.break_ratio,
+      min_break: self.min_break_duration.saturating_mul(60),
+      max_break: self.max_break_duration.saturating_mul(60),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimerSettings {
+  pub mode: TimerMode,
+  pub classic: ClassicSettings,
+  pub smart: SmartSettings,
+  pub flow: FlowSettings,
+}
+
+impl TimerSettings {
+  /// 智能模式的微休息安排，关闭微休息时为 None
+  pub fn smart_micro_breaks(&self) -> Option<MicroBreakConfig> {
+    self
+      .smart
+      .enable_micro_breaks
+      .then(|| MicroBreakConfig::from_smart(&self.smart, MICRO_BREAK_GUARD_MINUTES))
+  }
+}
+
+/// 读取保存的设置；未保存或无法解析时使用默认设置，前端保存设置时会整体覆盖
+pub fn load(db: &Database) -> TimerSettings {
+  db.get_setting(SETTINGS_KEY)
+    .ok()
+    .flatten()
+    .and_then(|json| serde_json::from_str(&json).ok())
+    .unwrap_or_default()
+}
+
+pub fn save(db: &Database, settings: &TimerSettings) -> DbResult<()> {
+  let json = serde_json::to_string(settings).expect("timer settings are always serializable");
+  db.save_setting(SETTINGS_KEY, &json)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_frontend_settings_json() {
+    let db = Database::open_in_memory().unwrap();
+    assert_eq!(load(&db), TimerSettings::default());
+
+    // 前端保存的是完整的 UnifiedTimerSettings，多余字段忽略，缺少的字段取默认值
+    db.save_setting(
+      SETTINGS_KEY,
+      r#"{"mode":"flow","classic":{"focusDuration":30},"flow":{"targetDuration":45,"breakRatio":0.25},"soundEnabled":true}"#,
+    )
+    .unwrap();
+    let settings = load(&db);
+    assert_eq!(settings.mode, TimerMode::Flow);
+    assert_eq!(settings.classic.policy().focus_duration, 30 * 60);
+    assert_eq!(settings.classic.break_duration, 5);
+    assert_eq!(settings.flow.policy().focus_duration, 45 * 60);
+    assert_eq!(settings.flow.flow_policy().break_ratio, 0.25);
+
+    db.save_setting(SETTINGS_KEY, "not json").unwrap();
+    assert_eq!(load(&db), TimerSettings::default());
+
+    save(&db, &settings).unwrap();
+    assert_eq!(load(&db), settings);
+  }
+
+  #[test]
+  fn huge_minute_values_saturate() {
+    let classic = ClassicSettings {
+      focus_duration: u32::MAX,
+      micro_break_min_interval: u32::MAX / 2,
+      ..ClassicSettings::default()
+    };
+    assert_eq!(classic.policy().focus_duration, u32::MAX);
+    assert_eq!(classic.micro_breaks().distribution, Distribution::Uniform { min: u32::MAX, max: u32::MAX });
+
+    let flow = FlowSettings { max_break_duration: u32::MAX, ..FlowSettings::default() };
+    assert_eq!(flow.flow_policy().max_break, u32::MAX);
+
+    let mut settings = TimerSettings::default();
+    settings.smart.enable_micro_breaks = true;
+    settings.smart.micro_break_max_duration = u32::MAX;
+    assert_eq!(settings.smart_micro_breaks().unwrap().max_duration, u32::MAX);
+  }
+}
@@ -0,0 +1,221 @@
+// 系统托盘
+// 托盘菜单直接操作后端计时器，提示文字与菜单状态随计时状态刷新；
+// 前端可以通过 set_tray_menu 在内置菜单中追加自己的菜单项，通过 update_tray_tooltip 临时改用自己的提示文字
+
+use std::sync::{Mutex, MutexGuard};
+
+use serde::Deserialize;
+use tauri::{
+  AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem, SystemTraySubmenu,
+};
+
+use crate::timer::{TimerService, TimerTick};
+use crate::timer_core::{TimerPhase, PRESETS};
+
+pub const MENU_CLICK_EVENT: &str = "tray://menu-click";
+
+const MAIN_WINDOW: &str = "main";
+
+// 内置菜单项；前端追加的菜单项使用相同 id 时同样由后端处理
+const TOGGLE_TIMER: &str = "toggle_timer";
+const SKIP: &str = "skip";
+const MICRO_BREAK: &str = "micro_break";
+const TOGGLE_WINDOW: &str = "toggle_window";
+const QUIT: &str = "quit";
+const CLASSIC: &str = "preset:classic";
+const PRESET_PREFIX: &str = "preset:";
+
+/// 前端 `SystemTray.tsx` 追加的菜单项，点击动作留在前端，通过 `tray://menu-click` 事件回传 id
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TrayMenuItem {
+  pub id: String,
+  pub label: String,
+  pub enabled: Option<bool>,
+  pub checked: Option<bool>,
+  pub separator: bool,
+  pub submenu: Option<Vec<TrayMenuItem>>,
+}
+
+/// 托盘的显示状态，作为 Tauri 托管状态
+#[derive(Default)]
+pub struct TrayState {
+  // 上次刷新时的计时状态，菜单文字只在变化时更新
+  last: Mutex<Option<TimerTick>>,
+  // 前端设置的提示文字，清除前代替按计时状态生成的提示
+  tooltip: Mutex<Option<String>>,
+}
+
+pub fn build() -> SystemTray {
+  SystemTray::new().with_menu(menu(&[])).with_tooltip("FocusFlow - 就绪")
+}
+
+// 内置菜单，前端追加的菜单项位于计时控制与窗口控制之间
+fn menu(extra: &[TrayMenuItem]) -> SystemTrayMenu {
+  let presets = PRESETS.iter().fold(
+    SystemTrayMenu::new().add_item(CustomMenuItem::new(CLASSIC, "经典循环").selected()),
+    |menu, (name, _)| menu.add_item(CustomMenuItem::new(format!("{}{}", PRESET_PREFIX, name), *name)),
+  );
+  let mut menu = SystemTrayMenu::new()
+    .add_item(CustomMenuItem::new(TOGGLE_TIMER, "开始"))
+    .add_item(CustomMenuItem::new(SKIP, "跳过当前阶段"))
+    .add_item(CustomMenuItem::new(MICRO_BREAK, "立即微休息"))
+    .add_submenu(SystemTraySubmenu::new("计时方案", presets));
+  if !extra.is_empty() {
+    menu = append_items(menu.add_native_item(SystemTrayMenuItem::Separator), extra);
+  }
+  menu
+    .add_native_item(SystemTrayMenuItem::Separator)
+    .add_item(CustomMenuItem::new(TOGGLE_WINDOW, "隐藏窗口"))
+    .add_item(CustomMenuItem::new(QUIT, "退出"))
+}
+
+fn append_items(menu: SystemTrayMenu, items: &[TrayMenuItem]) -> SystemTrayMenu {
+  items.iter().fold(menu, |menu, item| {
+    if item.separator {
+      return menu.add_native_item(SystemTrayMenuItem::Separator);
+    }
+    if let Some(submenu) = &item.submenu {
+      let submenu = append_items(SystemTrayMenu::new(), submenu);
+      return menu.add_submenu(SystemTraySubmenu::new(item.label.clone(), submenu));
+    }
+    let mut entry = CustomMenuItem::new(item.id.clone(), item.label.clone());
+    if item.enabled == Some(false) {
+      entry = entry.disabled();
+    }
+    if item.checked == Some(true) {
+      entry = entry.selected();
+    }
+    menu.add_item(entry)
+  })
+}
+
+pub fn handle_event(app: &AppHandle, event: SystemTrayEvent) {
+  match event {
+    SystemTrayEvent::LeftClick { .. } => toggle_window(app),
+    SystemTrayEvent::MenuItemClick { id, .. } => handle_menu_click(app, &id),
+    _ => {}
+  }
+}
+
+fn handle_menu_click(app: &AppHandle, id: &str) {
+  let timer = app.state::<TimerService>();
+  match id {
+    TOGGLE_TIMER => {
+      timer.toggle();
+    }
+    SKIP => {
+      timer.skip();
+    }
+    MICRO_BREAK => {
+      timer.micro_break();
+    }
+    CLASSIC => {
+      let _ = timer.use_preset(None);
+    }
+    TOGGLE_WINDOW => toggle_window(app),
+    QUIT => app.exit(0),
+    id => match id.strip_prefix(PRESET_PREFIX) {
+      Some(name) => {
+        let _ = timer.use_preset(Some(name));
+      }
+      None => {
+        let _ = app.emit_all(MENU_CLICK_EVENT, id);
+      }
+    },
+  }
+}
+
+/// 显示或隐藏主窗口
+pub fn toggle_window(app: &AppHandle) {
+  let Some(window) = app.get_window(MAIN_WINDOW) else { return };
+  if window.is_visible().unwrap_or(false) {
+    let _ = window.hide();
+    set_window_item(app, false);
+  } else {
+    show_window(app);
+  }
+}
+
+/// 显示主窗口并获得焦点
+pub fn show_window(app: &AppHandle) {
+  let Some(window) = app.get_window(MAIN_WINDOW) else { return };
+  let _ = window.show();
+  let _ = window.unminimize();
+  let _ = window.set_focus();
+  set_window_item(app, true);
+}
+
+fn set_window_item(app: &AppHandle, visible: bool) {
+  if let Some(item) = app.tray_handle().try_get_item(TOGGLE_WINDOW) {
+    let _ = item.set_title(if visible { "隐藏窗口" } else { "显示窗口" });
+  }
+}
+
+/// 按计时状态刷新提示文字与菜单，由计时线程在每次显示变化时调用
+pub fn update(app: &AppHandle, tick: &TimerTick) {
+  let state = app.state::<TrayState>();
+  let handle = app.tray_handle();
+  let mut last = lock(&state.last);
+
+  if last.as_ref().map(|last| last.running) != Some(tick.running) {
+    if let Some(item) = handle.try_get_item(TOGGLE_TIMER) {
+      let _ = item.set_title(if tick.running { "暂停" } else { "开始" });
+    }
+  }
+  if last.as_ref().map(|last| last.preset) != Some(tick.preset) {
+    if let Some(item) = handle.try_get_item(CLASSIC) {
+      let _ = item.set_selected(tick.preset.is_none());
+    }
+    for (name, _) in PRESETS {
+      if let Some(item) = handle.try_get_item(&format!("{}{}", PRESET_PREFIX, name)) {
+        let _ = item.set_selected(tick.preset == Some(*name));
+      }
+    }
+  }
+
+  let custom = lock(&state.tooltip).clone();
+  let _ = handle.set_tooltip(&custom.unwrap_or_else(|| tooltip(tick)));
+  // macOS 菜单栏直接显示剩余时间
+  #[cfg(target_os = "macos")]
+  let _ = handle.set_title(&tick.formatted_time);
+
+  *last = Some(tick.clone());
+}
+
+/// 设置追加在内置菜单中的菜单项；传入空列表只保留内置菜单
+pub fn set_extra_items(app: &AppHandle, items: &[TrayMenuItem]) -> tauri::Result<()> {
+  app.tray_handle().set_menu(menu(items))?;
+  // 新菜单按当前计时状态重新设置文字与勾选
+  *lock(&app.state::<TrayState>().last) = None;
+  let tick = app.state::<TimerService>().status();
+  update(app, &tick);
+  Ok(())
+}
+
+/// 设置前端的提示文字；传入 None 恢复为按计时状态生成的提示
+pub fn set_tooltip(app: &AppHandle, custom: Option<String>) -> tauri::Result<()> {
+  let text = match &custom {
+    Some(text) => text.clone(),
+    None => tooltip(&app.state::<TimerService>().status()),
+  };
+  *lock(&app.state::<TrayState>().tooltip) = custom;
+  app.tray_handle().set_tooltip(&text)
+}
+
+fn tooltip(tick: &TimerTick) -> String {
+  let phase = match tick.state {
+    TimerPhase::Focus => "专注",
+    TimerPhase::Break => "休息",
+    TimerPhase::MicroBreak => "微休息",
+    TimerPhase::ForcedBreak => "强制休息",
+    TimerPhase::LongBreak => "长休息",
+    TimerPhase::Idle => return "FocusFlow - 就绪".to_string(),
+  };
+  let status = if tick.running { "中" } else { "（已暂停）" };
+  format!("FocusFlow - {}{} {}", phase, status, tick.formatted_time)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+  mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -9,6 +9,9 @@ import { AdapterFactory } from '../adapters/AdapterFactory';
 import { configManager } from '../config/ConfigurationManager';
 import { environmentConfig } from '../config/environment';
 import { initializeStore } from '../store';
+import { wasmTimer } from '../wasm/wasmTimer';
+import { settingsStore } from '../stores/settingsStore';
+import { statsStore } from '../stores/statsStore';
 
 /**
  * 初始化应用
@@ -18,6 +21,10 @@ export async function initializeApp(): Promise<void> {
   try {
     console.log('Starting application initialization...');
 
+    // 计时核心（WASM）加载完成后才能格式化时长、划分日期
+    await wasmTimer.ready();
+    syncDayCalendar();
+
     // 注册所有服务到依赖注入容器
     registerServices();
 
@@ -40,6 +47,29 @@ export async function initializeApp(): Promise<void> {
   }
 }
 
+/**
+ * 让 WASM 日历跟随设置中的时区与每天开始时刻，变化后按新的日期划分重新汇总统计
+ */
+function syncDayCalendar(): void {
+  const apply = (timeZone: string, dayStartHour: number) => {
+    if (!wasmTimer.setDayCalendar(timeZone, dayStartHour * 60)) {
+      console.warn(`Invalid time zone: ${timeZone}. Using system time zone`);
+      wasmTimer.setDayCalendar('', dayStartHour * 60);
+    }
+    statsStore.getState().loadStats();
+  };
+
+  const { timeZone, dayStartHour } = settingsStore.getState().settings;
+  apply(timeZone, dayStartHour);
+
+  settingsStore.subscribe((state, previous) => {
+    const { timeZone, dayStartHour } = state.settings;
+    if (timeZone !== previous.settings.timeZone || dayStartHour !== previous.settings.dayStartHour) {
+      apply(timeZone, dayStartHour);
+    }
+  });
+}
+
 /**
  * 初始化配置管理器
  */
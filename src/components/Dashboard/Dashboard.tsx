@@ -20,7 +20,7 @@ import {
 } from 'lucide-react';
 import { useUnifiedTimerStore } from '../../stores/unifiedTimerStore';
 import { useStatsStore } from '../../stores/statsStore';
-import { formatTimeShort } from '../../utils/formatTime';
+import { wasmTimer, DurationFormat } from '../../wasm/wasmTimer';
 import PerformanceMonitor from '../Performance/PerformanceMonitor';
 
 interface DashboardProps {
@@ -66,12 +66,7 @@ const Dashboard: React.FC<DashboardProps> = ({ className = '' }) => {
   };
 
   // 格式化时间
-  const formatTime = (milliseconds: number) => {
-    const totalSeconds = Math.ceil(milliseconds / 1000);
-    const minutes = Math.floor(totalSeconds / 60);
-    const seconds = totalSeconds % 60;
-    return `${minutes.toString().padStart(2, '0')}:${seconds.toString().padStart(2, '0')}`;
-  };
+  const formatTime = (milliseconds: number) => wasmTimer.formatDuration(Math.ceil(milliseconds / 1000));
 
   // 获取会话类型文本
   const getSessionTypeText = () => {
@@ -211,7 +206,7 @@ const Dashboard: React.FC<DashboardProps> = ({ className = '' }) => {
                   <span className="font-medium text-blue-700 dark:text-blue-300">今日专注</span>
                 </div>
                 <div className="text-2xl font-bold text-blue-700 dark:text-blue-300">
-                  {stats ? formatTimeShort(stats.daily.totalFocusTime * 60) : '0分钟'}
+                  {stats ? wasmTimer.formatDuration(stats.daily.totalFocusTime * 60, DurationFormat.Compact) : '0分钟'}
                 </div>
               </div>
 
@@ -355,7 +350,7 @@ const Dashboard: React.FC<DashboardProps> = ({ className = '' }) => {
                     </div>
                   </div>
                   <div className="text-sm font-medium text-gray-900 dark:text-white">
-                    {Math.round((session.focusTime / (session.sessions * 25)) * 100)}% 效率
+                    {session.efficiencyScore ?? Math.round((session.focusTime / (session.sessions * 25)) * 100)}% 效率
                   </div>
                 </div>
               ))}
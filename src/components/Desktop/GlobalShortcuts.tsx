@@ -6,10 +6,13 @@
 import React, { useEffect, useCallback, useState, createContext, useContext } from 'react';
 import { useUnifiedTimerStore } from '../../stores/unifiedTimerStore';
 import { useSettingsStore } from '../../stores/settingsStore';
+import {
+  getShortcutService,
+  type BackendShortcutAction,
+  type ShortcutStatus
+} from '../../services/shortcutService';
 
-// Tauri API imports (在实际项目中使用)
-// import { invoke } from '@tauri-apps/api/tauri';
-// import { register, unregister, isRegistered } from '@tauri-apps/api/globalShortcut';
+const shortcutService = getShortcutService();
 
 // 全局快捷键定义
 export interface GlobalShortcut {
@@ -22,6 +25,8 @@ export interface GlobalShortcut {
   category: 'timer' | 'window' | 'system' | 'custom';
   handler: () => void | Promise<void>;
   conflictsWith?: string[]; // 可能冲突的快捷键
+  // 由后端注册并执行的动作，桌面端按下时不经过 handler
+  backendAction?: BackendShortcutAction;
 }
 
 // 快捷键冲突信息
@@ -61,6 +66,10 @@ export class GlobalShortcutManager {
 
   // 注册快捷键
   async registerShortcut(shortcut: GlobalShortcut): Promise<boolean> {
+    if (this.isBackendShortcut(shortcut)) {
+      return this.registerWithBackend(shortcut);
+    }
+
     try {
       // 检查冲突
       const conflict = await this.checkConflict(shortcut.currentKeys);
@@ -74,9 +83,6 @@ export class GlobalShortcutManager {
         await this.unregisterShortcut(shortcut.id);
       }
 
-      // 在Tauri应用中注册全局快捷键
-      // await register(shortcut.currentKeys, shortcut.handler);
-      
       // 模拟注册（开发环境）
       console.log(`Registered global shortcut: ${shortcut.currentKeys} for ${shortcut.name}`);
 
@@ -101,9 +107,7 @@ export class GlobalShortcutManager {
       const shortcut = this.shortcuts.get(shortcutId);
       if (!shortcut) return false;
 
-      // 在Tauri应用中注销全局快捷键
-      // await unregister(shortcut.currentKeys);
-      
+      // 后端快捷键只移出列表，系统注册由后端管理
       console.log(`Unregistered global shortcut: ${shortcut.currentKeys}`);
 
       this.shortcuts.delete(shortcutId);
@@ -132,6 +136,10 @@ export class GlobalShortcutManager {
     const shortcut = this.shortcuts.get(shortcutId);
     if (!shortcut) return false;
 
+    if (this.isBackendShortcut(shortcut)) {
+      return shortcut.enabled === enabled || await this.registerWithBackend({ ...shortcut, enabled });
+    }
+
     if (enabled && !shortcut.enabled) {
       return await this.registerShortcut({ ...shortcut, enabled: true });
     } else if (!enabled && shortcut.enabled) {
@@ -144,6 +152,68 @@ export class GlobalShortcutManager {
     return true;
   }
 
+  private isBackendShortcut(shortcut: GlobalShortcut): boolean {
+    return !!shortcut.backendAction && shortcutService.isAvailable();
+  }
+
+  // 保存到后端设置并立即重新注册；绑定无效时不保存，系统拒绝注册时记为冲突
+  private async registerWithBackend(shortcut: GlobalShortcut): Promise<boolean> {
+    try {
+      const previous = this.shortcuts.get(shortcut.id);
+      const statuses = await shortcutService.setShortcuts([{
+        action: shortcut.backendAction!,
+        accelerator: shortcut.currentKeys,
+        enabled: shortcut.enabled
+      }]);
+      if (previous) {
+        this.registeredKeys.delete(previous.currentKeys);
+        this.conflicts.delete(previous.currentKeys);
+      }
+      this.shortcuts.set(shortcut.id, shortcut);
+      this.applyBackendStatuses(statuses);
+      return !statuses.some(status => status.action === shortcut.backendAction && status.error);
+    } catch (error) {
+      console.error(`Failed to register shortcut ${shortcut.id}:`, error);
+      return false;
+    }
+  }
+
+  // 以后端的绑定与注册结果为准更新快捷键和冲突信息
+  applyBackendStatuses(statuses: ShortcutStatus[]) {
+    for (const status of statuses) {
+      const shortcut = Array.from(this.shortcuts.values()).find(s => s.backendAction === status.action);
+      if (!shortcut) continue;
+
+      this.registeredKeys.delete(shortcut.currentKeys);
+      this.conflicts.delete(shortcut.currentKeys);
+      this.shortcuts.set(shortcut.id, { ...shortcut, currentKeys: status.accelerator, enabled: status.enabled });
+
+      if (status.registered) {
+        this.registeredKeys.add(status.accelerator);
+      }
+      if (status.error) {
+        this.conflicts.set(status.accelerator, {
+          shortcut: status.accelerator,
+          conflictingApps: [],
+          severity: 'high',
+          suggestion: status.error
+        });
+      }
+    }
+    this.notifyUpdate();
+  }
+
+  // 读取后端保存的绑定及注册结果
+  async syncWithBackend(): Promise<void> {
+    if (!shortcutService.isAvailable()) return;
+
+    try {
+      this.applyBackendStatuses(await shortcutService.getShortcuts());
+    } catch (error) {
+      console.error('Failed to load global shortcuts:', error);
+    }
+  }
+
   // 检查快捷键冲突
   private async checkConflict(keys: string): Promise<ShortcutConflict | null> {
     try {
@@ -197,7 +267,15 @@ export class GlobalShortcutManager {
 
   // 重置所有快捷键为默认值
   async resetToDefaults(): Promise<void> {
-    const shortcuts = Array.from(this.shortcuts.values());
+    if (shortcutService.isAvailable()) {
+      try {
+        this.applyBackendStatuses(await shortcutService.resetShortcuts());
+      } catch (error) {
+        console.error('Failed to reset global shortcuts:', error);
+      }
+    }
+
+    const shortcuts = Array.from(this.shortcuts.values()).filter(s => !this.isBackendShortcut(s));
     
     for (const shortcut of shortcuts) {
       if (shortcut.currentKeys !== shortcut.defaultKeys) {
@@ -229,6 +307,12 @@ interface GlobalShortcutContext {
 
 const GlobalShortcutContext = createContext<GlobalShortcutContext | null>(null);
 
+// 打开统计页面，通知路由同步地址
+const showStats = () => {
+  window.history.pushState(null, '', '/stats');
+  window.dispatchEvent(new PopStateEvent('popstate'));
+};
+
 // 全局快捷键Provider
 export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = ({ children }) => {
   const [shortcuts, setShortcuts] = useState<GlobalShortcut[]>([]);
@@ -257,10 +341,11 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
         id: 'timer-start-pause',
         name: '开始/暂停计时器',
         description: '开始或暂停当前计时器',
-        defaultKeys: 'Ctrl+Shift+Space',
-        currentKeys: settingsStore.settings.shortcuts?.startPause || 'Ctrl+Shift+Space',
+        defaultKeys: 'CmdOrCtrl+Shift+Space',
+        currentKeys: settingsStore.settings.shortcuts?.startPause || 'CmdOrCtrl+Shift+Space',
         enabled: true,
         category: 'timer',
+        backendAction: 'startPause',
         handler: () => {
           if (timerStore.isRunning) {
             timerStore.pause?.();
@@ -285,10 +370,11 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
         id: 'timer-skip',
         name: '跳过当前阶段',
         description: '跳过当前专注或休息阶段',
-        defaultKeys: 'Ctrl+Shift+S',
-        currentKeys: settingsStore.settings.shortcuts?.skip || 'Ctrl+Shift+S',
+        defaultKeys: 'CmdOrCtrl+Shift+S',
+        currentKeys: settingsStore.settings.shortcuts?.skip || 'CmdOrCtrl+Shift+S',
         enabled: true,
         category: 'timer',
+        backendAction: 'skip',
         handler: () => {
           timerStore.skip?.();
         }
@@ -297,10 +383,11 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
         id: 'show-hide-window',
         name: '显示/隐藏窗口',
         description: '切换主窗口的显示状态',
-        defaultKeys: 'Ctrl+Shift+F',
-        currentKeys: 'Ctrl+Shift+F',
+        defaultKeys: 'CmdOrCtrl+Shift+F',
+        currentKeys: 'CmdOrCtrl+Shift+F',
         enabled: true,
         category: 'window',
+        backendAction: 'toggleWindow',
         handler: async () => {
           try {
             // 在Tauri应用中实现
@@ -317,6 +404,30 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
           }
         }
       },
+      {
+        id: 'micro-break-now',
+        name: '立即微休息',
+        description: '暂停当前专注开始微休息，结束后继续剩余的专注时间',
+        defaultKeys: 'CmdOrCtrl+Shift+M',
+        currentKeys: 'CmdOrCtrl+Shift+M',
+        enabled: true,
+        category: 'timer',
+        backendAction: 'microBreak',
+        handler: () => {
+          timerStore.triggerMicroBreak?.();
+        }
+      },
+      {
+        id: 'show-stats',
+        name: '查看统计',
+        description: '显示主窗口并打开统计页面',
+        defaultKeys: 'CmdOrCtrl+Shift+D',
+        currentKeys: 'CmdOrCtrl+Shift+D',
+        enabled: true,
+        category: 'window',
+        backendAction: 'showStats',
+        handler: showStats
+      },
       {
         id: 'quick-focus-25',
         name: '快速开始25分钟专注',
@@ -359,15 +470,18 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
       }
     ];
 
-    // 注册所有启用的快捷键
+    // 注册所有启用的快捷键；桌面端由后端注册的快捷键以后端保存的绑定为准
     defaultShortcuts.forEach(shortcut => {
-      if (shortcut.enabled) {
+      if (shortcut.backendAction && shortcutService.isAvailable()) {
+        manager.shortcuts.set(shortcut.id, shortcut);
+      } else if (shortcut.enabled) {
         manager.registerShortcut(shortcut);
       } else {
         // 添加到管理器但不注册
         manager.shortcuts.set(shortcut.id, shortcut);
       }
     });
+    manager.syncWithBackend();
 
     // 清理函数
     return () => {
@@ -375,6 +489,16 @@ export const GlobalShortcutProvider: React.FC<{ children: React.ReactNode }> = (
     };
   }, [manager, timerStore, settingsStore]);
 
+  // 后端按下“查看统计”时打开统计页面；注册失败的快捷键由挂载时的 syncWithBackend 读取
+  useEffect(() => {
+    if (!shortcutService.isAvailable()) return;
+
+    const unlisten = shortcutService.onShowStats(showStats);
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [manager]);
+
   const contextValue: GlobalShortcutContext = {
     shortcuts,
     conflicts,
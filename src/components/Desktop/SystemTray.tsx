@@ -4,13 +4,11 @@
  */
 
 import React, { useEffect, useCallback, useState } from 'react';
-import { useUnifiedTimerStore } from '../../stores/unifiedTimerStore';
 import { useSettingsStore } from '../../stores/settingsStore';
+import { systemTrayService, type NativeTrayMenuItem } from '../../services/SystemTrayService';
 
 // Tauri API imports (在实际项目中使用)
-// import { invoke } from '@tauri-apps/api/tauri';
 // import { appWindow } from '@tauri-apps/api/window';
-// import { Menu, MenuItem } from '@tauri-apps/api/menu';
 
 // 托盘菜单项类型
 interface TrayMenuItem {
@@ -32,10 +30,14 @@ interface TrayState {
 }
 
 // 系统托盘管理器
+// 原生托盘的提示文字与计时菜单由后端按计时状态刷新，这里的状态只用于调试显示；
+// 前端的菜单项追加在内置菜单中，设置的提示文字在清除前代替后端的提示
 export class SystemTrayManager {
   private static instance: SystemTrayManager;
   private trayState: TrayState;
   private updateCallbacks: Set<(state: TrayState) => void> = new Set();
+  // 原生菜单项 id 对应的点击动作
+  private menuActions = new Map<string, () => void>();
 
   private constructor() {
     this.trayState = {
@@ -44,6 +46,7 @@ export class SystemTrayManager {
       tooltip: 'FocusFlow - 就绪',
       blinking: false
     };
+    systemTrayService.onMenuClick(id => this.menuActions.get(id)?.());
   }
 
   static getInstance(): SystemTrayManager {
@@ -63,7 +66,6 @@ export class SystemTrayManager {
   private updateState(updates: Partial<TrayState>) {
     this.trayState = { ...this.trayState, ...updates };
     this.updateCallbacks.forEach(callback => callback(this.trayState));
-    this.syncWithNativeTray();
   }
 
   // 设置托盘图标
@@ -71,9 +73,12 @@ export class SystemTrayManager {
     this.updateState({ icon });
   }
 
-  // 设置工具提示
-  setTooltip(tooltip: string) {
-    this.updateState({ tooltip });
+  // 设置工具提示；传入 null 恢复为随计时刷新的提示
+  setTooltip(tooltip: string | null) {
+    if (tooltip !== null) {
+      this.updateState({ tooltip });
+    }
+    systemTrayService.setTooltip(tooltip);
   }
 
   // 设置闪烁状态
@@ -86,36 +91,24 @@ export class SystemTrayManager {
     this.updateState({ isVisible: visible });
   }
 
-  // 同步到原生托盘（在实际项目中实现）
-  private async syncWithNativeTray() {
-    try {
-      // 在Tauri应用中，这里会调用原生API
-      // await invoke('update_tray_icon', { icon: this.trayState.icon });
-      // await invoke('update_tray_tooltip', { tooltip: this.trayState.tooltip });
-      
-      console.log('Tray state updated:', this.trayState);
-    } catch (error) {
-      console.error('Failed to update system tray:', error);
-    }
-  }
-
-  // 创建托盘菜单
+  // 在原生托盘的内置菜单中追加菜单项
   async createTrayMenu(menuItems: TrayMenuItem[]): Promise<void> {
     try {
-      // 在Tauri应用中实现
-      // const menu = await this.buildNativeMenu(menuItems);
-      // await invoke('set_tray_menu', { menu });
-      
-      console.log('Tray menu created:', menuItems);
+      this.menuActions.clear();
+      await systemTrayService.setExtraItems(this.buildNativeMenu(menuItems));
     } catch (error) {
       console.error('Failed to create tray menu:', error);
     }
   }
 
-  // 构建原生菜单（在实际项目中实现）
-  private async buildNativeMenu(items: TrayMenuItem[]): Promise<any> {
-    // 这里会使用Tauri的Menu API构建原生菜单
-    return items;
+  // 构建原生菜单：点击动作留在前端，按 id 回调
+  private buildNativeMenu(items: TrayMenuItem[]): NativeTrayMenuItem[] {
+    return items.map(({ action, submenu, ...item }) => {
+      if (action) {
+        this.menuActions.set(item.id, action);
+      }
+      return { ...item, submenu: submenu && this.buildNativeMenu(submenu) };
+    });
   }
 
   // 获取当前状态
@@ -141,7 +134,7 @@ export const useSystemTray = () => {
     trayManager.setIcon(icon);
   }, [trayManager]);
 
-  const updateTooltip = useCallback((tooltip: string) => {
+  const updateTooltip = useCallback((tooltip: string | null) => {
     trayManager.setTooltip(tooltip);
   }, [trayManager]);
 
@@ -168,76 +161,14 @@ export const useSystemTray = () => {
 };
 
 // 系统托盘集成组件
+// 开始/暂停、跳过、微休息、显示窗口与退出由后端内置菜单提供，这里只追加设置相关的菜单项
 export const SystemTrayIntegration: React.FC = () => {
-  const timerStore = useUnifiedTimerStore();
   const settingsStore = useSettingsStore();
-  const { updateIcon, updateTooltip, setBlinking, createMenu } = useSystemTray();
+  const { createMenu } = useSystemTray();
 
-  // 根据计时器状态更新托盘
-  useEffect(() => {
-    const { isRunning, isPaused, currentPhase, currentTime } = timerStore;
-    
-    let icon = 'idle';
-    let tooltip = 'FocusFlow - 就绪';
-    let shouldBlink = false;
-
-    if (isRunning) {
-      icon = currentPhase === 'focus' ? 'focus-active' : 'break-active';
-      const minutes = Math.floor(currentTime / 60);
-      const seconds = currentTime % 60;
-      const timeStr = `${minutes.toString().padStart(2, '0')}:${seconds.toString().padStart(2, '0')}`;
-      
-      tooltip = `FocusFlow - ${currentPhase === 'focus' ? '专注中' : '休息中'} (${timeStr})`;
-    } else if (isPaused) {
-      icon = 'paused';
-      tooltip = 'FocusFlow - 已暂停';
-    }
-
-    // 时间即将结束时闪烁提醒
-    if (isRunning && currentTime <= 60) {
-      shouldBlink = true;
-    }
-
-    updateIcon(icon);
-    updateTooltip(tooltip);
-    setBlinking(shouldBlink);
-  }, [timerStore, updateIcon, updateTooltip, setBlinking]);
-
-  // 创建托盘菜单
+  // 追加托盘菜单项
   useEffect(() => {
     const menuItems: TrayMenuItem[] = [
-      {
-        id: 'show-window',
-        label: '显示窗口',
-        action: () => showMainWindow()
-      },
-      {
-        id: 'separator-1',
-        label: '',
-        separator: true
-      },
-      {
-        id: 'timer-control',
-        label: timerStore.isRunning ? '暂停计时器' : '开始计时器',
-        action: () => {
-          if (timerStore.isRunning) {
-            timerStore.pause?.();
-          } else {
-            timerStore.start?.();
-          }
-        }
-      },
-      {
-        id: 'reset-timer',
-        label: '重置计时器',
-        enabled: !timerStore.isRunning,
-        action: () => timerStore.reset?.()
-      },
-      {
-        id: 'separator-2',
-        label: '',
-        separator: true
-      },
       {
         id: 'quick-settings',
         label: '快速设置',
@@ -271,7 +202,7 @@ export const SystemTrayIntegration: React.FC = () => {
         })
       },
       {
-        id: 'separator-3',
+        id: 'separator-1',
         label: '',
         separator: true
       },
@@ -284,21 +215,11 @@ export const SystemTrayIntegration: React.FC = () => {
         id: 'about',
         label: '关于',
         action: () => showAbout()
-      },
-      {
-        id: 'separator-4',
-        label: '',
-        separator: true
-      },
-      {
-        id: 'quit',
-        label: '退出',
-        action: () => quitApplication()
       }
     ];
 
     createMenu(menuItems);
-  }, [timerStore, settingsStore, createMenu]);
+  }, [settingsStore, createMenu]);
 
   // 窗口管理函数
   const showMainWindow = useCallback(async () => {
@@ -333,16 +254,6 @@ export const SystemTrayIntegration: React.FC = () => {
     }
   }, []);
 
-  const quitApplication = useCallback(async () => {
-    try {
-      // 退出应用
-      // await invoke('quit_app');
-      console.log('Quitting application');
-    } catch (error) {
-      console.error('Failed to quit application:', error);
-    }
-  }, []);
-
   // 这个组件不渲染任何UI，只负责托盘集成逻辑
   return null;
 };
@@ -4,7 +4,8 @@ import { Button } from '../ui/Button';
 import { Switch } from '../ui/Switch';
 import { Input } from '../ui/Input';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '../ui/Select';
-import { Settings, Bell, Monitor, Moon, Sun, Volume2 } from 'lucide-react';
+import { Settings, Bell, Monitor, Moon, Sun, Volume2, Calendar } from 'lucide-react';
+import { useSettingsStore } from '../../stores/settingsStore';
 
 interface GeneralSettingsProps {
   className?: string;
@@ -17,6 +18,7 @@ const GeneralSettings: React.FC<GeneralSettingsProps> = ({ className = '' }) =>
   const [focusDuration, setFocusDuration] = useState('25');
   const [breakDuration, setBreakDuration] = useState('5');
   const [longBreakDuration, setLongBreakDuration] = useState('15');
+  const { settings: appSettings, updateSettings } = useSettingsStore();
 
   const handleSaveSettings = () => {
     // 保存设置的逻辑
@@ -165,6 +167,49 @@ const GeneralSettings: React.FC<GeneralSettingsProps> = ({ className = '' }) =>
         </CardContent>
       </Card>
 
+      <Card className="mb-6">
+        <CardHeader>
+          <CardTitle className="flex items-center">
+            <Calendar className="w-5 h-5 mr-2" />
+            日期设置
+          </CardTitle>
+          <CardDescription>
+            统计和会话记录按此划分日期
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+            <div>
+              <label className="text-sm font-medium mb-1 block">时区</label>
+              <Input
+                defaultValue={appSettings.timeZone}
+                onBlur={(e) => updateSettings({ timeZone: e.target.value.trim() })}
+                placeholder="跟随系统，如 Asia/Shanghai"
+              />
+            </div>
+
+            <div>
+              <label className="text-sm font-medium mb-1 block">每天开始于</label>
+              <Select
+                value={String(appSettings.dayStartHour)}
+                onValueChange={(value) => updateSettings({ dayStartHour: Number(value) })}
+              >
+                <SelectTrigger>
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {Array.from({ length: 24 }, (_, hour) => (
+                    <SelectItem key={hour} value={String(hour)}>
+                      {`${String(hour).padStart(2, '0')}:00`}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
+        </CardContent>
+      </Card>
+
       <div className="flex justify-end">
         <Button onClick={handleSaveSettings}>
           保存设置
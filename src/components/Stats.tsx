@@ -1,7 +1,7 @@
 import React from 'react';
 import { useStatsStore } from '../stores/statsStore';
 import StatsComponent from './Stats/Stats';
-import { formatTimeShort } from '../utils/formatTime';
+import { wasmTimer, DurationFormat } from '../wasm/wasmTimer';
 
 /**
  * 统计页面组件
@@ -62,7 +62,7 @@ const Stats: React.FC = () => {
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4 mb-8">
         <div className="bg-white p-4 rounded-lg shadow">
           <h3 className="text-gray-500 text-sm font-medium mb-1">总专注时间</h3>
-          <p className="text-2xl font-bold">{formatTimeShort(stats.allTime.totalFocusTime * 60)}</p>
+          <p className="text-2xl font-bold">{wasmTimer.formatDuration(stats.allTime.totalFocusTime * 60, DurationFormat.Compact)}</p>
         </div>
 
         <div className="bg-white p-4 rounded-lg shadow">
@@ -1,6 +1,6 @@
 import React, { useMemo, useCallback } from 'react';
 import { useUnifiedTimerStore } from '../../stores/unifiedTimerStore';
-import { formatTime } from '../../utils/formatTime';
+import { wasmTimer } from '../../wasm/wasmTimer';
 import { UnifiedTimerStateType } from '../../types/unifiedTimer';
 
 interface ModernTimerDisplayProps {
@@ -68,7 +68,7 @@ export const ModernTimerDisplay: React.FC<ModernTimerDisplayProps> = React.memo(
     return ((totalTime - timeLeft) / totalTime) * 100;
   }, [timeLeft, totalTime]);
 
-  const formattedTime = useMemo(() => formatTime(timeLeft), [timeLeft]);
+  const formattedTime = useMemo(() => wasmTimer.formatDuration(timeLeft), [timeLeft]);
 
   const config = useMemo(() => STATE_CONFIG[currentState], [currentState]);
 
@@ -202,7 +202,7 @@ export const ModernTimerDisplay: React.FC<ModernTimerDisplayProps> = React.memo(
 // 紧凑版本的计时器显示
 export const CompactTimerDisplay: React.FC = React.memo(() => {
   const { timeLeft, currentState, isActive, start, pause } = useUnifiedTimerStore();
-  const formattedTime = useMemo(() => formatTime(timeLeft), [timeLeft]);
+  const formattedTime = useMemo(() => wasmTimer.formatDuration(timeLeft), [timeLeft]);
 
   const stateColors: Record<UnifiedTimerStateType, string> = {
     focus: 'text-green-500',
@@ -10,7 +10,7 @@ import EfficiencyRating from './EfficiencyRating';
 import TimerDisplayOptimized from './TimerDisplayOptimized';
 import { wrapFunction } from '../../utils/errorHandler';
 import { formatConfirmationMessage } from '../../utils/confirmationUtils';
-import { formatTime } from '../../utils/formatTime';
+import { wasmTimer } from '../../wasm/wasmTimer';
 import { UnifiedTimerSettings } from '../../types/unifiedTimer';
 
 interface TimerProps {
@@ -187,7 +187,7 @@ const TimerComponent: React.FC<TimerProps> = React.memo(({ onStateChange }) => {
         {/* 计时器显示 */}
         <TimerDisplayOptimized 
           time={timeLeft}
-          formattedTime={formatTime(timeLeft)} 
+          formattedTime={wasmTimer.formatDuration(timeLeft)} 
           progress={progressPercentage}
           currentState={currentState}
           isActive={isActive}
@@ -82,7 +82,7 @@ const UnifiedTimer: React.FC<UnifiedTimerProps> = ({
     pendingRatingSession,
     continuousFocusTime,
     microBreakCount,
-    recentEfficiencyScores,
+    lastEfficiencyScore,
     
     // 控制方法
     start,
@@ -183,9 +183,7 @@ const UnifiedTimer: React.FC<UnifiedTimerProps> = ({
         features: [
           `连续专注: ${Math.round(continuousFocusTime)}分钟`,
           `微休息: ${microBreakCount}次`,
-          `效率评分: ${recentEfficiencyScores.length > 0 
-            ? (recentEfficiencyScores.reduce((a, b) => a + b, 0) / recentEfficiencyScores.length).toFixed(1)
-            : 'N/A'}`
+          `效率评分: ${lastEfficiencyScore ? lastEfficiencyScore.score : 'N/A'}`
         ]
       };
     } else {
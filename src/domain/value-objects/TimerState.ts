@@ -15,7 +15,8 @@ export enum TimerState {
  */
 export enum TimerMode {
   CLASSIC = 'classic',
-  SMART = 'smart'
+  SMART = 'smart',
+  FLOW = 'flow'
 }
 
 /**
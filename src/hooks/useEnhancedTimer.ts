@@ -6,7 +6,7 @@
 import { useEffect, useCallback, useMemo } from 'react';
 import { useUnifiedTimerStoreEnhanced } from '../stores/unifiedTimerStoreEnhanced';
 import { TimerState, TimerMode } from '../types/unifiedTimer';
-import { formatTime } from '../utils/formatTime';
+import { wasmTimer } from '../wasm/wasmTimer';
 
 export interface UseEnhancedTimerResult {
   // 基础状态
@@ -102,7 +102,7 @@ export function useEnhancedTimer(): UseEnhancedTimerResult {
 
   // 格式化时间显示
   const formattedTime = useMemo(() => {
-    return formatTime(store.timeLeft);
+    return wasmTimer.formatDuration(store.timeLeft);
   }, [store.timeLeft]);
 
   // 获取状态显示文本
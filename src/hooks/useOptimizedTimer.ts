@@ -6,7 +6,7 @@
 import { useEffect, useCallback, useMemo, useRef } from 'react';
 import { useUnifiedTimerStoreEnhanced } from '../stores/unifiedTimerStoreEnhanced';
 import { TimerState, TimerMode } from '../types/unifiedTimer';
-import { formatTime } from '../utils/formatTime';
+import { wasmTimer } from '../wasm/wasmTimer';
 import { PerformanceMonitorService } from '../infrastructure/services/PerformanceMonitorService';
 import { container } from '../container/IoCContainer';
 
@@ -127,7 +127,7 @@ export function useOptimizedTimer(): UseOptimizedTimerResult {
 
   // 格式化时间显示
   const formattedTime = useMemo(() => {
-    return formatTime(store.timeLeft);
+    return wasmTimer.formatDuration(store.timeLeft);
   }, [store.timeLeft]);
 
   // 获取状态显示文本
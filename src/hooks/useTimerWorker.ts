@@ -0,0 +1,74 @@
+/**
+ * 使用 timer.worker 的Hook
+ * Worker 按显示精度对齐唤醒，页面不可见时降为分钟级刷新；可见性由这里转发给 Worker
+ */
+
+import { useEffect, useRef, useState, useCallback } from 'react';
+import type { TimerCalculation, TickPrecision } from '../workers/timer.worker';
+
+interface UseTimerWorkerReturn {
+  calculation: TimerCalculation | null;
+  start: (durationMs: number, precision?: TickPrecision) => void;
+  pause: () => void;
+  reset: (durationMs: number) => void;
+}
+
+const isPageVisible = (): boolean =>
+  typeof document === 'undefined' || document.visibilityState === 'visible';
+
+/**
+ * 单个计时器的 Worker 封装，id 用于区分同一 Worker 中的计时器
+ */
+export const useTimerWorker = (id: string = 'main'): UseTimerWorkerReturn => {
+  const workerRef = useRef<Worker | null>(null);
+  const [calculation, setCalculation] = useState<TimerCalculation | null>(null);
+
+  useEffect(() => {
+    const worker = new Worker(new URL('../workers/timer.worker.ts', import.meta.url), { type: 'module' });
+    workerRef.current = worker;
+
+    const handleMessage = (event: MessageEvent) => {
+      const { type, payload } = event.data;
+      if ((type === 'TICK' || type === 'CURRENT_TIME') && payload.id === id) {
+        setCalculation(payload.calculation);
+      }
+    };
+
+    // 页面切到后台或回到前台时通知 Worker 调整刷新粒度
+    const handleVisibilityChange = () => {
+      worker.postMessage({ type: 'SET_VISIBILITY', payload: { visible: isPageVisible() } });
+    };
+
+    worker.addEventListener('message', handleMessage);
+    document.addEventListener('visibilitychange', handleVisibilityChange);
+    handleVisibilityChange();
+
+    return () => {
+      document.removeEventListener('visibilitychange', handleVisibilityChange);
+      worker.removeEventListener('message', handleMessage);
+      worker.terminate();
+      workerRef.current = null;
+    };
+  }, [id]);
+
+  const start = useCallback((durationMs: number, precision: TickPrecision = 'seconds') => {
+    workerRef.current?.postMessage({
+      type: 'START_TIMER',
+      id,
+      payload: { duration: durationMs, startTime: Date.now(), precision },
+    });
+  }, [id]);
+
+  const pause = useCallback(() => {
+    workerRef.current?.postMessage({ type: 'PAUSE_TIMER', id });
+  }, [id]);
+
+  const reset = useCallback((durationMs: number) => {
+    workerRef.current?.postMessage({ type: 'RESET_TIMER', id, payload: { duration: durationMs } });
+    setCalculation(null);
+  }, [id]);
+
+  return { calculation, start, pause, reset };
+};
+
+export default useTimerWorker;
@@ -4,8 +4,10 @@
  */
 
 import { useEffect, useRef } from 'react';
-import { useUnifiedTimerStore } from '../stores/unifiedTimerStore';
-import { TimerMode } from '../types/unifiedTimer';
+import { useUnifiedTimerStore, isCountingUp } from '../stores/unifiedTimerStore';
+import { TimerMode, DEFAULT_UNIFIED_SETTINGS } from '../types/unifiedTimer';
+import { wasmTimer } from '../wasm/wasmTimer';
+import { getBackendTimerService } from '../services/backendTimer';
 
 /**
  * 统一计时器逻辑 Hook
@@ -16,22 +18,38 @@ export const useUnifiedTimer = () => {
     currentMode,
     timeLeft,
     totalTime,
+    elapsedTime,
     isActive,
     settings,
     updateTimeLeft,
+    updateElapsedTime,
     checkMicroBreakTrigger,
     triggerMicroBreak,
+    connectBackend,
   } = useUnifiedTimerStore();
 
   const intervalRef = useRef<number | null>(null);
   const microBreakCheckRef = useRef<number | null>(null);
 
-  // 主计时器逻辑
+  const countingUp = isCountingUp(currentMode, currentState);
+  const flowSettings = settings.flow ?? DEFAULT_UNIFIED_SETTINGS.flow!;
+  // 桌面端由后端计时并安排微休息，这里不再本地计时
+  const backendDriven = getBackendTimerService().isAvailable();
+
+  useEffect(() => {
+    connectBackend();
+  }, [connectBackend]);
+
+  // 主计时器逻辑；心流专注段正计时，到达目标时长后继续计时
   useEffect(() => {
-    if (isActive && timeLeft > 0) {
+    if (!backendDriven && isActive && (countingUp || timeLeft > 0)) {
       intervalRef.current = window.setInterval(() => {
-        const newTimeLeft = useUnifiedTimerStore.getState().timeLeft - 1;
-        updateTimeLeft(newTimeLeft);
+        const state = useUnifiedTimerStore.getState();
+        if (countingUp) {
+          updateElapsedTime(state.elapsedTime + 1);
+        } else {
+          updateTimeLeft(state.timeLeft - 1);
+        }
       }, 1000);
     } else {
       // 清除计时器
@@ -47,11 +65,11 @@ export const useUnifiedTimer = () => {
         intervalRef.current = null;
       }
     };
-  }, [isActive, timeLeft, updateTimeLeft]);
+  }, [backendDriven, isActive, timeLeft, countingUp, updateTimeLeft, updateElapsedTime]);
 
-  // 微休息检查逻辑（仅在专注状态下）
+  // 微休息检查逻辑（仅在专注状态下；心流模式不打断专注）
   useEffect(() => {
-    const shouldCheckMicroBreak = isActive && 
+    const shouldCheckMicroBreak = !backendDriven && isActive && 
       currentState === 'focus' && 
       (
         (currentMode === TimerMode.CLASSIC) ||
@@ -77,19 +95,16 @@ export const useUnifiedTimer = () => {
         microBreakCheckRef.current = null;
       }
     };
-  }, [isActive, currentState, currentMode, settings, checkMicroBreakTrigger, triggerMicroBreak]);
+  }, [backendDriven, isActive, currentState, currentMode, settings, checkMicroBreakTrigger, triggerMicroBreak]);
 
   // 格式化时间显示
-  const formatTime = (seconds: number): string => {
-    const mins = Math.floor(seconds / 60);
-    const secs = seconds % 60;
-    return `${mins.toString().padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
-  };
+  const formatTime = (seconds: number): string => wasmTimer.formatDuration(seconds);
 
   // 获取状态显示文本
   const getStateText = (): string => {
     switch (currentState) {
       case 'focus':
+        if (currentMode === TimerMode.FLOW) return '心流中';
         return currentMode === TimerMode.SMART ? '深度专注' : '专注中';
       case 'break':
         return '休息中';
@@ -131,6 +146,9 @@ export const useUnifiedTimer = () => {
         if (currentMode === TimerMode.SMART) {
           return '保持深度专注，避免干扰。智能系统会根据您的表现自动调整。';
         }
+        if (currentMode === TimerMode.FLOW) {
+          return '想停时再停。休息时长会按本次专注时长计算。';
+        }
         return '保持专注，避免干扰。完成后会有短暂休息。';
       case 'break':
         return '放松身心，准备下一轮专注。可以起身活动或做些轻松的事情。';
@@ -154,6 +172,15 @@ export const useUnifiedTimer = () => {
         defaultFocusDuration: settings.smart.focusDuration,
         defaultBreakDuration: settings.smart.breakDuration,
       };
+    } else if (currentMode === TimerMode.FLOW) {
+      return {
+        hasAdaptiveAdjustment: false,
+        hasCircadianOptimization: false,
+        hasForcedBreaks: false,
+        hasEfficiencyTracking: true,
+        defaultFocusDuration: flowSettings.targetDuration,
+        defaultBreakDuration: flowSettings.minBreakDuration,
+      };
     } else {
       return {
         hasAdaptiveAdjustment: false,
@@ -168,8 +195,8 @@ export const useUnifiedTimer = () => {
 
   // 检查是否可以跳过当前阶段
   const canSkipPhase = (): boolean => {
-    // 智能模式允许跳过任何阶段
-    if (currentMode === TimerMode.SMART) {
+    // 智能模式允许跳过任何阶段；心流模式靠手动结束专注
+    if (currentMode === TimerMode.SMART || currentMode === TimerMode.FLOW) {
       return true;
     }
     
@@ -179,6 +206,23 @@ export const useUnifiedTimer = () => {
 
   // 获取下一阶段的预期时间
   const getNextPhaseTime = (): number => {
+    if (currentMode === TimerMode.FLOW) {
+      switch (currentState) {
+        case 'focus':
+          return wasmTimer.suggestFlowBreak(
+            elapsedTime,
+            flowSettings.breakRatio,
+            flowSettings.minBreakDuration,
+            flowSettings.maxBreakDuration
+          );
+        case 'break':
+        case 'forcedBreak':
+          return flowSettings.targetDuration * 60;
+        default:
+          return 0;
+      }
+    }
+
     const currentSettings = currentMode === TimerMode.CLASSIC ? settings.classic : settings.smart;
     
     switch (currentState) {
@@ -200,11 +244,13 @@ export const useUnifiedTimer = () => {
     currentMode,
     timeLeft,
     totalTime,
+    elapsedTime,
     isActive,
     settings,
     
     // 计算属性
-    formattedTime: formatTime(timeLeft),
+    formattedTime: formatTime(countingUp ? elapsedTime : timeLeft),
+    isCountingUp: countingUp,
     stateText: getStateText(),
     stateColor: getStateColor(),
     progress: getProgress(),
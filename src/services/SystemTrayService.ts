@@ -1,166 +1,41 @@
-import { appWindow, Tray } from '@tauri-apps/api/window';
-import { Menu, MenuItem, PredefinedMenuItem, Submenu } from '@tauri-apps/api/menu';
-import { emit, listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/tauri';
+import { emit, listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environment';
+
+// 托盘菜单项，与后端 tray.rs 中的 TrayMenuItem 对应
+export interface NativeTrayMenuItem {
+  id: string;
+  label: string;
+  enabled?: boolean;
+  checked?: boolean;
+  separator?: boolean;
+  submenu?: NativeTrayMenuItem[];
+}
+
+// 前端追加的菜单项被点击时，后端通过该事件回传 id
+export const TRAY_MENU_CLICK_EVENT = 'tray://menu-click';
 
 /**
  * 系统托盘服务
- * 管理桌面应用的系统托盘功能
+ * 托盘由后端创建，内置菜单（开始/暂停、跳过、微休息、计时方案、显示/隐藏、退出）直接操作后端计时器，
+ * 提示文字与菜单状态随后端计时刷新。前端可以在内置菜单中追加自己的菜单项，或临时改用自己的提示文字
  */
 export class SystemTrayService {
-  private tray: Tray | null = null;
   private isInitialized = false;
+  private unlistenMenuClick: UnlistenFn | null = null;
+  private menuClickHandlers = new Set<(id: string) => void>();
 
   /**
    * 初始化系统托盘
    */
   async initialize(): Promise<void> {
-    if (this.isInitialized) {
+    if (this.isInitialized || !isTauriEnvironment()) {
       return;
     }
 
     try {
-      // 创建菜单
-      const menu = await Menu.new({
-        items: [
-          await MenuItem.new({
-            id: 'show',
-            text: '显示窗口',
-            action: async () => {
-              await appWindow.show();
-              await appWindow.unminimize();
-              await appWindow.setFocus();
-            },
-          }),
-          await MenuItem.new({
-            id: 'hide',
-            text: '隐藏窗口',
-            action: async () => {
-              await appWindow.hide();
-            },
-          }),
-          await PredefinedMenuItem.new({
-            id: 'separator',
-            item: 'Separator',
-          }),
-          await Submenu.new({
-            id: 'timer',
-            text: '计时器',
-            items: [
-              await MenuItem.new({
-                id: 'start-focus',
-                text: '开始专注',
-                action: () => {
-                  emit('tray-focus-mode');
-                },
-              }),
-              await MenuItem.new({
-                id: 'start-break',
-                text: '开始休息',
-                action: () => {
-                  emit('tray-break-mode');
-                },
-              }),
-              await MenuItem.new({
-                id: 'pause-timer',
-                text: '暂停计时器',
-                action: () => {
-                  emit('tray-pause-timer');
-                },
-              }),
-            ],
-          }),
-          await Submenu.new({
-            id: 'theme',
-            text: '主题',
-            items: [
-              await MenuItem.new({
-                id: 'theme-light',
-                text: '浅色',
-                action: () => {
-                  emit('tray-theme-change', { theme: 'light' });
-                },
-              }),
-              await MenuItem.new({
-                id: 'theme-dark',
-                text: '深色',
-                action: () => {
-                  emit('tray-theme-change', { theme: 'dark' });
-                },
-              }),
-              await MenuItem.new({
-                id: 'theme-system',
-                text: '系统',
-                action: () => {
-                  emit('tray-theme-change', { theme: 'system' });
-                },
-              }),
-            ],
-          }),
-          await PredefinedMenuItem.new({
-            id: 'separator',
-            item: 'Separator',
-          }),
-          await MenuItem.new({
-            id: 'quit',
-            text: '退出',
-            action: () => {
-              emit('tray-quit');
-            },
-          }),
-        ],
-      });
-
-      // 创建托盘图标
-      this.tray = await Tray.new({
-        id: 'main-tray',
-        icon: 'icons/icon.png',
-        menu,
-        tooltip: 'FocusFlow - 智能专注管理',
-        menuOnLeftClick: false,
-        title: 'FocusFlow',
-      });
-
-      // 监听窗口状态变化
-      await listen('tauri://blur', async () => {
-        // 可以在这里添加窗口失去焦点时的逻辑
-      });
-
-      await listen('tauri://focus', async () => {
-        // 可以在这里添加窗口获得焦点时的逻辑
-      });
-
-      // 监听托盘事件
-      await listen('tray-focus-mode', async () => {
-        // 处理开始专注模式事件
-        await appWindow.show();
-        await appWindow.unminimize();
-        await appWindow.setFocus();
-      });
-
-      await listen('tray-break-mode', async () => {
-        // 处理开始休息模式事件
-        await appWindow.show();
-        await appWindow.unminimize();
-        await appWindow.setFocus();
-      });
-
-      await listen('tray-pause-timer', async () => {
-        // 处理暂停计时器事件
-        await appWindow.show();
-        await appWindow.unminimize();
-        await appWindow.setFocus();
-      });
-
-      await listen('tray-theme-change', async (event) => {
-        // 处理主题变更事件
-        const { theme } = event.payload as { theme: string };
-        // 保存主题设置到本地存储
-        localStorage.setItem('theme', theme);
-      });
-
-      await listen('tray-quit', async () => {
-        // 处理退出应用事件
-        await appWindow.close();
+      this.unlistenMenuClick = await listen<string>(TRAY_MENU_CLICK_EVENT, (event) => {
+        this.menuClickHandlers.forEach(handler => handler(event.payload));
       });
 
       this.isInitialized = true;
@@ -171,32 +46,40 @@ export class SystemTrayService {
   }
 
   /**
-   * 更新托盘提示文本
+   * 订阅自定义菜单项的点击，返回取消订阅函数
+   */
+  onMenuClick(handler: (id: string) => void): () => void {
+    this.menuClickHandlers.add(handler);
+    return () => this.menuClickHandlers.delete(handler);
+  }
+
+  /**
+   * 设置追加在内置菜单中的菜单项；传入空列表只保留内置菜单
    */
-  async updateTooltip(text: string): Promise<void> {
-    if (!this.tray) {
+  async setExtraItems(items: NativeTrayMenuItem[]): Promise<void> {
+    if (!isTauriEnvironment()) {
       return;
     }
 
     try {
-      await this.tray.setTooltip(text);
+      await invoke('set_tray_menu', { items });
     } catch (error) {
-      console.error('Failed to update tray tooltip:', error);
+      console.error('Failed to set tray menu items:', error);
     }
   }
 
   /**
-   * 更新托盘标题
+   * 用自己的提示文字代替随计时刷新的提示；传入 null 恢复默认
    */
-  async updateTitle(title: string): Promise<void> {
-    if (!this.tray) {
+  async setTooltip(tooltip: string | null): Promise<void> {
+    if (!isTauriEnvironment()) {
       return;
     }
 
     try {
-      await this.tray.setTitle(title);
+      await invoke('update_tray_tooltip', { tooltip });
     } catch (error) {
-      console.error('Failed to update tray title:', error);
+      console.error('Failed to update tray tooltip:', error);
     }
   }
 
@@ -213,21 +96,13 @@ export class SystemTrayService {
   }
 
   /**
-   * 销毁系统托盘
+   * 停止监听托盘事件；托盘本身随应用退出
    */
   async destroy(): Promise<void> {
-    if (!this.tray) {
-      return;
-    }
-
-    try {
-      await this.tray.destroy();
-      this.tray = null;
-      this.isInitialized = false;
-      console.log('System tray destroyed successfully');
-    } catch (error) {
-      console.error('Failed to destroy system tray:', error);
-    }
+    this.unlistenMenuClick?.();
+    this.unlistenMenuClick = null;
+    this.menuClickHandlers.clear();
+    this.isInitialized = false;
   }
 
   /**
@@ -0,0 +1,158 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environment';
+import type { TimerMode, UnifiedTimerSettings } from '../types/unifiedTimer';
+
+// 后端计时器的阶段，与 timer_core 的 TimerPhase 序列化结果一致
+export type BackendTimerPhase = 'focus' | 'break' | 'microBreak' | 'forcedBreak' | 'longBreak' | 'idle';
+
+export interface BackendTimerTick {
+  state: BackendTimerPhase;
+  running: boolean;
+  // 显示的秒数：倒计时为剩余，正计时为已计时
+  time: number;
+  formattedTime: string;
+  remaining: number;
+  duration: number;
+  progress: number;
+  cycle: number;
+  cyclesPerSet: number;
+  remainingMs: number;
+  endsAt: number;
+  overtimeMs: number;
+  mode: TimerMode;
+  // 正在使用的计时方案，按循环策略运行时为 null
+  preset: string | null;
+}
+
+// scheduled：到达计划的微休息或强制休息时间
+export type BackendTransitionReason = 'completed' | 'skipped' | 'reset' | 'switched' | 'scheduled';
+
+export interface BackendTimerTransition {
+  from: BackendTimerPhase;
+  to: BackendTimerPhase;
+  duration: number;
+  reason: BackendTransitionReason;
+}
+
+// 不改变阶段的计时提醒
+export type BackendTimerNotice =
+  | { kind: 'targetReached' }
+  // 已超时的秒数
+  | { kind: 'overtimeReminder'; overtime: number }
+  // 系统时间被调整，为正表示向后调
+  | { kind: 'clockJump'; deltaMs: number }
+  // 系统从休眠中恢复
+  | { kind: 'suspended'; gapMs: number };
+
+// 时长单位为秒，间隔/阈值为 0 表示关闭对应规则
+export interface BackendCyclePolicy {
+  focusDuration: number;
+  breakDuration: number;
+  longBreakDuration: number;
+  longBreakInterval: number;
+  forcedBreakThreshold: number;
+  forcedBreakDuration: number;
+}
+
+export const TIMER_TICK_EVENT = 'timer://tick';
+export const TIMER_TRANSITION_EVENT = 'timer://transition';
+export const TIMER_NOTICE_EVENT = 'timer://notice';
+
+/**
+ * 后端计时器：桌面端的计时在 Tauri 进程中进行，窗口隐藏时也不会被节流。
+ * 所有窗口通过 timer://tick、timer://transition 与 timer://notice 事件获得同一份状态；浏览器环境中不可用
+ */
+class BackendTimerService {
+  isAvailable(): boolean {
+    return isTauriEnvironment();
+  }
+
+  async status(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_status');
+  }
+
+  async start(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_start');
+  }
+
+  async pause(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_pause');
+  }
+
+  /**
+   * 结束当前阶段，进入建议的下一阶段
+   */
+  async skip(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_skip');
+  }
+
+  /**
+   * 当前阶段从头开始并暂停
+   */
+  async reset(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_reset');
+  }
+
+  /**
+   * 立即开始一次微休息
+   */
+  async microBreak(): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_micro_break');
+  }
+
+  /**
+   * 切换到指定阶段，未给出时长（秒）时使用当前模式下的时长
+   */
+  async switchPhase(phase: Exclude<BackendTimerPhase, 'idle'>, duration?: number): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_switch_phase', { phase, duration: duration ?? null });
+  }
+
+  /**
+   * 切换计时模式，新模式按已保存的设置从一个完整的专注段开始（暂停）
+   */
+  async switchMode(mode: TimerMode): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_switch_mode', { mode });
+  }
+
+  /**
+   * 保存计时设置，时长从下一次阶段切换开始生效
+   */
+  async updateSettings(settings: UnifiedTimerSettings): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_update_settings', { settings });
+  }
+
+  /**
+   * 按内置计时方案（pomodoro、52-17、ultradian）运行，传入 null 恢复为循环策略
+   */
+  async usePreset(preset: string | null): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_use_preset', { preset });
+  }
+
+  async setPolicy(policy: BackendCyclePolicy): Promise<BackendTimerTick> {
+    return invoke<BackendTimerTick>('timer_set_policy', { policy });
+  }
+
+  onTick(handler: (tick: BackendTimerTick) => void): Promise<UnlistenFn> {
+    return listen<BackendTimerTick>(TIMER_TICK_EVENT, (event) => handler(event.payload));
+  }
+
+  onTransition(handler: (transition: BackendTimerTransition) => void): Promise<UnlistenFn> {
+    return listen<BackendTimerTransition>(TIMER_TRANSITION_EVENT, (event) => handler(event.payload));
+  }
+
+  onNotice(handler: (notice: BackendTimerNotice) => void): Promise<UnlistenFn> {
+    return listen<BackendTimerNotice>(TIMER_NOTICE_EVENT, (event) => handler(event.payload));
+  }
+}
+
+export { BackendTimerService };
+
+let backendTimerInstance: BackendTimerService | null = null;
+
+export const getBackendTimerService = (): BackendTimerService => {
+  if (!backendTimerInstance) {
+    backendTimerInstance = new BackendTimerService();
+  }
+  return backendTimerInstance;
+};
@@ -1,5 +1,7 @@
+import { invoke } from '@tauri-apps/api/tauri';
 import { logError } from '../utils/errorHandler';
 import { isTauriEnvironment } from '../utils/environment';
+import { wasmTimer } from '../wasm/wasmTimer';
 
 // 数据库接口类型定义
 export interface FocusSession {
@@ -18,98 +20,6 @@ export interface AppSetting {
   updated_at?: string;
 }
 
-// 模拟数据库接口
-class MockDatabase {
-  private data: Map<string, any[]> = new Map();
-  private counter: number = 1;
-
-  async execute(query: string, params?: any[]): Promise<any> {
-    console.log('Mock DB execute:', query, params);
-    
-    // 简单解析SQL语句
-    if (query.includes('CREATE TABLE')) {
-      const tableName = query.match(/CREATE TABLE IF NOT EXISTS (\w+)/)?.[1];
-      if (tableName && !this.data.has(tableName)) {
-        this.data.set(tableName, []);
-      }
-      return { lastInsertId: 0, rowsAffected: 0 };
-    }
-    
-    if (query.includes('INSERT INTO')) {
-      const tableName = query.match(/INSERT INTO (\w+)/)?.[1];
-      if (tableName && this.data.has(tableName)) {
-        const table = this.data.get(tableName)!;
-        const id = this.counter++;
-        const row = { id, ...params };
-        table.push(row);
-        return { lastInsertId: id, rowsAffected: 1 };
-      }
-      return { lastInsertId: 0, rowsAffected: 0 };
-    }
-    
-    if (query.includes('SELECT')) {
-      const tableName = query.match(/FROM (\w+)/)?.[1];
-      if (tableName && this.data.has(tableName)) {
-        const table = this.data.get(tableName)!;
-        // 简单实现，实际应用中需要更复杂的SQL解析
-        if (query.includes('WHERE date = ?') && params?.length) {
-          return table.filter((row: any) => row.date === params[0]);
-        }
-        return table;
-      }
-      return [];
-    }
-    
-    if (query.includes('UPDATE')) {
-      // 简化实现
-      return { rowsAffected: 1 };
-    }
-    
-    if (query.includes('DELETE')) {
-      // 简化实现
-      return { rowsAffected: 1 };
-    }
-    
-    return { lastInsertId: 0, rowsAffected: 0 };
-  }
-  
-  async select<T>(query: string, params?: any[]): Promise<T[]> {
-    return this.execute(query, params) as Promise<T[]>;
-  }
-  
-  async load(dbPath: string): Promise<MockDatabase> {
-    console.log('Mock DB load:', dbPath);
-    // 尝试从localStorage加载数据
-    try {
-      const savedData = localStorage.getItem('focusflow_db');
-      if (savedData) {
-        const parsed = JSON.parse(savedData);
-        this.data = new Map(Object.entries(parsed));
-        // 计算最大ID
-        let maxId = 1;
-        for (const [, rows] of this.data) {
-          for (const row of rows) {
-            if (row.id && row.id > maxId) maxId = row.id;
-          }
-        }
-        this.counter = maxId + 1;
-      }
-    } catch (e) {
-      console.error('Failed to load data from localStorage:', e);
-    }
-    return this;
-  }
-  
-  saveToLocalStorage() {
-    try {
-      const obj = Object.fromEntries(this.data);
-      localStorage.setItem('focusflow_db', JSON.stringify(obj));
-    } catch (e) {
-      console.error('Failed to save data to localStorage:', e);
-    }
-  }
-}
-
 export interface DailyStats {
   date: string;
   total_focus_time: number;
@@ -119,8 +29,45 @@ export interface DailyStats {
   session_count: number;
 }
 
+export interface DatabaseStats {
+  totalSessions: number;
+  totalFocusTime: number;
+  totalBreakTime: number;
+  totalMicroBreaks: number;
+  averageEfficiency: number;
+  firstSessionDate: string | null;
+  lastSessionDate: string | null;
+  bestDay: DailyStats | null;
+}
+
+const EMPTY_DATABASE_STATS: DatabaseStats = {
+  totalSessions: 0,
+  totalFocusTime: 0,
+  totalBreakTime: 0,
+  totalMicroBreaks: 0,
+  averageEfficiency: 0,
+  firstSessionDate: null,
+  lastSessionDate: null,
+  bestDay: null,
+};
+
+// 时刻所属日期，YYYY-MM-DD；按 wasmTimer 的日历划分（设置中的时区、每天的开始时刻，含夏令时）
+const localDate = (timestampMs: number = Date.now()): string => {
+  const key = wasmTimer.dateKey(timestampMs);
+  if (key === null) throw new Error('WASM timer core is not loaded');
+  return key;
+};
+
+const daysAgo = (days: number): string => {
+  const key = wasmTimer.dateKeyDaysAgo(days);
+  if (key === null) throw new Error('WASM timer core is not loaded');
+  return key;
+};
+
+/**
+ * 数据库服务：桌面端通过 Tauri 命令读写后端的 SQLite 数据库，浏览器环境中所有操作为空操作
+ */
 class DatabaseService {
-  private db: MockDatabase | null = null;
   private isInitialized = false;
   private initializationPromise: Promise<void> | null = null;
 
@@ -149,18 +96,14 @@ class DatabaseService {
     }
 
     try {
-      // 连接到模拟数据库
-      this.db = await new MockDatabase().load('sqlite:focusflow.db');
-
-      // 创建表结构
-      await this.createTables();
+      // 数据库由后端在启动时打开，这里确认连接可用
+      await invoke<DatabaseStats>('get_database_stats');
 
       this.isInitialized = true;
       console.log('Database initialized successfully');
     } catch (error) {
       const errorId = logError(error instanceof Error ? error : new Error(String(error)), {
         operation: 'database_initialize',
-        dbPath: 'sqlite:focusflow.db'
       }, 'critical');
 
       console.error('Failed to initialize database:', error);
@@ -168,52 +111,11 @@ class DatabaseService {
       // 重置状态以允许重试
       this.initializationPromise = null;
       this.isInitialized = false;
-      this.db = null;
 
       throw new Error(`数据库初始化失败 (错误ID: ${errorId})`);
     }
   }
 
-  /**
-   * 创建数据库表结构
-   */
-  private async createTables(): Promise<void> {
-    if (!this.db) throw new Error('Database not initialized');
-
-    // 创建专注会话表
-    await this.db.execute(`
-      CREATE TABLE IF NOT EXISTS focus_sessions (
-        id INTEGER PRIMARY KEY AUTOINCREMENT,
-        date TEXT NOT NULL,
-        focus_duration INTEGER NOT NULL,
-        break_duration INTEGER NOT NULL,
-        micro_breaks INTEGER DEFAULT 0,
-        efficiency_score REAL DEFAULT 0,
-        created_at DATETIME DEFAULT CURRENT_TIMESTAMP
-      )
-    `);
-
-    // 创建应用设置表
-    await this.db.execute(`
-      CREATE TABLE IF NOT EXISTS app_settings (
-        key TEXT PRIMARY KEY,
-        value TEXT NOT NULL,
-        updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
-      )
-    `);
-
-    // 创建索引以提高查询性能
-    await this.db.execute(`
-      CREATE INDEX IF NOT EXISTS idx_sessions_date 
-      ON focus_sessions(date)
-    `);
-
-    await this.db.execute(`
-      CREATE INDEX IF NOT EXISTS idx_sessions_created_at 
-      ON focus_sessions(created_at)
-    `);
-  }
-
   /**
    * 保存专注会话数据
    */
@@ -223,39 +125,14 @@ class DatabaseService {
       return 0;
     }
 
-    if (!this.db) await this.initialize();
-
-    const result = await this.db!.execute(
-      `INSERT INTO focus_sessions
-       (date, focus_duration, break_duration, micro_breaks, efficiency_score)
-       VALUES (?, ?, ?, ?, ?)`,
-      [
-        session.date,
-        session.focus_duration,
-        session.break_duration,
-        session.micro_breaks,
-        session.efficiency_score
-      ]
-    );
-    
-    // 保存到localStorage
-    this.db!.saveToLocalStorage();
-
-    return result.lastInsertId || 0;
+    return invoke<number>('save_focus_session', { session });
   }
 
   /**
    * 获取指定日期的专注会话
    */
   async getFocusSessionsByDate(date: string): Promise<FocusSession[]> {
-    if (!this.db) await this.initialize();
-
-    const sessions = await this.db!.select<FocusSession[]>(
-      'SELECT * FROM focus_sessions WHERE date = ? ORDER BY created_at DESC',
-      [date]
-    );
-
-    return sessions;
+    return this.getFocusSessionsByDateRange(date, date);
   }
 
   /**
@@ -267,14 +144,14 @@ class DatabaseService {
       return [];
     }
 
-    if (!this.db) await this.initialize();
-
-    const sessions = await this.db!.select<FocusSession[]>(
-      'SELECT * FROM focus_sessions WHERE date BETWEEN ? AND ? ORDER BY created_at DESC',
-      [startDate, endDate]
-    );
+    return invoke<FocusSession[]>('get_focus_sessions_by_date_range', { startDate, endDate });
+  }
 
-    return sessions;
+  /**
+   * 获取全部专注会话
+   */
+  async getAllFocusSessions(): Promise<FocusSession[]> {
+    return this.getFocusSessionsByDateRange('0000-01-01', '9999-12-31');
   }
 
   /**
@@ -286,22 +163,7 @@ class DatabaseService {
       return null;
     }
 
-    if (!this.db) await this.initialize();
-
-    const stats = await this.db!.select<DailyStats[]>(
-      `SELECT
-         date,
-         SUM(focus_duration) as total_focus_time,
-         SUM(break_duration) as total_break_time,
-         SUM(micro_breaks) as total_micro_breaks,
-         AVG(efficiency_score) as average_efficiency,
-         COUNT(*) as session_count
-       FROM focus_sessions
-       WHERE date = ?
-       GROUP BY date`,
-      [date]
-    );
-
+    const stats = await invoke<DailyStats[]>('get_daily_stats', { startDate: date, endDate: date });
     return stats.length > 0 ? stats[0] : null;
   }
 
@@ -314,49 +176,25 @@ class DatabaseService {
       return [];
     }
 
-    if (!this.db) await this.initialize();
-
-    const stats = await this.db!.select<DailyStats[]>(
-      `SELECT
-         date,
-         SUM(focus_duration) as total_focus_time,
-         SUM(break_duration) as total_break_time,
-         SUM(micro_breaks) as total_micro_breaks,
-         AVG(efficiency_score) as average_efficiency,
-         COUNT(*) as session_count
-       FROM focus_sessions
-       WHERE date >= date('now', '-${days} days')
-       GROUP BY date
-       ORDER BY date DESC`,
-      []
-    );
-
-    return stats;
+    return invoke<DailyStats[]>('get_daily_stats', { startDate: daysAgo(days), endDate: localDate() });
   }
 
   /**
    * 更新专注会话的效率评分
    */
   async updateSessionEfficiency(sessionId: number, efficiency: number): Promise<void> {
-    if (!this.db) await this.initialize();
+    if (!isTauriEnvironment()) return;
 
-    await this.db!.execute(
-      'UPDATE focus_sessions SET efficiency_score = ? WHERE id = ?',
-      [efficiency, sessionId]
-    );
+    await invoke<boolean>('update_session_efficiency', { sessionId, efficiency });
   }
 
   /**
    * 保存应用设置
    */
   async saveSetting(key: string, value: string): Promise<void> {
-    if (!this.db) await this.initialize();
+    if (!isTauriEnvironment()) return;
 
-    await this.db!.execute(
-      `INSERT OR REPLACE INTO app_settings (key, value, updated_at) 
-       VALUES (?, ?, CURRENT_TIMESTAMP)`,
-      [key, value]
-    );
+    await invoke('save_setting', { key, value });
   }
 
   /**
@@ -368,115 +206,48 @@ class DatabaseService {
       return null;
     }
 
-    if (!this.db) await this.initialize();
-
-    const settings = await this.db!.select<AppSetting[]>(
-      'SELECT value FROM app_settings WHERE key = ?',
-      [key]
-    );
-
-    return settings.length > 0 ? settings[0].value : null;
+    return invoke<string | null>('get_setting', { key });
   }
 
   /**
    * 获取所有应用设置
    */
   async getAllSettings(): Promise<Record<string, string>> {
-    if (!this.db) await this.initialize();
-
     // 在非Tauri环境中，返回空对象
-    if (!this.db) {
+    if (!isTauriEnvironment()) {
       return {};
     }
 
-    const settings = await this.db.select<AppSetting[]>(
-      'SELECT key, value FROM app_settings',
-      []
-    );
-
-    const result: Record<string, string> = {};
-    settings.forEach(setting => {
-      result[setting.key] = setting.value;
-    });
-
-    return result;
+    return invoke<Record<string, string>>('get_all_settings');
   }
 
   /**
    * 删除指定日期之前的旧数据
    */
   async cleanupOldData(beforeDate: string): Promise<number> {
-    if (!this.db) await this.initialize();
-
-    const result = await this.db!.execute(
-      'DELETE FROM focus_sessions WHERE date < ?',
-      [beforeDate]
-    );
+    if (!isTauriEnvironment()) return 0;
 
-    return result.rowsAffected;
+    return invoke<number>('cleanup_old_data', { beforeDate });
   }
 
   /**
    * 获取数据库统计信息
    */
-  async getDatabaseStats(): Promise<{
-    totalSessions: number;
-    totalFocusTime: number;
-    totalBreakTime: number;
-    totalMicroBreaks: number;
-    averageEfficiency: number;
-    firstSessionDate: string | null;
-    lastSessionDate: string | null;
-  }> {
+  async getDatabaseStats(): Promise<DatabaseStats> {
     if (!isTauriEnvironment()) {
       console.warn('Database operation skipped: not in Tauri environment');
-      return {
-        totalSessions: 0,
-        totalFocusTime: 0,
-        totalBreakTime: 0,
-        totalMicroBreaks: 0,
-        averageEfficiency: 0,
-        firstSessionDate: null,
-        lastSessionDate: null,
-      };
+      return { ...EMPTY_DATABASE_STATS };
     }
 
-    if (!this.db) await this.initialize();
-
-    const stats = await this.db!.select<any[]>(
-      `SELECT
-         COUNT(*) as total_sessions,
-         SUM(focus_duration) as total_focus_time,
-         SUM(break_duration) as total_break_time,
-         SUM(micro_breaks) as total_micro_breaks,
-         AVG(efficiency_score) as average_efficiency,
-         MIN(date) as first_session_date,
-         MAX(date) as last_session_date
-       FROM focus_sessions`,
-      []
-    );
-
-    const result = stats[0] || {};
-    return {
-      totalSessions: result.total_sessions || 0,
-      totalFocusTime: result.total_focus_time || 0,
-      totalBreakTime: result.total_break_time || 0,
-      totalMicroBreaks: result.total_micro_breaks || 0,
-      averageEfficiency: result.average_efficiency || 0,
-      firstSessionDate: result.first_session_date || null,
-      lastSessionDate: result.last_session_date || null,
-    };
+    return invoke<DatabaseStats>('get_database_stats');
   }
 
   /**
-   * 关闭数据库连接
+   * 关闭数据库连接（连接由后端持有，这里只重置初始化状态）
    */
   async close(): Promise<void> {
-    if (this.db) {
-      await this.db.close();
-      this.db = null;
-      this.isInitialized = false;
-    }
+    this.isInitialized = false;
+    this.initializationPromise = null;
   }
 
   /**
@@ -496,49 +267,20 @@ class DatabaseService {
       return 0;
     }
 
-    if (!this.db) await this.initialize();
-
-    // 计算日期
-    const date = new Date().toISOString().split('T')[0];
-
-    const result = await this.db!.execute(
-      `INSERT INTO focus_sessions
-       (date, focus_duration, break_duration, micro_breaks, efficiency_score)
-       VALUES (?, ?, ?, ?, ?)`,
-      [
-        date,
-        session.focusTime,
-        session.breakTime,
-        session.microBreaks,
-        session.efficiency || 0
-      ]
-    );
-
-    // 保存到localStorage
-    this.db!.saveToLocalStorage();
-
-    return result.lastInsertId || 0;
+    return this.saveFocusSession({
+      date: localDate(session.startTime || Date.now()),
+      focus_duration: session.focusTime,
+      break_duration: session.breakTime,
+      micro_breaks: session.microBreaks,
+      efficiency_score: session.efficiency || 0,
+    });
   }
 
   /**
    * 获取最近N天的会话（兼容unifiedTimerStore）
    */
-  async getRecentSessions(days: number = 7): Promise<any[]> {
-    if (!isTauriEnvironment()) {
-      console.warn('Database operation skipped: not in Tauri environment');
-      return [];
-    }
-
-    if (!this.db) await this.initialize();
-
-    const sessions = await this.db!.select<any[]>(
-      `SELECT * FROM focus_sessions 
-       WHERE date >= date('now', '-${days} days') 
-       ORDER BY created_at DESC`,
-      []
-    );
-
-    return sessions;
+  async getRecentSessions(days: number = 7): Promise<FocusSession[]> {
+    return this.getFocusSessionsByDateRange(daysAgo(days), localDate());
   }
 
   /**
@@ -549,55 +291,15 @@ class DatabaseService {
     totalFocusTime: number;
     totalBreakTime: number;
     averageEfficiency: number;
-    bestDay: any;
+    bestDay: DailyStats | null;
   }> {
-    if (!isTauriEnvironment()) {
-      console.warn('Database operation skipped: not in Tauri environment');
-      return {
-        totalSessions: 0,
-        totalFocusTime: 0,
-        totalBreakTime: 0,
-        averageEfficiency: 0,
-        bestDay: null
-      };
-    }
-
-    if (!this.db) await this.initialize();
-
-    // 获取总体统计
-    const totalStats = await this.db!.select<any[]>(
-      `SELECT
-         COUNT(*) as total_sessions,
-         SUM(focus_duration) as total_focus_time,
-         SUM(break_duration) as total_break_time,
-         AVG(efficiency_score) as average_efficiency
-       FROM focus_sessions`,
-      []
-    );
-
-    const stats = totalStats[0] || {};
-
-    // 获取最佳效率的一天
-    const bestDayResult = await this.db!.select<any[]>(
-      `SELECT 
-         date,
-         AVG(efficiency_score) as avg_efficiency,
-         SUM(focus_duration) as total_focus
-       FROM focus_sessions 
-       GROUP BY date 
-       ORDER BY avg_efficiency DESC 
-       LIMIT 1`,
-      []
-    );
-
-    const bestDay = bestDayResult.length > 0 ? bestDayResult[0] : null;
-
+    const stats = await this.getDatabaseStats();
     return {
-      totalSessions: stats.total_sessions || 0,
-      totalFocusTime: stats.total_focus_time || 0,
-      totalBreakTime: stats.total_break_time || 0,
-      averageEfficiency: stats.average_efficiency || 0,
-      bestDay: bestDay
+      totalSessions: stats.totalSessions,
+      totalFocusTime: stats.totalFocusTime,
+      totalBreakTime: stats.totalBreakTime,
+      averageEfficiency: stats.averageEfficiency,
+      bestDay: stats.bestDay,
     };
   }
 }
@@ -0,0 +1,70 @@
+import { invoke } from '@tauri-apps/api/tauri';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import { isTauriEnvironment } from '../utils/environment';
+
+// 后端全局快捷键动作，与 shortcuts.rs 中的 ShortcutAction 对应
+export type BackendShortcutAction = 'toggleWindow' | 'startPause' | 'skip' | 'microBreak' | 'showStats';
+
+export interface ShortcutBinding {
+  action: BackendShortcutAction;
+  // Tauri 加速键格式，如 CmdOrCtrl+Shift+F
+  accelerator: string;
+  enabled: boolean;
+}
+
+export interface ShortcutStatus extends ShortcutBinding {
+  registered: boolean;
+  // 系统拒绝注册的原因，如已被其他程序占用
+  error: string | null;
+}
+
+export const SHORTCUT_SHOW_STATS_EVENT = 'shortcut://show-stats';
+
+/**
+ * 全局快捷键服务：快捷键由后端注册并直接操作计时器与窗口，绑定保存在设置中。
+ * 浏览器环境中没有全局快捷键，所有操作为空操作
+ */
+class ShortcutService {
+  isAvailable(): boolean {
+    return isTauriEnvironment();
+  }
+
+  /**
+   * 当前绑定及其注册结果，包括启动时注册失败的快捷键
+   */
+  async getShortcuts(): Promise<ShortcutStatus[]> {
+    if (!isTauriEnvironment()) return [];
+
+    return invoke<ShortcutStatus[]>('get_global_shortcuts');
+  }
+
+  /**
+   * 修改绑定并立即重新注册，只需传入改动的绑定；加速键无效、绑定重复或为空时抛出错误
+   */
+  async setShortcuts(bindings: ShortcutBinding[]): Promise<ShortcutStatus[]> {
+    if (!isTauriEnvironment()) return [];
+
+    return invoke<ShortcutStatus[]>('set_global_shortcuts', { bindings });
+  }
+
+  async resetShortcuts(): Promise<ShortcutStatus[]> {
+    if (!isTauriEnvironment()) return [];
+
+    return invoke<ShortcutStatus[]>('reset_global_shortcuts');
+  }
+
+  onShowStats(handler: () => void): Promise<UnlistenFn> {
+    return listen(SHORTCUT_SHOW_STATS_EVENT, () => handler());
+  }
+}
+
+export { ShortcutService };
+
+let shortcutServiceInstance: ShortcutService | null = null;
+
+export const getShortcutService = (): ShortcutService => {
+  if (!shortcutServiceInstance) {
+    shortcutServiceInstance = new ShortcutService();
+  }
+  return shortcutServiceInstance;
+};
@@ -3,10 +3,12 @@
  * 测试Zustand store的状态管理功能
  */
 
-import { act, renderHook } from '@testing-library/react';
+import { act, renderHook, waitFor } from '@testing-library/react';
 import { useUnifiedTimerStore } from '../unifiedTimerStore';
+import { getBackendTimerService, type BackendTimerTick } from '../../services/backendTimer';
 import type { TimerState, TimerSettings, EfficiencyRatingData } from '../../types/unifiedTimer';
 import { TimerMode } from '../../types/unifiedTimer';
+import { wasmTimer } from '../../wasm/wasmTimer';
 
 // Mock dependencies
 jest.mock('../../services/crypto', () => ({
@@ -43,6 +45,39 @@ jest.mock('../../services/database', () => ({
   },
 }));
 
+// 默认不在 Tauri 中，Backend Timer 用例中打开
+jest.mock('../../services/backendTimer', () => {
+  const service = {
+    isAvailable: jest.fn(() => false),
+    start: jest.fn(),
+    pause: jest.fn(),
+    skip: jest.fn(),
+    reset: jest.fn(),
+    microBreak: jest.fn(),
+    switchMode: jest.fn(),
+    updateSettings: jest.fn(),
+  };
+  return { getBackendTimerService: () => service };
+});
+
+const backendTick = (tick: Partial<BackendTimerTick>): BackendTimerTick => ({
+  state: 'focus',
+  running: false,
+  time: 25 * 60,
+  formattedTime: '25:00',
+  remaining: 25 * 60,
+  duration: 25 * 60,
+  progress: 0,
+  cycle: 1,
+  cyclesPerSet: 4,
+  remainingMs: 25 * 60 * 1000,
+  endsAt: 0,
+  overtimeMs: 0,
+  mode: TimerMode.CLASSIC,
+  preset: null,
+  ...tick,
+});
+
 describe('TimerStore', () => {
   beforeEach(() => {
     jest.clearAllMocks();
@@ -381,6 +416,21 @@ describe('TimerStore', () => {
       expect(result.current.pendingRatingSession).toBeNull();
       expect(result.current.recentEfficiencyScores).toContain(4);
     });
+
+    it('scores the focus session through wasm', async () => {
+      await wasmTimer.ready();
+      const { result } = renderHook(() => useUnifiedTimerStore());
+
+      act(() => {
+        result.current.showEfficiencyRating({ duration: 20, plannedDuration: 25, type: 'focus' });
+        result.current.submitEfficiencyRating(4);
+      });
+
+      // 完成度 80、无暂停 100、评分 4 → 75，按权重归一后为 82
+      expect(result.current.lastEfficiencyScore?.score).toBe(82);
+      expect(result.current.lastEfficiencyScore?.breakdown.map((item) => item.factor))
+        .toEqual(['completion', 'pauses', 'rating']);
+    });
   });
 
   describe('Statistics', () => {
@@ -427,6 +477,132 @@ describe('TimerStore', () => {
     });
   });
 
+  describe('Flow Mode', () => {
+    // 休息建议来自 WASM 的 suggest_flow_break
+    beforeAll(() => wasmTimer.ready());
+
+    afterEach(() => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+      act(() => {
+        result.current.switchMode(TimerMode.CLASSIC);
+      });
+    });
+
+    it('counts up past the target and keeps the soft target as totalTime', () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+
+      act(() => {
+        result.current.switchMode(TimerMode.FLOW);
+      });
+      expect(result.current.totalTime).toBe(50 * 60);
+
+      act(() => {
+        result.current.updateElapsedTime(60 * 60);
+      });
+      expect(result.current.elapsedTime).toBe(60 * 60);
+      expect(result.current.timeLeft).toBe(0);
+    });
+
+    it('sizes the break from the focused time', () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+
+      act(() => {
+        result.current.switchMode(TimerMode.FLOW);
+        result.current.updateElapsedTime(60 * 60);
+      });
+      act(() => {
+        result.current.transitionTo('break');
+      });
+
+      // 60 分钟 × 0.2 = 12 分钟，在 5–30 分钟范围内
+      expect(result.current.currentState).toBe('break');
+      expect(result.current.timeLeft).toBe(12 * 60);
+      expect(result.current.elapsedTime).toBe(0);
+    });
+
+    it('clamps short flow sessions to the minimum break', () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+
+      act(() => {
+        result.current.switchMode(TimerMode.FLOW);
+        result.current.updateElapsedTime(10 * 60);
+      });
+      act(() => {
+        result.current.transitionTo('break');
+      });
+
+      expect(result.current.timeLeft).toBe(5 * 60);
+    });
+  });
+
+  describe('Backend Timer', () => {
+    const backend = getBackendTimerService() as unknown as Record<string, jest.Mock>;
+
+    beforeEach(() => {
+      backend.isAvailable.mockReturnValue(true);
+    });
+
+    afterEach(() => {
+      backend.isAvailable.mockReturnValue(false);
+    });
+
+    it('sends controls to the timer commands', async () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+      backend.start.mockResolvedValue(backendTick({ running: true }));
+      backend.skip.mockResolvedValue(backendTick({ state: 'break', remaining: 5 * 60, duration: 5 * 60 }));
+
+      act(() => {
+        result.current.start();
+      });
+      expect(backend.start).toHaveBeenCalledTimes(1);
+      await waitFor(() => expect(result.current.isActive).toBe(true));
+
+      act(() => {
+        result.current.skipToNext();
+      });
+      expect(backend.skip).toHaveBeenCalledTimes(1);
+      await waitFor(() => expect(result.current.currentState).toBe('break'));
+      expect(result.current.timeLeft).toBe(5 * 60);
+    });
+
+    it('reads state from backend ticks', () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+
+      act(() => {
+        result.current.applyBackendTick(backendTick({ state: 'longBreak', running: true, remaining: 600, duration: 900 }));
+      });
+      expect(result.current.currentState).toBe('break');
+      expect(result.current.isActive).toBe(true);
+      expect(result.current.timeLeft).toBe(600);
+      expect(result.current.elapsedTime).toBe(300);
+
+      // 心流专注段正计时，显示时间即已计时
+      act(() => {
+        result.current.applyBackendTick(
+          backendTick({ mode: TimerMode.FLOW, time: 60 * 60, remaining: 0, duration: 50 * 60 })
+        );
+      });
+      expect(result.current.currentMode).toBe(TimerMode.FLOW);
+      expect(result.current.elapsedTime).toBe(60 * 60);
+      expect(result.current.timeLeft).toBe(0);
+    });
+
+    it('counts micro breaks from backend transitions', () => {
+      const { result } = renderHook(() => useUnifiedTimerStore());
+      const count = result.current.microBreakCount;
+
+      act(() => {
+        result.current.handleBackendTransition({
+          from: 'focus',
+          to: 'microBreak',
+          duration: 3 * 60,
+          reason: 'scheduled',
+        });
+      });
+      expect(result.current.microBreakCount).toBe(count + 1);
+    });
+  });
+
   describe('Error Handling', () => {
     it('handles invalid state transitions gracefully', () => {
       const { result } = renderHook(() => useUnifiedTimerStore());
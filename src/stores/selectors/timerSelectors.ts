@@ -76,7 +76,8 @@ export const useEfficiencyRating = createSelector((state) => ({
   pendingRatingSession: state.pendingRatingSession,
   hideEfficiencyRating: state.hideEfficiencyRating,
   submitEfficiencyRating: state.submitEfficiencyRating,
-  recentEfficiencyScores: state.recentEfficiencyScores
+  recentEfficiencyScores: state.recentEfficiencyScores,
+  lastEfficiencyScore: state.lastEfficiencyScore
 }));
 
 // 微休息选择器
@@ -26,6 +26,8 @@ describe('settingsStore', () => {
     showKeyboardShortcuts: false,
     showMicroBreakReminders: true,
     microBreakInterval: 20,
+    timeZone: '',
+    dayStartHour: 0,
   };
 
   const mockCustomSettings = {
@@ -44,6 +46,8 @@ describe('settingsStore', () => {
     showKeyboardShortcuts: true,
     showMicroBreakReminders: false,
     microBreakInterval: 15,
+    timeZone: 'Asia/Shanghai',
+    dayStartHour: 4,
   };
 
   beforeEach(() => {
@@ -128,6 +132,20 @@ describe('settingsStore', () => {
     consoleWarnSpy.mockRestore();
   });
 
+  it('should reject a day start outside 0-23', () => {
+    const state = useTestSettingsStore.getState();
+    const consoleWarnSpy = vi.spyOn(console, 'warn').mockImplementation(() => {});
+
+    state.updateSettings({ dayStartHour: 24 });
+    expect(useTestSettingsStore.getState().settings.dayStartHour).toBe(0);
+
+    state.updateSettings({ dayStartHour: 4 });
+    expect(useTestSettingsStore.getState().settings.dayStartHour).toBe(4);
+
+    expect(consoleWarnSpy).toHaveBeenCalledTimes(1);
+    consoleWarnSpy.mockRestore();
+  });
+
   it('should reset to default settings', () => {
     const state = useTestSettingsStore.getState();
 
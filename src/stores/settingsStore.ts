@@ -33,6 +33,10 @@ interface AppSettings {
   // 微休息设置
   showMicroBreakReminders: boolean;
   microBreakInterval: number;
+
+  // 日期设置：统计和会话记录按此划分日期
+  timeZone: string;      // IANA 时区名，空字符串为系统时区
+  dayStartHour: number;  // 每天开始的本地整点（0-23）
 }
 
 // 主题设置
@@ -79,6 +83,8 @@ const defaultSettings: AppSettings = {
   showKeyboardShortcuts: false,
   showMicroBreakReminders: true,
   microBreakInterval: 20,
+  timeZone: '',
+  dayStartHour: 0,
 };
 
 // 创建设置存储
@@ -237,6 +243,20 @@ function validateSettings(settings: AppSettings): AppSettings {
     validatedSettings.microBreakInterval = defaultSettings.microBreakInterval;
   }
 
+  // 验证每天开始时刻
+  if (!Number.isInteger(validatedSettings.dayStartHour) ||
+      validatedSettings.dayStartHour < 0 ||
+      validatedSettings.dayStartHour > 23) {
+    console.warn(`Invalid day start hour: ${validatedSettings.dayStartHour}. Using default: ${defaultSettings.dayStartHour}`);
+    validatedSettings.dayStartHour = defaultSettings.dayStartHour;
+  }
+
+  // 验证时区（具体时区名由 WASM 日历校验）
+  if (typeof validatedSettings.timeZone !== 'string') {
+    console.warn(`Invalid time zone: ${validatedSettings.timeZone}. Using default: system time zone`);
+    validatedSettings.timeZone = defaultSettings.timeZone;
+  }
+
   // 验证主题
   if (!['light', 'dark', 'system'].includes(validatedSettings.theme)) {
     console.warn(`Invalid theme: ${validatedSettings.theme}. Using default: ${defaultSettings.theme}`);
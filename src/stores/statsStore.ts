@@ -6,6 +6,9 @@
 import { create } from 'zustand';
 import { devtools } from 'zustand/middleware';
 import { getStats, saveStats } from '../utils/storageUtils';
+import { isTauriEnvironment } from '../utils/environment';
+import { getDatabaseService, FocusSession } from '../services/database';
+import { wasmTimer, PeriodSeries } from '../wasm/wasmTimer';
 
 // 时间段统计数据接口
 interface TimeStats {
@@ -95,6 +98,77 @@ const defaultStats: StatsData = {
   sessionHistory: [],
 };
 
+const emptyTimeStats: TimeStats = defaultStats.daily;
+
+// SQLite CURRENT_TIMESTAMP 为 UTC 的 "YYYY-MM-DD HH:MM:SS"
+const parseCreatedAt = (createdAt: string): number => Date.parse(`${createdAt.replace(' ', 'T')}Z`);
+
+// 把数据库中的会话记录交给 WASM 一次性汇总，日期按 wasmTimer 的日历划分；WASM 不可用时返回 null
+const aggregateSessions = (sessions: FocusSession[], nowMs: number = Date.now()): StatsData | null => {
+  const rows = sessions.filter(session => session.created_at);
+  const columns = {
+    startedAt: new Float64Array(rows.length),
+    endedAt: new Float64Array(rows.length),
+    focus: new Uint32Array(rows.length),
+    breakTime: new Uint32Array(rows.length),
+    microBreaks: new Uint32Array(rows.length),
+    efficiency: new Float64Array(rows.length),
+  };
+  rows.forEach((session, i) => {
+    // created_at 是会话保存（结束）的时间，按专注时长倒推开始时间
+    const endedAt = parseCreatedAt(session.created_at!);
+    columns.startedAt[i] = endedAt - session.focus_duration * 60000;
+    columns.endedAt[i] = endedAt;
+    columns.focus[i] = session.focus_duration;
+    columns.breakTime[i] = session.break_duration;
+    columns.microBreaks[i] = session.micro_breaks;
+    columns.efficiency[i] = session.efficiency_score;
+  });
+
+  const report = wasmTimer.aggregateSessions(columns, nowMs);
+  const keys = wasmTimer.periodKeys(nowMs);
+  if (!report || !keys) return null;
+
+  // 数据库只保存已结束的会话，会话数即完成数
+  const period = (series: PeriodSeries, key: number): TimeStats => {
+    const i = series.keys.indexOf(key);
+    if (i < 0) return { ...emptyTimeStats };
+    return {
+      focusSessions: series.sessions[i],
+      totalFocusTime: series.focus[i],
+      completedSessions: series.sessions[i],
+      averageFocusDuration: series.sessions[i] > 0 ? Math.round(series.focus[i] / series.sessions[i]) : 0,
+      efficiencyScore: Math.round(series.averageEfficiency[i]),
+    };
+  };
+
+  const sessionHistory: SessionHistoryItem[] = [];
+  for (let i = report.daily.keys.length - 1; i >= 0 && sessionHistory.length < 30; i--) {
+    sessionHistory.push({
+      date: wasmTimer.dayDateKey(report.daily.keys[i]) ?? '',
+      focusTime: report.daily.focus[i],
+      sessions: report.daily.sessions[i],
+      efficiencyScore: Math.round(report.daily.averageEfficiency[i]),
+    });
+  }
+
+  return {
+    daily: period(report.daily, keys.day),
+    weekly: period(report.weekly, keys.week),
+    monthly: period(report.monthly, keys.month),
+    allTime: {
+      focusSessions: report.totalSessions,
+      totalFocusTime: report.totalFocus,
+      completedSessions: report.totalSessions,
+      averageFocusDuration: Math.round(report.averageSessionFocus),
+      efficiencyScore: Math.round(report.averageEfficiency),
+    },
+    focusStreak: report.currentStreak,
+    longestStreak: report.longestStreak,
+    sessionHistory,
+  };
+};
+
 // 创建统计存储
 export const statsStore = create<StatsState>()(
   devtools(
@@ -109,7 +183,12 @@ export const statsStore = create<StatsState>()(
         set({ isLoading: true, error: null });
 
         try {
-          const savedStats = await getStats();
+          // 桌面端以数据库中的会话记录为准，浏览器环境使用本地保存的统计
+          const sessions = isTauriEnvironment()
+            ? await getDatabaseService().getAllFocusSessions()
+            : null;
+          const aggregated = sessions ? aggregateSessions(sessions) : null;
+          const savedStats = aggregated ?? await getStats();
 
           set({
             stats: savedStats || defaultStats,
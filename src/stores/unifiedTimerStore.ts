@@ -14,27 +14,44 @@ import {
   UnifiedTimerControls,
   ModeSwitchOptions,
   DEFAULT_UNIFIED_SETTINGS,
-  ClassicTimerSettings,
-  SmartTimerSettings
+  FlowTimerSettings
 } from '../types/unifiedTimer';
+import { wasmTimer } from '../wasm/wasmTimer';
 import { getSoundService } from '../services/sound';
 import { getNotificationService } from '../services/notification';
 import { getDatabaseService } from '../services/database';
+import {
+  getBackendTimerService,
+  type BackendTimerNotice,
+  type BackendTimerPhase,
+  type BackendTimerTick,
+  type BackendTimerTransition
+} from '../services/backendTimer';
 
 // Store接口定义
 interface UnifiedTimerStore extends UnifiedState, UnifiedTimerControls {
+  // 本阶段已计时（秒）；心流模式的专注段正计时，以此为显示时间
+  elapsedTime: number;
+
   // 数据持久化
   saveToStorage: () => Promise<void>;
   loadFromStorage: () => Promise<void>;
 
   // 内部方法
   updateTimeLeft: (timeLeft: number) => void;
+  updateElapsedTime: (elapsedTime: number) => void;
   transitionTo: (state: UnifiedTimerStateType) => void;
   scheduleNextMicroBreak: () => void;
   checkMicroBreakTrigger: () => boolean;
   handleTimeUp: () => void;
   showEfficiencyRating: (session: { duration: number; type: UnifiedTimerStateType; sessionId?: number }) => void;
 
+  // 后端计时（桌面端）：状态来自 timer://tick 等事件，控制方法转为 timer_* 命令
+  connectBackend: () => Promise<void>;
+  applyBackendTick: (tick: BackendTimerTick) => void;
+  handleBackendTransition: (transition: BackendTimerTransition) => void;
+  handleBackendNotice: (notice: BackendTimerNotice) => void;
+
   // 设置管理
   settings: UnifiedTimerSettings;
   showSettings: boolean;
@@ -51,14 +68,64 @@ interface UnifiedTimerStore extends UnifiedState, UnifiedTimerControls {
   updateTodayStats: (type: 'focus' | 'break' | 'microBreak', duration: number) => void;
 }
 
+const backendTimer = getBackendTimerService();
+// 事件监听在整个应用生命周期内有效，只建立一次
+let backendConnection: Promise<void> | null = null;
+
+// 后端的长休息按普通休息显示，空闲按专注显示
+const fromBackendPhase = (phase: BackendTimerPhase): UnifiedTimerStateType => {
+  switch (phase) {
+    case 'break':
+    case 'longBreak':
+      return 'break';
+    case 'microBreak':
+    case 'forcedBreak':
+      return phase;
+    default:
+      return 'focus';
+  }
+};
+
+// 执行后端命令并以返回的状态更新 store；状态随后也会通过 timer://tick 广播
+const runBackend = (command: () => Promise<BackendTimerTick>) => {
+  command()
+    .then((tick) => useUnifiedTimerStore.getState().applyBackendTick(tick))
+    .catch((error) => console.error('Backend timer command failed:', error));
+};
+
+// 心流模式设置，旧版本保存的设置中可能缺失
+const getFlowSettings = (settings: UnifiedTimerSettings): FlowTimerSettings =>
+  settings.flow ?? DEFAULT_UNIFIED_SETTINGS.flow!;
+
+// 各模式的专注/休息时长（分钟）；心流模式的专注时长是软目标，休息时长在专注结束时按比例计算
+const getPhaseDurations = (settings: UnifiedTimerSettings, mode: TimerMode) => {
+  switch (mode) {
+    case TimerMode.SMART:
+      return settings.smart;
+    case TimerMode.FLOW: {
+      const flow = getFlowSettings(settings);
+      return { focusDuration: flow.targetDuration, breakDuration: flow.minBreakDuration };
+    }
+    default:
+      return settings.classic;
+  }
+};
+
+// 心流模式的专注段正计时，没有终点
+export const isCountingUp = (mode: TimerMode, state: UnifiedTimerStateType): boolean =>
+  mode === TimerMode.FLOW && state === 'focus';
+
 // 获取初始状态
-const getInitialState = (settings: UnifiedTimerSettings): Omit<UnifiedState, 'currentMode'> => {
-  const currentSettings = settings.mode === TimerMode.CLASSIC ? settings.classic : settings.smart;
+const getInitialState = (
+  settings: UnifiedTimerSettings
+): Omit<UnifiedState, 'currentMode'> & { elapsedTime: number } => {
+  const currentSettings = getPhaseDurations(settings, settings.mode);
   
   return {
     currentState: 'focus',
     timeLeft: currentSettings.focusDuration * 60,
     totalTime: currentSettings.focusDuration * 60,
+    elapsedTime: 0,
     isActive: false,
     sessionStartTime: 0,
     focusStartTime: 0,
@@ -88,6 +155,7 @@ const getInitialState = (settings: UnifiedTimerSettings): Omit<UnifiedState, 'cu
     },
     showRatingDialog: false,
     pendingRatingSession: null,
+    lastEfficiencyScore: null,
     showSettings: false,
   };
 };
@@ -105,7 +173,11 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       start: () => set((state) => {
         if (state.isActive) return;
         
-        state.isActive = true;
+        if (backendTimer.isAvailable()) {
+          runBackend(() => backendTimer.start());
+        } else {
+          state.isActive = true;
+        }
         
         // 初始化会话时间
         if (state.sessionStartTime === 0) {
@@ -130,10 +202,18 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       }),
       
       pause: () => set((state) => {
+        if (backendTimer.isAvailable()) {
+          runBackend(() => backendTimer.pause());
+          return;
+        }
         state.isActive = false;
       }),
       
       reset: () => set((state) => {
+        if (backendTimer.isAvailable()) {
+          runBackend(() => backendTimer.reset());
+          return;
+        }
         const newState = getInitialState(state.settings);
         Object.assign(state, newState);
         state.currentMode = state.settings.mode;
@@ -163,6 +243,14 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
         state.currentMode = mode;
         state.settings.mode = mode;
         
+        // 后端按保存的设置切换，并从新模式的专注段开头开始
+        if (backendTimer.isAvailable()) {
+          const settings = { ...get().settings, mode };
+          runBackend(() => backendTimer.updateSettings(settings).then(() => backendTimer.switchMode(mode)));
+          get().saveToStorage();
+          return;
+        }
+        
         // 重置或保留状态
         if (options.resetOnSwitch) {
           const newState = getInitialState(state.settings);
@@ -174,7 +262,7 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
           }
         } else {
           // 调整时间设置以匹配新模式
-          const newSettings = mode === TimerMode.CLASSIC ? state.settings.classic : state.settings.smart;
+          const newSettings = getPhaseDurations(state.settings, mode);
           if (state.currentState === 'focus') {
             state.timeLeft = newSettings.focusDuration * 60;
             state.totalTime = newSettings.focusDuration * 60;
@@ -182,6 +270,7 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
             state.timeLeft = newSettings.breakDuration * 60;
             state.totalTime = newSettings.breakDuration * 60;
           }
+          state.elapsedTime = Math.max(0, state.totalTime - state.timeLeft);
         }
         
         // 保存到存储
@@ -191,12 +280,12 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       // 状态转换
       transitionTo: (newState: UnifiedTimerStateType) => set((state) => {
         const oldState = state.currentState;
+        const focusedTime = state.elapsedTime;
         state.currentState = newState;
+        state.elapsedTime = 0;
         
         // 根据当前模式和新状态设置时间
-        const currentSettings = state.currentMode === TimerMode.CLASSIC 
-          ? state.settings.classic 
-          : state.settings.smart;
+        const currentSettings = getPhaseDurations(state.settings, state.currentMode);
         
         switch (newState) {
           case 'focus':
@@ -204,28 +293,43 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
             state.totalTime = currentSettings.focusDuration * 60;
             state.focusStartTime = Date.now();
             break;
-          case 'break':
-            state.timeLeft = currentSettings.breakDuration * 60;
-            state.totalTime = currentSettings.breakDuration * 60;
+          case 'break': {
+            let breakDuration = currentSettings.breakDuration * 60;
+            let focusMinutes = currentSettings.focusDuration;
+            // 心流模式按实际专注时长建议休息
+            if (state.currentMode === TimerMode.FLOW && oldState === 'focus') {
+              const flow = getFlowSettings(state.settings);
+              breakDuration = wasmTimer.suggestFlowBreak(
+                focusedTime,
+                flow.breakRatio,
+                flow.minBreakDuration,
+                flow.maxBreakDuration
+              );
+              focusMinutes = Math.round(focusedTime / 60);
+            }
+            state.timeLeft = breakDuration;
+            state.totalTime = breakDuration;
             // 专注会话结束，可能触发效率评分
             if (oldState === 'focus') {
               setTimeout(() => {
                 get().showEfficiencyRating({
-                  duration: currentSettings.focusDuration,
+                  duration: focusMinutes,
+                  plannedDuration: currentSettings.focusDuration,
                   type: 'focus',
                   sessionId: state.currentSession.id || undefined,
                 });
               }, 1000);
             }
             break;
+          }
           case 'microBreak':
             {
-              // 设置微休息时长（根据模式确定）
+              // 设置微休息时长（根据模式确定，心流模式沿用经典模式的设置）
               let microBreakDuration;
-              if (state.currentMode === TimerMode.CLASSIC) {
-                microBreakDuration = (currentSettings as ClassicTimerSettings).microBreakDuration * 60;
+              if (state.currentMode !== TimerMode.SMART) {
+                microBreakDuration = state.settings.classic.microBreakDuration * 60;
               } else {
-                const smartSettings = currentSettings as SmartTimerSettings;
+                const smartSettings = state.settings.smart;
                 microBreakDuration = Math.floor(
                   Math.random() * (
                     smartSettings.microBreakMaxDuration - smartSettings.microBreakMinDuration + 1
@@ -252,9 +356,11 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       
       // 跳转到下一状态
       skipToNext: () => set((state) => {
-        const currentSettings = state.currentMode === TimerMode.CLASSIC 
-          ? state.settings.classic 
-          : state.settings.smart;
+        if (backendTimer.isAvailable()) {
+          runBackend(() => backendTimer.skip());
+          return;
+        }
+        const currentSettings = getPhaseDurations(state.settings, state.currentMode);
         
         switch (state.currentState) {
           case 'focus':
@@ -271,6 +377,7 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
             state.currentState = 'focus';
             state.timeLeft = currentSettings.focusDuration * 60;
             state.totalTime = currentSettings.focusDuration * 60;
+            state.elapsedTime = 0;
         }
         
         // 重置活动状态
@@ -279,6 +386,12 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       
       // 触发微休息
       triggerMicroBreak: () => set((state) => {
+        // 后端切换后通过 timer://transition 通知，音效与通知在 handleBackendTransition 中处理
+        if (backendTimer.isAvailable()) {
+          runBackend(() => backendTimer.microBreak());
+          return;
+        }
+        
         // 切换到微休息状态
         get().transitionTo('microBreak');
         
@@ -293,29 +406,38 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       }),
       
       // 更新设置
-      updateSettings: (newSettings: Partial<UnifiedTimerSettings>) => set((state) => {
-        Object.assign(state.settings, newSettings);
-      }),
+      updateSettings: (newSettings: Partial<UnifiedTimerSettings>) => {
+        set((state) => {
+          Object.assign(state.settings, newSettings);
+        });
+        if (backendTimer.isAvailable()) {
+          const { settings } = get();
+          runBackend(() => backendTimer.updateSettings(settings));
+        }
+      },
       
       // 更新剩余时间
       updateTimeLeft: (timeLeft: number) => set((state) => {
         state.timeLeft = timeLeft;
+        state.elapsedTime = Math.max(0, state.totalTime - timeLeft);
+      }),
+      
+      // 更新已计时时间；正计时时剩余时间表示距软目标的时间，到达后保持为 0
+      updateElapsedTime: (elapsedTime: number) => set((state) => {
+        state.elapsedTime = elapsedTime;
+        state.timeLeft = Math.max(0, state.totalTime - elapsedTime);
       }),
       
       // 调度下次微休息
       scheduleNextMicroBreak: () => set((state) => {
-        const currentSettings = state.currentMode === TimerMode.CLASSIC 
-          ? state.settings.classic 
-          : state.settings.smart;
-        
         // 生成随机间隔（秒）
         let minInterval, maxInterval;
-        if (state.currentMode === TimerMode.CLASSIC) {
-          const classicSettings = currentSettings as ClassicTimerSettings;
+        if (state.currentMode !== TimerMode.SMART) {
+          const classicSettings = state.settings.classic;
           minInterval = classicSettings.microBreakMinInterval * 60;
           maxInterval = minInterval;
         } else {
-          const smartSettings = currentSettings as SmartTimerSettings;
+          const smartSettings = state.settings.smart;
           minInterval = smartSettings.microBreakMinInterval * 60;
           maxInterval = smartSettings.microBreakMaxInterval * 60;
         }
@@ -362,16 +484,34 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
         state.pendingRatingSession = null;
       }),
       
-      // 提交效率评分
-      submitEfficiencyRating: (score: number) => set((state) => {
-        state.showRatingDialog = false;
-        state.pendingRatingSession = null;
-        state.recentEfficiencyScores.push(score);
-        // 保持最近5个评分
-        if (state.recentEfficiencyScores.length > 5) {
-          state.recentEfficiencyScores.shift();
+      // 提交效率评分：手动评分（1-5）与本次专注的完成情况一起交给 WASM 计算综合评分
+      submitEfficiencyRating: (score: number) => {
+        const session = get().pendingRatingSession;
+        const result = session?.type === 'focus'
+          ? wasmTimer.computeEfficiencyScore({
+              plannedFocus: (session.plannedDuration ?? session.duration) * 60,
+              completedFocus: session.duration * 60,
+              rating: score,
+            })
+          : null;
+
+        set((state) => {
+          state.showRatingDialog = false;
+          state.pendingRatingSession = null;
+          // 手动评分供智能模式自适应调整使用，保持最近5个
+          state.recentEfficiencyScores.push(score);
+          if (state.recentEfficiencyScores.length > 5) {
+            state.recentEfficiencyScores.shift();
+          }
+          if (result) {
+            state.lastEfficiencyScore = result;
+          }
+        });
+
+        if (result && session?.sessionId) {
+          get().updateSessionEfficiency(session.sessionId, result.score);
         }
-      }),
+      },
       
       // 更新今日统计数据
       updateTodayStats: (type: 'focus' | 'break' | 'microBreak', duration: number) => 
@@ -398,6 +538,88 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
           }
         }),
       
+      // 连接后端计时器：同步设置并监听事件，之后状态完全由后端决定
+      connectBackend: () => {
+        if (!backendTimer.isAvailable()) return Promise.resolve();
+        if (!backendConnection) {
+          backendConnection = (async () => {
+            await backendTimer.onTick((tick) => get().applyBackendTick(tick));
+            await backendTimer.onTransition((transition) => get().handleBackendTransition(transition));
+            await backendTimer.onNotice((notice) => get().handleBackendNotice(notice));
+            get().applyBackendTick(await backendTimer.updateSettings(get().settings));
+          })().catch((error) => {
+            backendConnection = null;
+            console.error('Failed to connect backend timer:', error);
+          });
+        }
+        return backendConnection;
+      },
+      
+      applyBackendTick: (tick: BackendTimerTick) => set((state) => {
+        state.currentState = fromBackendPhase(tick.state);
+        state.currentMode = tick.mode;
+        state.isActive = tick.running;
+        state.totalTime = tick.duration;
+        state.timeLeft = tick.remaining;
+        state.elapsedTime = isCountingUp(tick.mode, state.currentState)
+          ? tick.time
+          : tick.duration - tick.remaining;
+      }),
+      
+      // 与 transitionTo 相同的附带效果：音效、微休息统计与专注结束后的效率评分
+      handleBackendTransition: (transition: BackendTimerTransition) => set((state) => {
+        const from = fromBackendPhase(transition.from);
+        const to = fromBackendPhase(transition.to);
+        // 专注已计时长取切换前最后一次 tick
+        const focusedTime = state.elapsedTime;
+        
+        if (to === 'focus' && from !== 'focus') {
+          state.focusStartTime = Date.now();
+        }
+        if (to === 'microBreak') {
+          state.lastMicroBreakTime = Date.now();
+          state.microBreakCount += 1;
+          if (state.settings.notificationEnabled) {
+            getNotificationService().sendNotification('微休息时间', '短暂休息一下，保持专注力');
+          }
+        }
+        if (from === 'focus' && (to === 'break' || to === 'forcedBreak')) {
+          // 切换事件先于新阶段的 tick 到达，totalTime 仍是刚结束的专注时长
+          const plannedMinutes = Math.round(state.totalTime / 60);
+          setTimeout(() => {
+            get().showEfficiencyRating({
+              duration: Math.round(focusedTime / 60),
+              plannedDuration: plannedMinutes,
+              type: 'focus',
+              sessionId: get().currentSession.id || undefined,
+            });
+          }, 1000);
+        }
+        
+        if (state.settings.soundEnabled && transition.reason !== 'reset') {
+          getSoundService().playMapped(`${to}Start`);
+        }
+      }),
+      
+      handleBackendNotice: (notice: BackendTimerNotice) => {
+        const { settings } = get();
+        if (!settings.notificationEnabled) return;
+        switch (notice.kind) {
+          case 'targetReached':
+            getNotificationService().sendNotification('已达到目标时长', '可以继续专注，想停时结束即可');
+            break;
+          case 'overtimeReminder':
+            getNotificationService().sendNotification(
+              '专注已超时',
+              `已超出计划 ${Math.round(notice.overtime / 60)} 分钟`
+            );
+            break;
+          default:
+            // 时钟调整与休眠恢复由后端修正计时，无需提示
+            break;
+        }
+      },
+      
       // 数据库相关方法
       initializeDatabase: async () => {
         const dbService = getDatabaseService();
@@ -415,8 +637,11 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
       },
       
       updateSessionEfficiency: async (sessionId: number, efficiency: number) => {
-        // 实现更新会话效率的逻辑
-        console.log(`Updating session ${sessionId} efficiency to ${efficiency}`);
+        try {
+          await getDatabaseService().updateSessionEfficiency(sessionId, efficiency);
+        } catch (error) {
+          console.error('Failed to update session efficiency:', error);
+        }
       },
       
       getDatabaseStats: async () => {
@@ -467,6 +692,9 @@ export const useUnifiedTimerStore = create<UnifiedTimerStore>()(
             }
           }
 
+          // 桌面端由后端计时，加载的设置同步给后端
+          await get().connectBackend();
+
           // 初始化数据库
           await get().initializeDatabase();
 
@@ -86,6 +86,7 @@ const getInitialState = (settings: UnifiedTimerSettings): Omit<UnifiedState, 'cu
     },
     showRatingDialog: false,
     pendingRatingSession: null,
+    lastEfficiencyScore: null,
   };
 };
 
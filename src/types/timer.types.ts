@@ -5,7 +5,7 @@
 /**
  * 计时器模式类型
  */
-export type TimerMode = 'classic' | 'smart';
+export type TimerMode = 'classic' | 'smart' | 'flow';
 
 /**
  * 经典模式设置
@@ -2,4 +2,4 @@
  * Timer组件相关类型定义导出
  */
 
-export type TimerMode = 'classic' | 'smart';
+export type TimerMode = 'classic' | 'smart' | 'flow';
@@ -3,10 +3,13 @@
  * 整合经典模式和智能模式的所有配置和状态
  */
 
+import type { EfficiencyScore } from '../wasm/wasmTimer';
+
 // 计时器模式枚举
 export enum TimerMode {
   CLASSIC = 'classic',
-  SMART = 'smart'
+  SMART = 'smart',
+  FLOW = 'flow'
 }
 
 // 统一的计时器状态类型
@@ -49,6 +52,14 @@ export interface SmartTimerSettings {
   forcedBreakThreshold: number; // 强制休息阈值（分钟），默认150
 }
 
+// 心流模式设置：专注正计时，结束时按比例建议休息
+export interface FlowTimerSettings {
+  targetDuration: number; // 专注软目标（分钟），到达后继续计时
+  breakRatio: number; // 休息时长占专注时长的比例，默认 0.2
+  minBreakDuration: number; // 最短休息（分钟）
+  maxBreakDuration: number; // 最长休息（分钟）
+}
+
 // 统一的计时器设置
 export interface UnifiedTimerSettings {
   // 当前模式
@@ -57,6 +68,7 @@ export interface UnifiedTimerSettings {
   // 模式特定设置
   classic: ClassicTimerSettings;
   smart: SmartTimerSettings;
+  flow?: FlowTimerSettings; // 旧版本保存的设置中没有该字段，缺失时使用默认值
   
   // 通用设置
   soundEnabled: boolean;
@@ -120,9 +132,11 @@ export interface UnifiedTimerState {
   showRatingDialog: boolean;
   pendingRatingSession: {
     duration: number;
+    plannedDuration?: number; // 计划专注时长（分钟），未提供时按实际时长计算完成度
     type: UnifiedTimerStateType;
     sessionId?: number;
   } | null;
+  lastEfficiencyScore: EfficiencyScore | null; // 最近一次专注的综合评分及各项明细，由 WASM 计算
 }
 
 // 模式切换选项
@@ -184,6 +198,13 @@ export const DEFAULT_UNIFIED_SETTINGS: UnifiedTimerSettings = {
     maxContinuousFocusTime: 120,
     forcedBreakThreshold: 150,
   },
+
+  flow: {
+    targetDuration: 50,
+    breakRatio: 0.2,
+    minBreakDuration: 5,
+    maxBreakDuration: 30,
+  },
   
   soundEnabled: true,
   notificationEnabled: true,
@@ -217,5 +238,12 @@ export const MODE_DISPLAY_CONFIG: Record<TimerMode, ModeDisplayConfig> = {
     icon: '🧠',
     color: '#3b82f6',
     features: ['自适应调整', '生理节律优化', '强制休息', '效率追踪', '微休息系统']
+  },
+  [TimerMode.FLOW]: {
+    name: '心流模式',
+    description: '正计时专注，结束后按比例休息',
+    icon: '🌊',
+    color: '#10b981',
+    features: ['无固定终点', '软目标提醒', '按比例建议休息']
   }
 };
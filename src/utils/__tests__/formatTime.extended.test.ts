@@ -5,17 +5,16 @@
  */
 
 import {
-  formatTime,
-  padZero,
   parseTimeString,
-  formatDuration,
-  formatTimeShort,
   getTimeDifference,
   isValidTimeFormat,
   getProgressPercentage,
-  formatRemainingTime,
   timeUtils,
 } from '../formatTime';
+import { wasmTimer, DurationFormat } from '../../wasm/wasmTimer';
+
+// 时长格式化来自 WASM 计时核心
+beforeAll(() => wasmTimer.ready());
 
 describe('getTimeDifference', () => {
   beforeEach(() => {
@@ -155,79 +154,49 @@ describe('getProgressPercentage', () => {
   });
 });
 
-describe('formatRemainingTime', () => {
-  it('formats positive remaining time', () => {
-    expect(formatRemainingTime(1500)).toBe('25:00');
-    expect(formatRemainingTime(90)).toBe('1:30');
-    expect(formatRemainingTime(30)).toBe('0:30');
-  });
-
-  it('handles zero remaining time', () => {
-    expect(formatRemainingTime(0)).toBe('0:00');
-  });
-
-  it('handles negative remaining time', () => {
-    expect(formatRemainingTime(-30)).toBe('0:00');
-  });
-
-  it('handles decimal remaining time', () => {
-    expect(formatRemainingTime(90.7)).toBe('1:30');
-  });
-});
-
 describe('timeUtils object', () => {
   it('exports all time utility functions', () => {
-    expect(timeUtils.formatTime).toBeDefined();
-    expect(timeUtils.padZero).toBeDefined();
     expect(timeUtils.parseTimeString).toBeDefined();
-    expect(timeUtils.formatDuration).toBeDefined();
-    expect(timeUtils.formatTimeShort).toBeDefined();
     expect(timeUtils.getTimeDifference).toBeDefined();
     expect(timeUtils.isValidTimeFormat).toBeDefined();
     expect(timeUtils.getProgressPercentage).toBeDefined();
-    expect(timeUtils.formatRemainingTime).toBeDefined();
   });
 
   it('functions work correctly when called from timeUtils object', () => {
-    expect(timeUtils.formatTime(1500)).toBe('25:00');
-    expect(timeUtils.padZero(5)).toBe('05');
     expect(timeUtils.parseTimeString('25:00')).toBe(1500);
-    expect(timeUtils.formatDuration(90)).toBe('1 minute 30 seconds');
-    expect(timeUtils.formatTimeShort(90)).toBe('1m 30s');
     expect(timeUtils.isValidTimeFormat('25:00')).toBe(true);
     expect(timeUtils.getProgressPercentage(25, 100)).toBe(25);
-    expect(timeUtils.formatRemainingTime(90)).toBe('1:30');
   });
 });
 
 // ==================== INTEGRATION TESTS ====================
 describe('Time Utils Integration', () => {
-  it('formatTime and parseTimeString are inverse operations', () => {
+  it('formatDuration and parseTimeString are inverse operations', () => {
     const testCases = [
-      { seconds: 30, timeString: '0:30' },
-      { seconds: 300, timeString: '5:00' },
+      { seconds: 30, timeString: '00:30' },
+      { seconds: 300, timeString: '05:00' },
       { seconds: 1500, timeString: '25:00' },
       { seconds: 3661, timeString: '1:01:01' },
     ];
 
     testCases.forEach(({ seconds, timeString }) => {
-      // formatTime -> parseTimeString should return original seconds
-      expect(parseTimeString(formatTime(seconds))).toBe(seconds);
+      // formatDuration -> parseTimeString should return original seconds
+      expect(parseTimeString(wasmTimer.formatDuration(seconds))).toBe(seconds);
       
-      // parseTimeString -> formatTime should return original string
-      expect(formatTime(parseTimeString(timeString))).toBe(timeString);
+      // parseTimeString -> formatDuration should return original string
+      expect(wasmTimer.formatDuration(parseTimeString(timeString))).toBe(timeString);
     });
   });
 
-  it('formatDuration and formatTimeShort provide different representations', () => {
+  it('duration formats provide different representations', () => {
     const seconds = 3661; // 1 hour 1 minute 1 second
     
-    const duration = formatDuration(seconds);
-    const shortFormat = formatTimeShort(seconds);
-    const timeFormat = formatTime(seconds);
+    const duration = wasmTimer.formatDuration(seconds, DurationFormat.Verbose, 'en');
+    const shortFormat = wasmTimer.formatDuration(seconds, DurationFormat.Compact);
+    const timeFormat = wasmTimer.formatDuration(seconds);
     
-    expect(duration).toBe('1 hour 1 minute 1 second');
-    expect(shortFormat).toBe('1h 1m 1s');
+    expect(duration).toBe('1 hour 1 minute');
+    expect(shortFormat).toBe('1h 1m');
     expect(timeFormat).toBe('1:01:01');
     
     // All should represent the same duration
@@ -240,7 +209,7 @@ describe('Time Utils Integration', () => {
     
     const progress = getProgressPercentage(currentSeconds, totalSeconds);
     const remainingSeconds = totalSeconds - currentSeconds;
-    const remainingTime = formatRemainingTime(remainingSeconds);
+    const remainingTime = wasmTimer.formatDuration(remainingSeconds);
     
     expect(progress).toBe(50);
     expect(remainingTime).toBe('12:30');
@@ -253,10 +222,8 @@ describe('Time Utils Performance', () => {
     const startTime = performance.now();
 
     for (let i = 0; i < 10000; i++) {
-      formatTime(i);
-      padZero(i % 60);
+      wasmTimer.formatDuration(i);
       getProgressPercentage(i, 10000);
-      formatRemainingTime(i);
     }
 
     const endTime = performance.now();
@@ -280,19 +247,19 @@ describe('Time Utils Performance', () => {
 describe('Time Utils Error Handling', () => {
   it('handles edge cases gracefully', () => {
     // Very large numbers
-    expect(() => formatTime(Number.MAX_SAFE_INTEGER)).not.toThrow();
+    expect(() => wasmTimer.formatDuration(Number.MAX_SAFE_INTEGER)).not.toThrow();
     expect(() => getProgressPercentage(Number.MAX_SAFE_INTEGER, 100)).not.toThrow();
     
     // Very small numbers
-    expect(() => formatTime(Number.MIN_SAFE_INTEGER)).not.toThrow();
+    expect(() => wasmTimer.formatDuration(Number.MIN_SAFE_INTEGER)).not.toThrow();
     expect(() => getProgressPercentage(Number.MIN_SAFE_INTEGER, 100)).not.toThrow();
     
     // Infinity
-    expect(() => formatTime(Infinity)).not.toThrow();
+    expect(() => wasmTimer.formatDuration(Infinity)).not.toThrow();
     expect(() => getProgressPercentage(Infinity, 100)).not.toThrow();
     
     // NaN
-    expect(() => formatTime(NaN)).not.toThrow();
+    expect(() => wasmTimer.formatDuration(NaN)).not.toThrow();
     expect(() => getProgressPercentage(NaN, 100)).not.toThrow();
   });
 
@@ -0,0 +1,47 @@
+/**
+ * 会话汇总测试
+ *
+ * 调用 wasm-pack 生成的 nodejs 绑定（pkg-node），即 timer_core/stats.rs 本身
+ */
+
+import { wasmTimer } from '../wasmTimer';
+
+const utc = (iso: string) => Date.parse(`${iso}Z`);
+
+describe('wasmTimer.aggregateSessions', () => {
+  beforeAll(async () => {
+    await wasmTimer.ready();
+    wasmTimer.setDayCalendar('UTC', 0);
+  });
+
+  afterAll(() => {
+    wasmTimer.setDayCalendar();
+  });
+
+  it('reports period keys that match the aggregated series', () => {
+    const now = utc('2024-01-15T12:00:00');
+    const stats = wasmTimer.aggregateSessions({
+      startedAt: new Float64Array([utc('2024-01-14T09:00:00'), utc('2024-01-15T09:00:00')]),
+      focus: new Uint32Array([25, 50]),
+      breakTime: new Uint32Array([5, 10]),
+      microBreaks: new Uint32Array([1, 2]),
+      efficiency: new Float64Array([80, 90]),
+    }, now)!;
+    const keys = wasmTimer.periodKeys(now)!;
+
+    expect(wasmTimer.dayDateKey(keys.day)).toBe('2024-01-15');
+    expect(keys.month).toBe(202401);
+
+    const today = stats.daily.keys.indexOf(keys.day);
+    expect(stats.daily.focus[today]).toBe(50);
+
+    // 2024-01-15 是周一，前一天属于上一周
+    const week = stats.weekly.keys.indexOf(keys.week);
+    expect(wasmTimer.dayDateKey(keys.week)).toBe('2024-01-15');
+    expect(stats.weekly.sessions[week]).toBe(1);
+
+    const month = stats.monthly.keys.indexOf(keys.month);
+    expect(stats.monthly.focus[month]).toBe(75);
+    expect(stats.currentStreak).toBe(2);
+  });
+});
@@ -0,0 +1,52 @@
+/**
+ * 时长格式化测试
+ *
+ * 调用 wasm-pack 生成的 nodejs 绑定（pkg-node），即 timer_core/format.rs 本身
+ */
+
+import { wasmTimer, DurationFormat } from '../wasmTimer';
+
+describe('wasmTimer.formatDuration', () => {
+  beforeAll(() => wasmTimer.ready());
+
+  describe('Clock styles', () => {
+    it('uses MM:SS below one hour and H:MM:SS above', () => {
+      expect(wasmTimer.formatDuration(30)).toBe('00:30');
+      expect(wasmTimer.formatDuration(1500)).toBe('25:00');
+      expect(wasmTimer.formatDuration(3661)).toBe('1:01:01');
+      expect(wasmTimer.formatDuration(9000)).toBe('2:30:00');
+    });
+
+    it('keeps minutes unbounded in MinutesSeconds', () => {
+      expect(wasmTimer.formatDuration(9000, DurationFormat.MinutesSeconds)).toBe('150:00');
+    });
+
+    it('always shows hours in HoursMinutesSeconds', () => {
+      expect(wasmTimer.formatDuration(65, DurationFormat.HoursMinutesSeconds)).toBe('0:01:05');
+    });
+  });
+
+  describe('Compact and verbose', () => {
+    it('keeps the two largest non-zero units', () => {
+      expect(wasmTimer.formatDuration(5130, DurationFormat.Compact)).toBe('1h 25m');
+      expect(wasmTimer.formatDuration(3600, DurationFormat.Compact)).toBe('1h');
+      expect(wasmTimer.formatDuration(90, DurationFormat.Compact)).toBe('1m 30s');
+      expect(wasmTimer.formatDuration(0, DurationFormat.Compact)).toBe('0s');
+    });
+
+    it('localizes verbose output', () => {
+      expect(wasmTimer.formatDuration(5130, DurationFormat.Verbose, 'en')).toBe('1 hour 25 minutes');
+      expect(wasmTimer.formatDuration(61, DurationFormat.Verbose, 'en')).toBe('1 minute 1 second');
+      expect(wasmTimer.formatDuration(5130, DurationFormat.Verbose, 'zh-CN')).toBe('1 小时 25 分');
+      expect(wasmTimer.formatDuration(45, DurationFormat.Verbose)).toBe('45 秒');
+    });
+  });
+
+  describe('Edge cases', () => {
+    it('clamps negative values and floors decimals', () => {
+      expect(wasmTimer.formatDuration(-30)).toBe('00:00');
+      expect(wasmTimer.formatDuration(90.7)).toBe('01:30');
+      expect(wasmTimer.formatDuration(NaN)).toBe('00:00');
+    });
+  });
+});
@@ -1,8 +1,21 @@
 // Timer Calculation WebAssembly Module
 // 用于高性能的计时器数学计算
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use wasm_bindgen::prelude::*;
 
+pub mod timer_core;
+
+use timer_core::micro_break::{self, MicroBreakConfig, MicroBreakScheduler};
+use timer_core::registry;
+use timer_core::smart;
+use timer_core::{Calculation, Clock, ClockEvent, CyclePolicy, GapPolicy, RunStatus, Timer, TimerPhase, Transition};
+use timer_core::{CountMode, DurationStyle, FlowPolicy, Locale, TimerEvent, TimerSnapshot};
+use timer_core::{FitStrategy, OvertimeMode, OvertimePolicy, TickPrecision, Visibility};
+use timer_core::{Program, ProgramError, ScoreWeights, SessionMetrics};
+
 #[wasm_bindgen]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -10,152 +23,1273 @@ pub enum TimerState {
     Focus = 0,
     Break = 1,
     MicroBreak = 2,
+    ForcedBreak = 3,
+    LongBreak = 4,
+    Idle = 5,
+}
+
+impl From<TimerState> for TimerPhase {
+    fn from(state: TimerState) -> TimerPhase {
+        match state {
+            TimerState::Focus => TimerPhase::Focus,
+            TimerState::Break => TimerPhase::Break,
+            TimerState::MicroBreak => TimerPhase::MicroBreak,
+            TimerState::ForcedBreak => TimerPhase::ForcedBreak,
+            TimerState::LongBreak => TimerPhase::LongBreak,
+            TimerState::Idle => TimerPhase::Idle,
+        }
+    }
+}
+
+impl From<TimerPhase> for TimerState {
+    fn from(phase: TimerPhase) -> TimerState {
+        match phase {
+            TimerPhase::Focus => TimerState::Focus,
+            TimerPhase::Break => TimerState::Break,
+            TimerPhase::MicroBreak => TimerState::MicroBreak,
+            TimerPhase::ForcedBreak => TimerState::ForcedBreak,
+            TimerPhase::LongBreak => TimerState::LongBreak,
+            TimerPhase::Idle => TimerState::Idle,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerStatus {
+    Running = 0,
+    Paused = 1,
+}
+
+impl From<RunStatus> for TimerStatus {
+    fn from(status: RunStatus) -> TimerStatus {
+        match status {
+            RunStatus::Running => TimerStatus::Running,
+            RunStatus::Paused => TimerStatus::Paused,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockEventKind {
+    None = 0,
+    ClockJump = 1,
+    SuspendGap = 2,
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SuspendPolicy {
+    Keep = 0,
+    Discount = 1,
+    Pause = 2,
+}
+
+impl From<SuspendPolicy> for GapPolicy {
+    fn from(policy: SuspendPolicy) -> GapPolicy {
+        match policy {
+            SuspendPolicy::Keep => GapPolicy::Keep,
+            SuspendPolicy::Discount => GapPolicy::Discount,
+            SuspendPolicy::Pause => GapPolicy::Pause,
+        }
+    }
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+// 浏览器时钟适配器：performance.now() 作单调时间，Date.now() 作墙上时间
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WasmClock;
+
+impl Clock for WasmClock {
+    fn now_ms(&self) -> u64 {
+        performance_now() as u64
+    }
+
+    fn wall_ms(&self) -> u64 {
+        js_sys::Date::now() as u64
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerEventKind {
+    Started = 0,
+    Paused = 1,
+    Resumed = 2,
+    Completed = 3,
+    StateChanged = 4,
+    MicroBreakDue = 5,
+    ForcedBreakDue = 6,
+    ClockJumpDetected = 7,
+    TargetReached = 8,
+    OvertimeReminder = 9,
+}
+
+// 计时方向：Up 时专注段正计时，duration 只作为软目标
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CountDirection {
+    Down = 0,
+    Up = 1,
+}
+
+impl From<CountDirection> for CountMode {
+    fn from(direction: CountDirection) -> CountMode {
+        match direction {
+            CountDirection::Down => CountMode::Down,
+            CountDirection::Up => CountMode::Up,
+        }
+    }
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisplayPrecision {
+    Tenths = 0,
+    Seconds = 1,
+    Minutes = 2,
+}
+
+impl From<DisplayPrecision> for TickPrecision {
+    fn from(precision: DisplayPrecision) -> TickPrecision {
+        match precision {
+            DisplayPrecision::Tenths => TickPrecision::Tenths,
+            DisplayPrecision::Seconds => TickPrecision::Seconds,
+            DisplayPrecision::Minutes => TickPrecision::Minutes,
+        }
+    }
+}
+
+fn visibility(page_visible: bool) -> Visibility {
+    if page_visible { Visibility::Visible } else { Visibility::Hidden }
+}
+
+// 专注段归零后的处理：Off 不记录超时；CountTowardSession 计入专注；AdjustBreak 调整下一次休息
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OvertimeRule {
+    Off = 0,
+    CountTowardSession = 1,
+    AdjustBreak = 2,
+}
+
+impl From<OvertimeRule> for OvertimeMode {
+    fn from(rule: OvertimeRule) -> OvertimeMode {
+        match rule {
+            OvertimeRule::Off => OvertimeMode::Off,
+            OvertimeRule::CountTowardSession => OvertimeMode::CountTowardSession,
+            OvertimeRule::AdjustBreak => OvertimeMode::AdjustBreak,
+        }
+    }
+}
+
+impl From<CountMode> for CountDirection {
+    fn from(mode: CountMode) -> CountDirection {
+        match mode {
+            CountMode::Down => CountDirection::Down,
+            CountMode::Up => CountDirection::Up,
+        }
+    }
+}
+
+// 计时器事件。state 为事件对应（或切换后）的阶段，from_state 仅 StateChanged 使用；
+// value 在 MicroBreakDue 时为微休息秒数，在 OvertimeReminder 时为已超时秒数，在 ClockJumpDetected 时同 clock_event_ms
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct TimerEventRecord {
+    pub kind: TimerEventKind,
+    pub state: TimerState,
+    pub from_state: TimerState,
+    pub clock_event: ClockEventKind,
+    pub value: f64,
+}
+
+impl TimerEventRecord {
+    fn new(kind: TimerEventKind, state: TimerState) -> TimerEventRecord {
+        TimerEventRecord {
+            kind,
+            state,
+            from_state: state,
+            clock_event: ClockEventKind::None,
+            value: 0.0,
+        }
+    }
+
+    fn from_event(event: TimerEvent, current: TimerState) -> TimerEventRecord {
+        match event {
+            TimerEvent::Started { state } => Self::new(TimerEventKind::Started, state.into()),
+            TimerEvent::Paused => Self::new(TimerEventKind::Paused, current),
+            TimerEvent::Resumed => Self::new(TimerEventKind::Resumed, current),
+            TimerEvent::Completed { state } => Self::new(TimerEventKind::Completed, state.into()),
+            TimerEvent::StateChanged { from, to } => TimerEventRecord {
+                from_state: from.into(),
+                ..Self::new(TimerEventKind::StateChanged, to.into())
+            },
+            TimerEvent::MicroBreakDue { duration } => TimerEventRecord {
+                value: duration as f64,
+                ..Self::new(TimerEventKind::MicroBreakDue, current)
+            },
+            TimerEvent::ForcedBreakDue => Self::new(TimerEventKind::ForcedBreakDue, current),
+            TimerEvent::TargetReached => Self::new(TimerEventKind::TargetReached, current),
+            TimerEvent::OvertimeReminder { overtime } => TimerEventRecord {
+                value: overtime as f64,
+                ..Self::new(TimerEventKind::OvertimeReminder, current)
+            },
+            TimerEvent::ClockJumpDetected(clock_event) => {
+                let (clock_event, value) = clock_event_fields(Some(clock_event));
+                TimerEventRecord {
+                    clock_event,
+                    value,
+                    ..Self::new(TimerEventKind::ClockJumpDetected, current)
+                }
+            }
+        }
+    }
+}
+
+fn clock_event_fields(event: Option<ClockEvent>) -> (ClockEventKind, f64) {
+    match event {
+        None => (ClockEventKind::None, 0.0),
+        Some(ClockEvent::ClockJump { delta_ms }) => (ClockEventKind::ClockJump, delta_ms as f64),
+        Some(ClockEvent::SuspendGap { gap_ms }) => (ClockEventKind::SuspendGap, gap_ms as f64),
+    }
 }
 
 #[wasm_bindgen]
 pub struct TimerCalculator {
-    start_time: u64,
-    duration: u32,
-    current_time: u32,
-    state: TimerState,
+    inner: Timer<WasmClock>,
+    // 注册后事件直接推送给该回调，不再排队等待 drain_events
+    listener: Option<js_sys::Function>,
 }
 
 #[wasm_bindgen]
 impl TimerCalculator {
     #[wasm_bindgen(constructor)]
     pub fn new(duration: u32, state: TimerState) -> TimerCalculator {
-        let start_time = js_sys::Date::now() as u64;
         TimerCalculator {
-            start_time,
-            duration,
-            current_time: duration,
-            state,
+            inner: Timer::new(WasmClock, duration, state.into()),
+            listener: None,
         }
     }
 
+    // 从 snapshot() 导出的 JSON 恢复计时器，快照后经过的时间按原状态补上
+    #[wasm_bindgen]
+    pub fn restore(snapshot_json: &str) -> Result<TimerCalculator, JsValue> {
+        let snapshot = TimerSnapshot::from_json(snapshot_json)
+            .map_err(|e| JsValue::from_str(&e.to_string()))?;
+        Ok(TimerCalculator {
+            inner: Timer::restore(WasmClock, snapshot),
+            listener: None,
+        })
+    }
+
+    // 导出带版本号的 JSON 快照，可保存到 localStorage 或交给 Tauri 后端
+    #[wasm_bindgen]
+    pub fn snapshot(&self) -> String {
+        self.inner.snapshot().to_json()
+    }
+
     #[wasm_bindgen]
     pub fn update(&mut self) -> TimerCalculation {
-        let now = js_sys::Date::now() as u64;
-        let elapsed = ((now - self.start_time) / 1000) as u32;
-        self.current_time = self.duration.saturating_sub(elapsed);
-        
-        TimerCalculation {
-            time: self.current_time,
-            formatted_time: self.format_time(self.current_time),
-            progress: self.calculate_progress(elapsed),
-            remaining: self.current_time,
-            state: self.state,
-        }
+        let calc = self.inner.update().into();
+        self.dispatch_events();
+        calc
     }
 
     #[wasm_bindgen]
     pub fn reset(&mut self, new_duration: u32, new_state: TimerState) {
-        self.start_time = js_sys::Date::now() as u64;
-        self.duration = new_duration;
-        self.current_time = new_duration;
-        self.state = new_state;
+        self.inner.reset(new_duration, new_state.into());
+        self.dispatch_events();
     }
 
     #[wasm_bindgen]
     pub fn pause(&mut self) -> u32 {
-        self.current_time
+        let remaining = self.inner.pause();
+        self.dispatch_events();
+        remaining
+    }
+
+    #[wasm_bindgen]
+    pub fn resume(&mut self) {
+        self.inner.resume();
+        self.dispatch_events();
+    }
+
+    // 注册事件回调，回调参数为 TimerEventRecord；注册时会先推送已排队的事件
+    #[wasm_bindgen]
+    pub fn set_event_listener(&mut self, callback: js_sys::Function) {
+        self.listener = Some(callback);
+        self.dispatch_events();
+    }
+
+    #[wasm_bindgen]
+    pub fn clear_event_listener(&mut self) {
+        self.listener = None;
+    }
+
+    #[wasm_bindgen]
+    pub fn drain_events(&mut self) -> Vec<TimerEventRecord> {
+        let current = self.inner.state().into();
+        self.inner
+            .drain_events()
+            .into_iter()
+            .map(|event| TimerEventRecord::from_event(event, current))
+            .collect()
+    }
+
+    // 按当前阶段时长生成微休息计划，到点时产生 MicroBreakDue 事件
+    #[wasm_bindgen]
+    pub fn plan_micro_breaks(&mut self, planner: &mut MicroBreakPlanner) {
+        let plan = planner.inner.plan(self.inner.duration());
+        self.inner.set_micro_breaks(plan);
+    }
+
+    #[wasm_bindgen]
+    pub fn set_count_direction(&mut self, direction: CountDirection) {
+        self.inner.set_count_mode(direction.into());
+    }
+
+    #[wasm_bindgen]
+    pub fn count_direction(&self) -> CountDirection {
+        self.inner.count_mode().into()
     }
 
+    // 心流模式的休息建议：专注时长乘以 break_ratio，限制在 [min_break, max_break] 秒
     #[wasm_bindgen]
-    pub fn resume(&mut self, remaining_time: u32) {
-        self.start_time = js_sys::Date::now() as u64;
-        self.duration = remaining_time;
-        self.current_time = remaining_time;
+    pub fn set_flow_policy(&mut self, break_ratio: f64, min_break: u32, max_break: u32) {
+        self.inner.set_flow_policy(FlowPolicy { break_ratio, min_break, max_break });
+    }
+
+    // 按当前专注段已计时长建议的休息秒数
+    #[wasm_bindgen]
+    pub fn suggested_break(&self) -> u32 {
+        self.inner.suggested_break()
+    }
+
+    // break_factor 为每超时 1 秒下一次休息增减的秒数；reminder_interval 为 0 时只在归零时提醒
+    #[wasm_bindgen]
+    pub fn set_overtime_policy(&mut self, rule: OvertimeRule, break_factor: f64, reminder_interval: u32) {
+        self.inner.set_overtime_policy(OvertimePolicy {
+            mode: rule.into(),
+            break_factor,
+            reminder_interval,
+        });
+    }
+
+    #[wasm_bindgen]
+    pub fn set_suspend_policy(&mut self, policy: SuspendPolicy) {
+        self.inner.set_gap_policy(policy.into());
+    }
+
+    #[wasm_bindgen]
+    pub fn set_clock_thresholds(&mut self, jump_tolerance_ms: u32, suspend_threshold_ms: u32) {
+        self.inner.set_clock_thresholds(jump_tolerance_ms as u64, suspend_threshold_ms as u64);
+    }
+
+    #[wasm_bindgen]
+    pub fn is_paused(&self) -> bool {
+        self.inner.status() == RunStatus::Paused
+    }
+
+    #[wasm_bindgen]
+    pub fn total_paused_time(&self) -> u32 {
+        (self.inner.total_paused_ms() / 1000) as u32
     }
 
     #[wasm_bindgen]
     pub fn calculate_formatted_time(&self, seconds: u32) -> String {
-        self.format_time(seconds)
+        timer_core::format_time(seconds)
     }
 
     #[wasm_bindgen]
     pub fn calculate_progress_percentage(&self, current: u32, total: u32) -> f64 {
-        if total == 0 { return 0.0; }
-        (current as f64 / total as f64) * 100.0
+        Timer::<WasmClock>::progress_percentage(current, total)
     }
 
     #[wasm_bindgen]
     pub fn batch_calculate_progress(&self, times: Vec<u32>) -> Vec<f64> {
-        times.iter()
-            .map(|&time| self.calculate_progress_percentage(time, self.duration))
-            .collect()
+        self.inner.batch_progress(&times)
     }
 
     #[wasm_bindgen]
     pub fn optimize_display_update(&self, last_update: u32) -> bool {
-        // 只在时间变化时更新显示，减少不必要的渲染
-        let now = js_sys::Date::now() as u64;
-        let elapsed = ((now - self.start_time) / 1000) as u32;
-        elapsed != last_update
+        self.inner.should_update_display(last_update)
     }
 
     #[wasm_bindgen]
     pub fn calculate_next_state(&self, completed: bool) -> TimerState {
-        match self.state {
-            TimerState::Focus if completed => TimerState::Break,
-            TimerState::Break if completed => TimerState::Focus,
-            TimerState::MicroBreak if completed => TimerState::Focus,
-            _ => self.state,
+        self.inner.next_state(completed).into()
+    }
+
+    // 设置循环策略，时长单位为秒；长休息间隔（专注段数）和强制休息阈值为 0 表示关闭
+    #[wasm_bindgen]
+    pub fn set_cycle_policy(
+        &mut self,
+        focus_duration: u32,
+        break_duration: u32,
+        long_break_duration: u32,
+        long_break_interval: u32,
+        forced_break_threshold: u32,
+        forced_break_duration: u32,
+    ) {
+        self.inner.set_policy(CyclePolicy {
+            focus_duration,
+            break_duration,
+            long_break_duration,
+            long_break_interval,
+            forced_break_threshold,
+            forced_break_duration,
+        });
+    }
+
+    #[wasm_bindgen]
+    pub fn use_pomodoro_policy(&mut self) {
+        self.inner.set_policy(CyclePolicy::pomodoro());
+    }
+
+    #[wasm_bindgen]
+    pub fn suggest_next(&self) -> TimerTransition {
+        self.inner.suggest_next().into()
+    }
+
+    // 结束当前阶段并切换到建议的下一阶段
+    #[wasm_bindgen]
+    pub fn advance(&mut self) -> TimerTransition {
+        let next = self.inner.advance().into();
+        self.dispatch_events();
+        next
+    }
+
+    // 按文本程序推进阶段，如 "repeat 4 { focus 25m; break 5m }; long_break 20m"；
+    // 错误信息带行号，可先用 check_program 取得结构化的诊断
+    #[wasm_bindgen]
+    pub fn set_program(&mut self, source: &str) -> Result<(), JsValue> {
+        let program = Program::parse(source).map_err(program_error)?;
+        self.inner.set_program(Some(&program));
+        Ok(())
+    }
+
+    // 按 JSON 形式的程序推进阶段
+    #[wasm_bindgen]
+    pub fn set_program_json(&mut self, json: &str) -> Result<(), JsValue> {
+        let program = Program::from_json(json).map_err(program_error)?;
+        self.inner.set_program(Some(&program));
+        Ok(())
+    }
+
+    // 使用内置程序（名称见 program_presets），未知名称返回 false
+    #[wasm_bindgen]
+    pub fn use_preset(&mut self, name: &str) -> bool {
+        match Program::preset(name) {
+            Some(program) => {
+                self.inner.set_program(Some(&program));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 清除程序，恢复按循环策略切换
+    #[wasm_bindgen]
+    pub fn clear_program(&mut self) {
+        self.inner.set_program(None);
+    }
+
+    // 展开后的程序步骤，未设置程序时为空
+    #[wasm_bindgen]
+    pub fn program(&self) -> Vec<TimerTransition> {
+        self.inner.program().iter().map(|&block| block.into()).collect()
+    }
+
+    #[wasm_bindgen]
+    pub fn program_position(&self) -> usize {
+        self.inner.program_position()
+    }
+
+    // 截止时间模式：切换到新阶段并计时到 deadline_ms（Unix 毫秒），返回得到的秒数
+    #[wasm_bindgen]
+    pub fn reset_until(&mut self, deadline_ms: f64, new_state: TimerState) -> u32 {
+        let duration = self.inner.reset_until(deadline_ms.max(0.0) as u64, new_state.into());
+        self.dispatch_events();
+        duration
+    }
+
+    // 按当前循环策略排满到截止时间的专注与休息；shorten_last 为 false 时只安排完整专注段
+    #[wasm_bindgen]
+    pub fn plan_until(&self, deadline_ms: f64, shorten_last: bool) -> Vec<TimerTransition> {
+        let strategy = if shorten_last { FitStrategy::ShortenLast } else { FitStrategy::WholeCycles };
+        self.inner
+            .plan_until(deadline_ms.max(0.0) as u64, strategy)
+            .blocks
+            .into_iter()
+            .map(TimerTransition::from)
+            .collect()
+    }
+
+    #[wasm_bindgen]
+    pub fn current_cycle(&self) -> u32 {
+        self.inner.cycle()
+    }
+
+    #[wasm_bindgen]
+    pub fn completed_focus_count(&self) -> u32 {
+        self.inner.completed_focus()
+    }
+
+    #[wasm_bindgen]
+    pub fn continuous_focus_time(&self) -> u32 {
+        self.inner.continuous_focus()
+    }
+
+    // 到下一次可见变化的毫秒数，暂停时返回 undefined；page_visible 为 false 时按分钟刷新
+    #[wasm_bindgen]
+    pub fn next_tick_delay(&self, precision: DisplayPrecision, page_visible: bool) -> Option<u32> {
+        self.inner
+            .next_tick_ms(precision.into(), visibility(page_visible))
+            .map(|delay| delay.min(u32::MAX as u64) as u32)
+    }
+}
+
+impl TimerCalculator {
+    fn dispatch_events(&mut self) {
+        let Some(listener) = self.listener.clone() else { return; };
+        for record in self.drain_events() {
+            let _ = listener.call1(&JsValue::NULL, &JsValue::from(record));
         }
     }
+}
+
+// 智能模式调度器，设置以 TS 端 SmartTimerSettings 的 JSON 传入
+#[wasm_bindgen]
+pub struct SmartScheduler {
+    inner: smart::SmartScheduler,
+}
+
+#[wasm_bindgen]
+impl SmartScheduler {
+    #[wasm_bindgen(constructor)]
+    pub fn new(settings_json: &str) -> Result<SmartScheduler, JsValue> {
+        let settings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid smart settings: {}", e)))?;
+        Ok(SmartScheduler {
+            inner: smart::SmartScheduler::new(settings),
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn update_settings(&mut self, settings_json: &str) -> Result<(), JsValue> {
+        let settings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid smart settings: {}", e)))?;
+        self.inner.set_settings(settings);
+        Ok(())
+    }
+
+    #[wasm_bindgen]
+    pub fn next_state(&self, current: TimerState) -> TimerState {
+        self.inner.next_phase(current.into()).into()
+    }
+
+    #[wasm_bindgen]
+    pub fn duration_for(&self, state: TimerState, hour: u8) -> u32 {
+        self.inner.duration_for(state.into(), hour)
+    }
+
+    // planned_secs 为刚完成阶段的计划时长（TS 端的 totalTime）
+    #[wasm_bindgen]
+    pub fn complete(&mut self, completed: TimerState, planned_secs: u32, hour: u8) -> TimerTransition {
+        self.inner.complete(completed.into(), planned_secs, hour).into()
+    }
+
+    #[wasm_bindgen]
+    pub fn submit_efficiency_score(&mut self, score: f64) {
+        self.inner.submit_efficiency_score(score, js_sys::Date::now() as u64);
+    }
+
+    #[wasm_bindgen]
+    pub fn reset_daily(&mut self) {
+        self.inner.reset_daily();
+    }
+
+    #[wasm_bindgen]
+    pub fn continuous_focus_minutes(&self) -> u32 {
+        self.inner.state().continuous_focus_minutes
+    }
 
     #[wasm_bindgen]
-    pub fn get_optimal_update_interval(&self) -> u32 {
-        // 根据剩余时间动态调整更新频率
-        match self.current_time {
-            0..=60 => 100,      // 最后1秒，100ms更新
-            61..=300 => 500,    // 最后5分钟，500ms更新
-            301..=1800 => 1000, // 最后30分钟，1秒更新
-            _ => 2000,          // 其他情况，2秒更新
+    pub fn today_focus_minutes(&self) -> u32 {
+        self.inner.state().today_focus_minutes
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct TimerTransition {
+    pub state: TimerState,
+    pub duration: u32,
+}
+
+impl From<Transition> for TimerTransition {
+    fn from(transition: Transition) -> TimerTransition {
+        TimerTransition {
+            state: transition.phase.into(),
+            duration: transition.duration,
         }
     }
+}
 
-    fn format_time(&self, seconds: u32) -> String {
-        let mins = seconds / 60;
-        let secs = seconds % 60;
-        format!("{:02}:{:02}", mins, secs)
+// 可复现的微休息调度器，时间单位均为秒
+#[wasm_bindgen]
+pub struct MicroBreakPlanner {
+    inner: MicroBreakScheduler,
+}
+
+#[wasm_bindgen]
+impl MicroBreakPlanner {
+    #[wasm_bindgen]
+    pub fn uniform(
+        seed: u32,
+        min_interval: u32,
+        max_interval: u32,
+        min_duration: u32,
+        max_duration: u32,
+        guard: u32,
+    ) -> MicroBreakPlanner {
+        let distribution = micro_break::Distribution::Uniform { min: min_interval, max: max_interval };
+        Self::with_distribution(seed, distribution, min_duration, max_duration, guard)
+    }
+
+    #[wasm_bindgen]
+    pub fn jittered(
+        seed: u32,
+        interval: u32,
+        jitter: u32,
+        min_duration: u32,
+        max_duration: u32,
+        guard: u32,
+    ) -> MicroBreakPlanner {
+        let distribution = micro_break::Distribution::Jittered { interval, jitter };
+        Self::with_distribution(seed, distribution, min_duration, max_duration, guard)
+    }
+
+    #[wasm_bindgen]
+    pub fn poisson(
+        seed: u32,
+        mean_interval: u32,
+        min_gap: u32,
+        min_duration: u32,
+        max_duration: u32,
+        guard: u32,
+    ) -> MicroBreakPlanner {
+        let distribution = micro_break::Distribution::Poisson { mean: mean_interval, min_gap };
+        Self::with_distribution(seed, distribution, min_duration, max_duration, guard)
     }
 
-    fn calculate_progress(&self, elapsed: u32) -> f64 {
-        if self.duration == 0 { return 0.0; }
-        (elapsed as f64 / self.duration as f64) * 100.0
+    // 按 TS 端 SmartTimerSettings 的 JSON 生成，guard_minutes 为与主休息的最小距离
+    #[wasm_bindgen]
+    pub fn from_smart_settings(
+        seed: u32,
+        settings_json: &str,
+        guard_minutes: u32,
+    ) -> Result<MicroBreakPlanner, JsValue> {
+        let settings: smart::SmartSettings = serde_json::from_str(settings_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid smart settings: {}", e)))?;
+        Ok(MicroBreakPlanner {
+            inner: MicroBreakScheduler::new(MicroBreakConfig::from_smart(&settings, guard_minutes), seed as u64),
+        })
+    }
+
+    #[wasm_bindgen]
+    pub fn plan(&mut self, focus_duration: u32) -> Vec<MicroBreakSlot> {
+        self.inner
+            .plan(focus_duration)
+            .into_iter()
+            .map(|b| MicroBreakSlot { at: b.at, duration: b.duration })
+            .collect()
+    }
+
+    fn with_distribution(
+        seed: u32,
+        distribution: micro_break::Distribution,
+        min_duration: u32,
+        max_duration: u32,
+        guard: u32,
+    ) -> MicroBreakPlanner {
+        let config = MicroBreakConfig {
+            distribution,
+            min_duration,
+            max_duration,
+            guard,
+        };
+        MicroBreakPlanner {
+            inner: MicroBreakScheduler::new(config, seed as u64),
+        }
     }
 }
 
 #[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct MicroBreakSlot {
+    pub at: u32,
+    pub duration: u32,
+}
+
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone)]
 pub struct TimerCalculation {
     pub time: u32,
     pub formatted_time: String,
     pub progress: f64,
     pub remaining: u32,
     pub state: TimerState,
+    pub status: TimerStatus,
+    pub paused_time: u32,
+    pub clock_event: ClockEventKind,
+    // ClockJump 时为墙上时间偏移（可为负），SuspendGap 时为休眠时长，单位毫秒
+    pub clock_event_ms: f64,
+    pub cycle: u32,
+    pub cycles_per_set: u32,
+    pub remaining_ms: f64,
+    pub elapsed_ms: f64,
+    // 本阶段开始与预计结束的 Unix 毫秒时间戳，可直接传给其他窗口或设备
+    pub started_at: f64,
+    pub ends_at: f64,
+    pub overtime_ms: f64,
+}
+
+impl From<Calculation> for TimerCalculation {
+    fn from(calc: Calculation) -> TimerCalculation {
+        let (clock_event, clock_event_ms) = clock_event_fields(calc.clock_event);
+        TimerCalculation {
+            time: calc.time,
+            formatted_time: calc.formatted_time,
+            progress: calc.progress,
+            remaining: calc.remaining,
+            state: calc.state.into(),
+            status: calc.status.into(),
+            paused_time: calc.paused_time,
+            clock_event,
+            clock_event_ms,
+            cycle: calc.cycle,
+            cycles_per_set: calc.cycles_per_set,
+            remaining_ms: calc.remaining_ms as f64,
+            elapsed_ms: calc.elapsed_ms as f64,
+            started_at: calc.started_at as f64,
+            ends_at: calc.ends_at as f64,
+            overtime_ms: calc.overtime_ms as f64,
+        }
+    }
+}
+
+// 具名计时器注册表：多个相互独立的计时器（如喝水提醒、单个任务计时）由一次 update_all 统一推进
+#[wasm_bindgen]
+pub struct TimerRegistry {
+    inner: registry::TimerRegistry<WasmClock>,
+}
+
+#[wasm_bindgen]
+impl TimerRegistry {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> TimerRegistry {
+        TimerRegistry {
+            inner: registry::TimerRegistry::new(WasmClock),
+        }
+    }
+
+    // 开始一个计时器，同名计时器会被替换
+    #[wasm_bindgen]
+    pub fn start(&mut self, name: &str, duration: u32, state: TimerState) {
+        self.inner.start(name, duration, state.into());
+    }
+
+    // 返回暂停时的剩余秒数，计时器不存在时返回 undefined
+    #[wasm_bindgen]
+    pub fn pause(&mut self, name: &str) -> Option<u32> {
+        self.inner.pause(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn resume(&mut self, name: &str) -> bool {
+        self.inner.resume(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn reset(&mut self, name: &str, duration: u32, state: TimerState) -> bool {
+        self.inner.reset(name, duration, state.into())
+    }
+
+    #[wasm_bindgen]
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.inner.remove(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[wasm_bindgen]
+    pub fn has(&self, name: &str) -> bool {
+        self.inner.contains(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn names(&self) -> Vec<String> {
+        self.inner.names()
+    }
+
+    #[wasm_bindgen]
+    pub fn update_all(&mut self) -> Vec<NamedTimerCalculation> {
+        self.inner
+            .update_all()
+            .into_iter()
+            .map(|(name, calc)| NamedTimerCalculation {
+                name,
+                calculation: calc.into(),
+            })
+            .collect()
+    }
+
+    // 取走指定计时器的事件
+    #[wasm_bindgen]
+    pub fn drain_events(&mut self, name: &str) -> Vec<TimerEventRecord> {
+        let Some(timer) = self.inner.get_mut(name) else { return Vec::new(); };
+        let current = timer.state().into();
+        timer
+            .drain_events()
+            .into_iter()
+            .map(|event| TimerEventRecord::from_event(event, current))
+            .collect()
+    }
+}
+
+impl Default for TimerRegistry {
+    fn default() -> Self {
+        TimerRegistry::new()
+    }
+}
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct NamedTimerCalculation {
+    pub name: String,
+    pub calculation: TimerCalculation,
 }
 
+fn program_error(error: ProgramError) -> JsValue {
+    JsValue::from_str(&format!("invalid timer program: {}", error))
+}
+
+// 程序校验结果；line 从 1 开始，JSON 结构错误等没有行号时为 0
+#[wasm_bindgen(getter_with_clone)]
+pub struct ProgramDiagnostic {
+    pub line: u32,
+    pub message: String,
+}
+
+// 校验文本程序，合法时返回 undefined
+#[wasm_bindgen]
+pub fn check_program(source: &str) -> Option<ProgramDiagnostic> {
+    Program::parse(source).err().map(|error| ProgramDiagnostic {
+        line: error.line as u32,
+        message: error.message,
+    })
+}
+
+// 内置程序名称
 #[wasm_bindgen]
-pub fn calculate_multiple_timers(durations: Vec<u32>) -> Vec<TimerCalculation> {
-    let now = js_sys::Date::now() as u64;
-    durations
+pub fn program_presets() -> Vec<String> {
+    timer_core::PRESETS.iter().map(|(name, _)| name.to_string()).collect()
+}
+
+// 内置程序的文本，可作为编辑的起点
+#[wasm_bindgen]
+pub fn preset_source(name: &str) -> Option<String> {
+    timer_core::PRESETS
         .iter()
-        .enumerate()
-        .map(|(i, &duration)| {
-            let start_time = now - (i as u64 * 1000); // 模拟不同开始时间
-            let elapsed = ((now - start_time) / 1000) as u32;
-            let current_time = duration.saturating_sub(elapsed);
-            
-            TimerCalculation {
-                time: current_time,
-                formatted_time: format!("{:02}:{:02}", current_time / 60, current_time % 60),
-                progress: if duration == 0 { 0.0 } else { (elapsed as f64 / duration as f64) * 100.0 },
-                remaining: current_time,
-                state: TimerState::Focus,
-            }
+        .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+        .map(|(_, source)| source.to_string())
+}
+
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DurationFormat {
+    Clock = 0,
+    MinutesSeconds = 1,
+    HoursMinutesSeconds = 2,
+    Compact = 3,
+    Verbose = 4,
+}
+
+impl From<DurationFormat> for DurationStyle {
+    fn from(format: DurationFormat) -> DurationStyle {
+        match format {
+            DurationFormat::Clock => DurationStyle::Clock,
+            DurationFormat::MinutesSeconds => DurationStyle::MinutesSeconds,
+            DurationFormat::HoursMinutesSeconds => DurationStyle::HoursMinutesSeconds,
+            DurationFormat::Compact => DurationStyle::Compact,
+            DurationFormat::Verbose => DurationStyle::Verbose,
+        }
+    }
+}
+
+// 统一的时长格式化入口，locale 为语言标签（如 "en"、"zh-CN"）
+#[wasm_bindgen]
+pub fn format_duration(seconds: u32, format: DurationFormat, locale: &str) -> String {
+    timer_core::format_duration(seconds, format.into(), Locale::from_tag(locale))
+}
+
+#[wasm_bindgen]
+pub fn format_time(seconds: u32) -> String {
+    timer_core::format_time(seconds)
+}
+
+// 无状态的心流休息建议，供不持有 TimerCalculator 的调用方使用；时长单位为秒
+#[wasm_bindgen]
+pub fn suggest_flow_break(focused_secs: u32, break_ratio: f64, min_break: u32, max_break: u32) -> u32 {
+    FlowPolicy { break_ratio, min_break, max_break }.break_for(focused_secs)
+}
+
+// 计算会话效率评分。metrics_json 为 SessionMetrics（camelCase），weights_json 为空字符串时使用默认权重；
+// 返回 {"score": 0-100, "breakdown": [{factor, score, weight, contribution}]} 的 JSON
+#[wasm_bindgen]
+pub fn compute_efficiency_score(metrics_json: &str, weights_json: &str) -> Result<String, JsValue> {
+    let metrics: SessionMetrics = serde_json::from_str(metrics_json)
+        .map_err(|e| JsValue::from_str(&format!("invalid session metrics: {}", e)))?;
+    let weights: ScoreWeights = if weights_json.trim().is_empty() {
+        ScoreWeights::default()
+    } else {
+        serde_json::from_str(weights_json)
+            .map_err(|e| JsValue::from_str(&format!("invalid score weights: {}", e)))?
+    };
+    let result = timer_core::score_session(&metrics, &weights);
+    serde_json::to_string(&result).map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
+// 无状态的刷新调度，供不持有 TimerCalculator 的调用方（如 Web Worker）使用
+#[wasm_bindgen]
+pub fn next_tick_delay(
+    elapsed_ms: f64,
+    duration_ms: f64,
+    precision: DisplayPrecision,
+    page_visible: bool,
+    direction: CountDirection,
+) -> f64 {
+    timer_core::tick::next_tick_delay(
+        elapsed_ms.max(0.0) as u64,
+        duration_ms.max(0.0) as u64,
+        precision.into(),
+        visibility(page_visible),
+        direction.into(),
+    ) as f64
+}
+
+// 本地时间下一次到达 hour:minute 的 Unix 毫秒时间，已过则取次日；
+// 本地时间按 calendar 的时区（含夏令时）换算
+#[wasm_bindgen]
+pub fn next_local_time(now_ms: f64, hour: u8, minute: u8, calendar: &DayCalendar) -> f64 {
+    timer_core::next_local_time(now_ms.max(0.0) as u64, hour, minute, calendar.inner.zone()) as f64
+}
+
+// 日/周/月汇总，按列返回以便直接得到 TypedArray；keys 的含义见 timer_core::stats::PeriodTotals
+#[wasm_bindgen]
+pub struct PeriodSeries {
+    totals: Vec<timer_core::PeriodTotals>,
+}
+
+#[wasm_bindgen]
+impl PeriodSeries {
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn keys(&self) -> Vec<f64> {
+        self.totals.iter().map(|t| t.key as f64).collect()
+    }
+
+    pub fn focus(&self) -> Vec<f64> {
+        self.totals.iter().map(|t| t.focus as f64).collect()
+    }
+
+    pub fn break_time(&self) -> Vec<f64> {
+        self.totals.iter().map(|t| t.break_time as f64).collect()
+    }
+
+    pub fn micro_breaks(&self) -> Vec<u32> {
+        self.totals.iter().map(|t| t.micro_breaks.min(u32::MAX as u64) as u32).collect()
+    }
+
+    pub fn sessions(&self) -> Vec<u32> {
+        self.totals.iter().map(|t| t.sessions).collect()
+    }
+
+    pub fn average_efficiency(&self) -> Vec<f64> {
+        self.totals.iter().map(|t| t.average_efficiency).collect()
+    }
+}
+
+#[wasm_bindgen]
+pub struct SessionStats {
+    inner: timer_core::StatsReport,
+}
+
+#[wasm_bindgen]
+impl SessionStats {
+    pub fn daily(&self) -> PeriodSeries {
+        PeriodSeries { totals: self.inner.daily.clone() }
+    }
+
+    pub fn weekly(&self) -> PeriodSeries {
+        PeriodSeries { totals: self.inner.weekly.clone() }
+    }
+
+    pub fn monthly(&self) -> PeriodSeries {
+        PeriodSeries { totals: self.inner.monthly.clone() }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_focus(&self) -> f64 {
+        self.inner.total_focus as f64
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn total_sessions(&self) -> u32 {
+        self.inner.total_sessions
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn average_daily_focus(&self) -> f64 {
+        self.inner.average_daily_focus
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn average_session_focus(&self) -> f64 {
+        self.inner.average_session_focus
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn average_efficiency(&self) -> f64 {
+        self.inner.average_efficiency
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn current_streak(&self) -> u32 {
+        self.inner.current_streak
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn longest_streak(&self) -> u32 {
+        self.inner.longest_streak
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn best_hour(&self) -> Option<u8> {
+        self.inner.best_hour
+    }
+
+    pub fn hourly_focus(&self) -> Vec<f64> {
+        self.inner.hourly_focus.iter().map(|&f| f as f64).collect()
+    }
+
+    // 365 天每日专注时长，最早的一天在前
+    pub fn heatmap(&self) -> Vec<f64> {
+        self.inner.heatmap.iter().map(|&f| f as f64).collect()
+    }
+
+    // 热力图第一天是星期几（0 为周一），用于排成按周分列的矩阵
+    #[wasm_bindgen(getter)]
+    pub fn heatmap_start_weekday(&self) -> u8 {
+        self.inner.heatmap_start_weekday
+    }
+}
+
+// 基于 Intl.DateTimeFormat 的 IANA 时区。偏移按小时缓存：整小时内偏移不变时直接复用
+struct IntlZone {
+    format: js_sys::Intl::DateTimeFormat,
+    name: String,
+    cache: RefCell<HashMap<i64, Option<i32>>>,
+}
+
+const MS_PER_HOUR: i64 = 3_600_000;
+
+impl IntlZone {
+    // name 为空时使用系统时区；时区名无效时返回 Err 而不是抛出异常
+    fn new(name: &str) -> Result<IntlZone, JsValue> {
+        let options = js_sys::Object::new();
+        if !name.is_empty() {
+            js_sys::Reflect::set(&options, &"timeZone".into(), &name.into())?;
+        }
+        for field in ["year", "month", "day", "hour", "minute", "second"] {
+            js_sys::Reflect::set(&options, &field.into(), &"numeric".into())?;
+        }
+        js_sys::Reflect::set(&options, &"hourCycle".into(), &"h23".into())?;
+
+        let intl = js_sys::Reflect::get(&js_sys::global(), &"Intl".into())?;
+        let constructor: js_sys::Function = js_sys::Reflect::get(&intl, &"DateTimeFormat".into())?.dyn_into()?;
+        let format: js_sys::Intl::DateTimeFormat =
+            js_sys::Reflect::construct(&constructor, &js_sys::Array::of2(&"en-US".into(), &options))?.unchecked_into();
+        let resolved = js_sys::Reflect::get(&format.resolved_options(), &"timeZone".into())?;
+        Ok(IntlZone {
+            format,
+            name: resolved.as_string().unwrap_or_else(|| name.to_string()),
+            cache: RefCell::new(HashMap::new()),
         })
-        .collect()
+    }
+
+    fn compute(&self, utc_ms: i64) -> i32 {
+        let utc_secs = utc_ms.div_euclid(1000);
+        let date = js_sys::Date::new(&JsValue::from_f64((utc_secs * 1000) as f64));
+        let (mut year, mut month, mut day, mut hour, mut minute, mut second) = (1970, 1, 1, 0, 0, 0);
+        for part in self.format.format_to_parts(&date).iter() {
+            let field = js_sys::Reflect::get(&part, &"type".into()).ok().and_then(|v| v.as_string());
+            let value = js_sys::Reflect::get(&part, &"value".into()).ok().and_then(|v| v.as_string());
+            let value: i64 = match value.and_then(|v| v.parse().ok()) {
+                Some(value) => value,
+                None => continue,
+            };
+            match field.as_deref() {
+                Some("year") => year = value,
+                Some("month") => month = value,
+                Some("day") => day = value,
+                // 部分旧引擎在 h23 下仍以 24 表示午夜
+                Some("hour") => hour = value % 24,
+                Some("minute") => minute = value,
+                Some("second") => second = value,
+                _ => {}
+            }
+        }
+        let local_secs = timer_core::calendar::days_from_civil(year, month as u32, day as u32) * 86_400
+            + hour * 3600 + minute * 60 + second;
+        ((local_secs - utc_secs) as f64 / 60.0).round() as i32
+    }
+}
+
+impl timer_core::TimeZone for IntlZone {
+    fn offset_minutes(&self, utc_ms: i64) -> i32 {
+        let bucket = utc_ms.div_euclid(MS_PER_HOUR);
+        let cached = *self.cache.borrow_mut().entry(bucket).or_insert_with(|| {
+            let start = self.compute(bucket * MS_PER_HOUR);
+            (start == self.compute((bucket + 1) * MS_PER_HOUR - 1)).then_some(start)
+        });
+        cached.unwrap_or_else(|| self.compute(utc_ms))
+    }
+}
+
+enum CalendarZone {
+    Intl(IntlZone),
+    Posix(timer_core::PosixZone, String),
+}
+
+impl timer_core::TimeZone for CalendarZone {
+    fn offset_minutes(&self, utc_ms: i64) -> i32 {
+        match self {
+            CalendarZone::Intl(zone) => zone.offset_minutes(utc_ms),
+            CalendarZone::Posix(zone, _) => zone.offset_minutes(utc_ms),
+        }
+    }
+}
+
+// 日期划分：按时区（含夏令时）和每天的开始时刻决定某一时刻属于哪一天。
+// 天以自 1970-01-01 起的天数表示
+#[wasm_bindgen]
+pub struct DayCalendar {
+    inner: timer_core::DayCalendar<CalendarZone>,
+}
+
+#[wasm_bindgen]
+impl DayCalendar {
+    // time_zone 为 IANA 时区名（如 "Asia/Shanghai"）、POSIX TZ 规则或空字符串（系统时区）；
+    // day_start_minutes 为每天开始的本地时刻，如 240 表示凌晨 4 点
+    #[wasm_bindgen(constructor)]
+    pub fn new(time_zone: &str, day_start_minutes: u32) -> Result<DayCalendar, JsValue> {
+        let zone = match IntlZone::new(time_zone) {
+            Ok(zone) => CalendarZone::Intl(zone),
+            Err(_) => timer_core::PosixZone::parse(time_zone)
+                .map(|zone| CalendarZone::Posix(zone, time_zone.to_string()))
+                .map_err(|e| JsValue::from_str(&format!("invalid time zone: {}", e)))?,
+        };
+        Ok(DayCalendar { inner: timer_core::DayCalendar::new(zone, day_start_minutes) })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn time_zone(&self) -> String {
+        match self.inner.zone() {
+            CalendarZone::Intl(zone) => zone.name.clone(),
+            CalendarZone::Posix(_, rule) => rule.clone(),
+        }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn day_start_minutes(&self) -> u32 {
+        self.inner.day_start_minutes()
+    }
+
+    pub fn offset_minutes(&self, utc_ms: f64) -> i32 {
+        timer_core::TimeZone::offset_minutes(self.inner.zone(), utc_ms as i64)
+    }
+
+    pub fn day_of(&self, utc_ms: f64) -> f64 {
+        self.inner.day_of(utc_ms as i64) as f64
+    }
+
+    // 第 day 天开始的 UTC 毫秒时间
+    pub fn day_start(&self, day: f64) -> f64 {
+        self.inner.day_start_utc(day as i64) as f64
+    }
+
+    // YYYY-MM-DD，用作会话记录的 date 字段
+    pub fn date_key(&self, utc_ms: f64) -> String {
+        self.inner.date_key(utc_ms as i64)
+    }
+
+    // 第 day 天所在周周一的天数，即 aggregate_sessions 周汇总的 key
+    pub fn week_of(&self, day: f64) -> f64 {
+        timer_core::stats::week_start(day as i64) as f64
+    }
+
+    // 第 day 天所在月的 年*100+月，即 aggregate_sessions 月汇总的 key
+    pub fn month_of(&self, day: f64) -> f64 {
+        timer_core::stats::month_key(day as i64) as f64
+    }
+
+    // [start_ms, end_ms) 落在各天的毫秒数，第一项对应 day_of(start_ms)
+    pub fn split(&self, start_ms: f64, end_ms: f64) -> Vec<f64> {
+        self.inner.split(start_ms as i64, end_ms as i64).into_iter().map(|ms| ms as f64).collect()
+    }
+}
+
+// 一次性聚合会话历史。各参数为等长的列（可直接传入 Float64Array / Uint32Array）：
+// 开始与结束时间（Unix 毫秒，结束时间可传空数组）、专注时长、休息时长、微休息次数、效率分；
+// 时长单位由调用方决定，结果沿用同一单位。日期按 calendar 划分，跨日会话按时长拆分到各天
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn aggregate_sessions(
+    started_at: &[f64],
+    ended_at: &[f64],
+    focus: &[u32],
+    break_time: &[u32],
+    micro_breaks: &[u32],
+    efficiency: &[f64],
+    now_ms: f64,
+    calendar: &DayCalendar,
+) -> Result<SessionStats, JsValue> {
+    let columns = timer_core::SessionColumns { started_at, ended_at, focus, break_time, micro_breaks, efficiency };
+    timer_core::aggregate(&columns, now_ms, &calendar.inner)
+        .map(|inner| SessionStats { inner })
+        .map_err(|e| JsValue::from_str(&format!("invalid session columns: {}", e)))
 }
 
 #[wasm_bindgen]
@@ -166,6 +1300,8 @@ pub fn benchmark_calculation(iterations: u32) -> f64 {
     for i in 0..iterations {
         result += (i as f64 * 1.1).sqrt();
     }
+    // 防止计算被优化掉
+    std::hint::black_box(result);
     
     let end = js_sys::Date::now();
     end - start
@@ -0,0 +1,449 @@
+// 日历与时区
+// 统一"哪一天"的划分：按时区（含夏令时）换算本地时间，支持自定义每天的开始时刻（如凌晨 4 点），
+// 跨越日界的会话按实际时长拆分到各天
+
+use std::fmt;
+
+const MS_PER_MINUTE: i64 = 60_000;
+const MS_PER_HOUR: i64 = 60 * MS_PER_MINUTE;
+const MS_PER_DAY: i64 = 24 * MS_PER_HOUR;
+const SECS_PER_DAY: i64 = 86_400;
+
+/// 时区：给出某一 UTC 时刻本地时间相对 UTC 的偏移（分钟，东为正）。
+/// 宿主按各自条件实现：wasm 端基于 Intl（IANA 时区名），核心内置固定偏移与 POSIX 规则
+pub trait TimeZone {
+    fn offset_minutes(&self, utc_ms: i64) -> i32;
+}
+
+impl<Z: TimeZone + ?Sized> TimeZone for &Z {
+    fn offset_minutes(&self, utc_ms: i64) -> i32 {
+        (**self).offset_minutes(utc_ms)
+    }
+}
+
+impl<Z: TimeZone + ?Sized> TimeZone for Box<Z> {
+    fn offset_minutes(&self, utc_ms: i64) -> i32 {
+        (**self).offset_minutes(utc_ms)
+    }
+}
+
+/// 固定偏移，不考虑夏令时
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct FixedOffset(pub i32);
+
+impl TimeZone for FixedOffset {
+    fn offset_minutes(&self, _utc_ms: i64) -> i32 {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZoneError {
+    pub rule: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ZoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid time zone rule `{}`: {}", self.rule, self.reason)
+    }
+}
+
+impl std::error::Error for ZoneError {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RuleDate {
+    // Jn：1-365，不计 2 月 29 日
+    Julian(u16),
+    // n：0-365，计 2 月 29 日
+    DayOfYear(u16),
+    // Mm.w.d：m 月第 w 个（5 为最后一个）星期 d（0 为周日）
+    MonthWeekday { month: u8, week: u8, weekday: u8 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct TransitionRule {
+    date: RuleDate,
+    // 切换时刻，以切换前的本地时间计（秒）
+    time: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DstRule {
+    offset: i32,
+    start: TransitionRule,
+    end: TransitionRule,
+}
+
+/// POSIX TZ 规则（如 `CET-1CEST,M3.5.0,M10.5.0/3`），即 IANA 时区数据末尾描述当前规则的字符串。
+/// 偏移以秒保存，东为正
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PosixZone {
+    std_offset: i32,
+    dst: Option<DstRule>,
+}
+
+impl PosixZone {
+    pub fn parse(rule: &str) -> Result<PosixZone, ZoneError> {
+        let error = |reason| ZoneError { rule: rule.to_string(), reason };
+        let mut cursor = Cursor { bytes: rule.trim().as_bytes(), pos: 0 };
+
+        cursor.name().ok_or_else(|| error("missing standard time name"))?;
+        // POSIX 偏移以西为正，这里统一换成东为正
+        let std_offset = -cursor.duration().ok_or_else(|| error("missing standard time offset"))?;
+        if cursor.done() {
+            return Ok(PosixZone { std_offset, dst: None });
+        }
+
+        cursor.name().ok_or_else(|| error("invalid daylight saving time name"))?;
+        let offset = match cursor.peek() {
+            Some(b',') | None => std_offset + 3600,
+            _ => -cursor.duration().ok_or_else(|| error("invalid daylight saving time offset"))?,
+        };
+        let (start, end) = if cursor.done() {
+            // 未给出切换规则时沿用 POSIX 默认（美国规则）
+            (
+                TransitionRule { date: RuleDate::MonthWeekday { month: 3, week: 2, weekday: 0 }, time: 7200 },
+                TransitionRule { date: RuleDate::MonthWeekday { month: 11, week: 1, weekday: 0 }, time: 7200 },
+            )
+        } else {
+            let start = cursor.transition().ok_or_else(|| error("invalid daylight saving time start"))?;
+            let end = cursor.transition().ok_or_else(|| error("invalid daylight saving time end"))?;
+            (start, end)
+        };
+        if !cursor.done() {
+            return Err(error("unexpected trailing characters"));
+        }
+        Ok(PosixZone { std_offset, dst: Some(DstRule { offset, start, end }) })
+    }
+
+    fn offset_secs(&self, utc_secs: i64) -> i32 {
+        let Some(dst) = self.dst else { return self.std_offset };
+        let year = civil_from_days((utc_secs + self.std_offset as i64).div_euclid(SECS_PER_DAY)).0;
+        let start = transition_secs(year, dst.start) - self.std_offset as i64;
+        let end = transition_secs(year, dst.end) - dst.offset as i64;
+        let in_dst = if start < end {
+            start <= utc_secs && utc_secs < end
+        } else {
+            // 南半球：夏令时跨越年末
+            !(end <= utc_secs && utc_secs < start)
+        };
+        if in_dst { dst.offset } else { self.std_offset }
+    }
+}
+
+impl TimeZone for PosixZone {
+    fn offset_minutes(&self, utc_ms: i64) -> i32 {
+        self.offset_secs(utc_ms.div_euclid(1000)) / 60
+    }
+}
+
+// `year` 年按规则切换的本地时刻（自 1970-01-01 起的秒数）
+fn transition_secs(year: i64, rule: TransitionRule) -> i64 {
+    let jan1 = days_from_civil(year, 1, 1);
+    let day = match rule.date {
+        RuleDate::Julian(n) => {
+            let n = n as i64;
+            jan1 + n - 1 + if is_leap_year(year) && n >= 60 { 1 } else { 0 }
+        }
+        RuleDate::DayOfYear(n) => jan1 + n as i64,
+        RuleDate::MonthWeekday { month, week, weekday } => {
+            let first = days_from_civil(year, month as u32, 1);
+            let next_month = if month == 12 { days_from_civil(year + 1, 1, 1) } else { days_from_civil(year, month as u32 + 1, 1) };
+            // 1970-01-01 是周四（周日为 0 时为 4）
+            let first_weekday = (first + 4).rem_euclid(7);
+            let mut day = first + (weekday as i64 - first_weekday).rem_euclid(7) + (week as i64 - 1) * 7;
+            while day >= next_month {
+                day -= 7;
+            }
+            day
+        }
+    };
+    day * SECS_PER_DAY + rule.time as i64
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // 时区缩写：至少 3 个字母，或用尖括号括起（如 `<+08>`）
+    fn name(&mut self) -> Option<()> {
+        let start = self.pos;
+        if self.eat(b'<') {
+            while !self.eat(b'>') {
+                self.peek()?;
+                self.pos += 1;
+            }
+            return (self.pos - start > 2).then_some(());
+        }
+        while matches!(self.peek(), Some(b) if b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        (self.pos - start >= 3).then_some(())
+    }
+
+    fn number(&mut self, max_digits: usize) -> Option<i64> {
+        let start = self.pos;
+        while self.pos - start < max_digits && matches!(self.peek(), Some(b) if b.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()
+    }
+
+    // [+|-]hh[:mm[:ss]]，返回秒数
+    fn duration(&mut self) -> Option<i32> {
+        let sign = if self.eat(b'-') { -1 } else { self.eat(b'+'); 1 };
+        let mut secs = self.number(3)? * 3600;
+        if self.eat(b':') {
+            secs += self.number(2)?.min(59) * 60;
+            if self.eat(b':') {
+                secs += self.number(2)?.min(59);
+            }
+        }
+        Some(sign * secs as i32)
+    }
+
+    // ,date[/time]
+    fn transition(&mut self) -> Option<TransitionRule> {
+        if !self.eat(b',') { return None; }
+        let date = if self.eat(b'J') {
+            RuleDate::Julian(self.number(3).filter(|n| (1..=365).contains(n))? as u16)
+        } else if self.eat(b'M') {
+            let month = self.number(2).filter(|n| (1..=12).contains(n))? as u8;
+            if !self.eat(b'.') { return None; }
+            let week = self.number(1).filter(|n| (1..=5).contains(n))? as u8;
+            if !self.eat(b'.') { return None; }
+            let weekday = self.number(1).filter(|n| (0..=6).contains(n))? as u8;
+            RuleDate::MonthWeekday { month, week, weekday }
+        } else {
+            RuleDate::DayOfYear(self.number(3).filter(|n| (0..=365).contains(n))? as u16)
+        };
+        let time = if self.eat(b'/') { self.duration()? } else { 7200 };
+        Some(TransitionRule { date, time })
+    }
+}
+
+/// 按时区和每天的开始时刻划分日期。第 `d` 天（自 1970-01-01 起）覆盖本地时间
+/// `d 日 day_start` 到 `d+1 日 day_start`；本地时间不存在（夏令时跳过）时从跳过后的第一刻开始，
+/// 重复（夏令时回拨）时取第一次出现
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DayCalendar<Z> {
+    zone: Z,
+    day_start_minutes: u32,
+}
+
+impl<Z: TimeZone> DayCalendar<Z> {
+    /// `day_start_minutes` 为每天开始的本地时刻（分钟），超出一天时取 23:59
+    pub fn new(zone: Z, day_start_minutes: u32) -> DayCalendar<Z> {
+        DayCalendar { zone, day_start_minutes: day_start_minutes.min(24 * 60 - 1) }
+    }
+
+    pub fn zone(&self) -> &Z {
+        &self.zone
+    }
+
+    pub fn day_start_minutes(&self) -> u32 {
+        self.day_start_minutes
+    }
+
+    /// 本地时间（以 UTC 方式表示的毫秒数）
+    pub fn local_ms(&self, utc_ms: i64) -> i64 {
+        utc_ms + self.zone.offset_minutes(utc_ms) as i64 * MS_PER_MINUTE
+    }
+
+    /// 本地小时（0-23），不受每天开始时刻影响
+    pub fn local_hour(&self, utc_ms: i64) -> u8 {
+        (self.local_ms(utc_ms).rem_euclid(MS_PER_DAY) / MS_PER_HOUR) as u8
+    }
+
+    /// `utc_ms` 所属的天
+    pub fn day_of(&self, utc_ms: i64) -> i64 {
+        let shift = self.day_start_minutes as i64 * MS_PER_MINUTE;
+        let day = (self.local_ms(utc_ms) - shift).div_euclid(MS_PER_DAY);
+        // 以日界为准修正夏令时回拨造成的本地时间倒退
+        if utc_ms < self.day_start_utc(day) {
+            day - 1
+        } else if utc_ms >= self.day_start_utc(day + 1) {
+            day + 1
+        } else {
+            day
+        }
+    }
+
+    /// 第 `day` 天开始的 UTC 毫秒时间
+    pub fn day_start_utc(&self, day: i64) -> i64 {
+        let local = day * MS_PER_DAY + self.day_start_minutes as i64 * MS_PER_MINUTE;
+        // 日界附近至多一次偏移变化：分别用前后一天的偏移求解
+        let before = local - self.zone.offset_minutes(local - MS_PER_DAY) as i64 * MS_PER_MINUTE;
+        let after = local - self.zone.offset_minutes(local + MS_PER_DAY) as i64 * MS_PER_MINUTE;
+        let exact = [before, after].into_iter().filter(|&utc| self.local_ms(utc) == local).min();
+        if let Some(utc) = exact {
+            return utc;
+        }
+
+        // 该本地时刻被夏令时跳过：找到切换的那一刻
+        let (mut lo, mut hi) = (before.min(after), before.max(after));
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if self.local_ms(mid) >= local { hi = mid; } else { lo = mid; }
+        }
+        hi
+    }
+
+    /// `[start_ms, end_ms)` 落在各天的毫秒数，从 `day_of(start_ms)` 起连续排列
+    pub fn split(&self, start_ms: i64, end_ms: i64) -> Vec<i64> {
+        if end_ms <= start_ms {
+            return vec![0];
+        }
+        let mut pieces = Vec::new();
+        let mut day = self.day_of(start_ms);
+        let mut cursor = start_ms;
+        while cursor < end_ms {
+            let next = self.day_start_utc(day + 1).min(end_ms);
+            pieces.push(next - cursor);
+            cursor = next;
+            day += 1;
+        }
+        pieces
+    }
+
+    /// `utc_ms` 所属天的公历日期
+    pub fn date_of(&self, utc_ms: i64) -> (i64, u32, u32) {
+        civil_from_days(self.day_of(utc_ms))
+    }
+
+    /// `YYYY-MM-DD` 形式的日期键，与会话记录的 `date` 字段一致
+    pub fn date_key(&self, utc_ms: i64) -> String {
+        let (year, month, day) = self.date_of(utc_ms);
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// 自 1970-01-01 起的天数转换为公历年月日（Howard Hinnant 的 civil_from_days 算法）
+pub fn civil_from_days(day: i64) -> (i64, u32, u32) {
+    let z = day + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+/// 公历年月日转换为自 1970-01-01 起的天数（civil_from_days 的逆运算）
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: i64 = MS_PER_HOUR;
+
+    fn utc(year: i64, month: u32, day: u32, hour: i64, minute: i64) -> i64 {
+        days_from_civil(year, month, day) * MS_PER_DAY + hour * HOUR + minute * MS_PER_MINUTE
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(2024, 2, 29), 19_782);
+        for day in [-800_000, -1, 0, 19_782, 2_932_896] {
+            let (y, m, d) = civil_from_days(day);
+            assert_eq!(days_from_civil(y, m, d), day);
+        }
+    }
+
+    #[test]
+    fn posix_rules_follow_dst() {
+        let berlin = PosixZone::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        // 2024 年 3 月 31 日 01:00 UTC 开始夏令时，10 月 27 日 01:00 UTC 结束
+        assert_eq!(berlin.offset_minutes(utc(2024, 3, 31, 0, 59)), 60);
+        assert_eq!(berlin.offset_minutes(utc(2024, 3, 31, 1, 0)), 120);
+        assert_eq!(berlin.offset_minutes(utc(2024, 10, 27, 0, 59)), 120);
+        assert_eq!(berlin.offset_minutes(utc(2024, 10, 27, 1, 0)), 60);
+
+        // 南半球夏令时跨年
+        let sydney = PosixZone::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(sydney.offset_minutes(utc(2024, 1, 15, 0, 0)), 660);
+        assert_eq!(sydney.offset_minutes(utc(2024, 7, 15, 0, 0)), 600);
+
+        assert_eq!(PosixZone::parse("<+0530>-5:30").unwrap().offset_minutes(0), 330);
+        assert!(PosixZone::parse("UTC").is_err());
+        assert!(PosixZone::parse("EST5EDT,M3.2.0").is_err());
+    }
+
+    #[test]
+    fn day_start_shifts_boundaries() {
+        let calendar = DayCalendar::new(FixedOffset(480), 4 * 60);
+        // 东八区 1 月 16 日 03:30 仍属于 1 月 15 日
+        let late_night = utc(2024, 1, 15, 19, 30);
+        assert_eq!(calendar.date_key(late_night), "2024-01-15");
+        assert_eq!(calendar.date_key(late_night + HOUR), "2024-01-16");
+        assert_eq!(calendar.day_start_utc(calendar.day_of(late_night)), utc(2024, 1, 14, 20, 0));
+        assert_eq!(calendar.local_hour(late_night), 3);
+    }
+
+    #[test]
+    fn dst_days_have_their_real_length() {
+        let new_york = PosixZone::parse("EST5EDT,M3.2.0,M11.1.0").unwrap();
+        let calendar = DayCalendar::new(&new_york, 0);
+        let spring = days_from_civil(2024, 3, 10);
+        let fall = days_from_civil(2024, 11, 3);
+        let length = |day: i64| calendar.day_start_utc(day + 1) - calendar.day_start_utc(day);
+        assert_eq!(length(spring), 23 * HOUR);
+        assert_eq!(length(fall), 25 * HOUR);
+
+        // 日界落在被跳过的 02:30 时，从 03:00 EDT 开始
+        let calendar = DayCalendar::new(&new_york, 150);
+        assert_eq!(calendar.day_start_utc(spring), utc(2024, 3, 10, 7, 0));
+        // 日界落在重复的 01:30 时取第一次（EDT），回拨后的一小时仍属当天
+        let calendar = DayCalendar::new(&new_york, 90);
+        let start = calendar.day_start_utc(fall);
+        assert_eq!(start, utc(2024, 11, 3, 5, 30));
+        assert_eq!(calendar.day_of(start + HOUR), fall);
+        assert_eq!(calendar.day_of(start - 1), fall - 1);
+    }
+
+    #[test]
+    fn splits_sessions_across_boundaries() {
+        let calendar = DayCalendar::new(FixedOffset(0), 0);
+        let start = utc(2024, 1, 15, 23, 30);
+        assert_eq!(calendar.split(start, start + HOUR), vec![30 * MS_PER_MINUTE, 30 * MS_PER_MINUTE]);
+        assert_eq!(calendar.split(start, start + 10), vec![10]);
+        assert_eq!(calendar.split(start, start), vec![0]);
+    }
+}
@@ -0,0 +1,214 @@
+// 时钟抽象
+// 计时核心只通过 Clock 读取时间，便于替换为浏览器、系统或测试时钟
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// 计时器使用的时间源
+pub trait Clock {
+    /// 单调时间（毫秒），只用于计算经过时长，不保证与系统时间对应
+    fn now_ms(&self) -> u64;
+
+    /// 系统墙上时间（Unix 毫秒），可能被 NTP 或用户手动调整
+    fn wall_ms(&self) -> u64 {
+        self.now_ms()
+    }
+}
+
+/// 原生单调时钟：以创建时的系统时间为基准，之后只按 `Instant` 前进，
+/// 不受系统时间调整影响
+#[derive(Clone, Debug)]
+pub struct MonotonicClock {
+    anchor: Instant,
+    anchor_ms: u64,
+}
+
+impl MonotonicClock {
+    pub fn new() -> MonotonicClock {
+        MonotonicClock {
+            anchor: Instant::now(),
+            anchor_ms: system_time_ms(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        MonotonicClock::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    fn now_ms(&self) -> u64 {
+        self.anchor_ms + self.anchor.elapsed().as_millis() as u64
+    }
+
+    fn wall_ms(&self) -> u64 {
+        system_time_ms()
+    }
+}
+
+fn system_time_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 测试用时钟：时间只在调用 `advance`/`set` 时变化。
+/// 克隆出的副本共享同一时间，可以一份交给计时器、一份留在测试里推进
+#[derive(Clone, Debug, Default)]
+pub struct FakeClock {
+    now: Arc<AtomicU64>,
+    wall_offset: Arc<AtomicI64>,
+}
+
+impl FakeClock {
+    pub fn new(start_ms: u64) -> FakeClock {
+        FakeClock {
+            now: Arc::new(AtomicU64::new(start_ms)),
+            wall_offset: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    /// 单调时间与墙上时间一起前进
+    pub fn advance(&self, ms: u64) {
+        self.now.fetch_add(ms, Ordering::SeqCst);
+    }
+
+    pub fn set(&self, ms: u64) {
+        self.now.store(ms, Ordering::SeqCst);
+    }
+
+    /// 只调整墙上时间，模拟用户或 NTP 修改系统时间
+    pub fn jump_wall(&self, delta_ms: i64) {
+        self.wall_offset.fetch_add(delta_ms, Ordering::SeqCst);
+    }
+
+    /// 模拟系统休眠：墙上时间前进，单调时间停止（Linux、macOS 上 `Instant` 的行为）
+    pub fn suspend(&self, ms: u64) {
+        self.jump_wall(ms as i64);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now_ms(&self) -> u64 {
+        self.now.load(Ordering::SeqCst)
+    }
+
+    fn wall_ms(&self) -> u64 {
+        let offset = self.wall_offset.load(Ordering::SeqCst);
+        (self.now_ms() as i64).saturating_add(offset).max(0) as u64
+    }
+}
+
+/// 检测到的时间异常
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockEvent {
+    /// 墙上时间与单调时间的走势不一致，`delta_ms` 为墙上时间多走（正）或倒退（负）的毫秒数
+    ClockJump { delta_ms: i64 },
+    /// 墙上时间比单调时间多走了至少休眠阈值，即单调时钟在系统休眠期间停止，`gap_ms` 为休眠时长。
+    /// 两次观测相隔很久（如后台标签页被节流）但两个时钟一致时不算休眠。
+    /// 手动把系统时间往后调超过阈值时两个时钟的表现与休眠相同，同样报告为休眠
+    SuspendGap { gap_ms: u64 },
+}
+
+/// 检测到休眠间隔后计时器的处理方式。
+/// 默认不计入：休眠与手动调快系统时间无法区分，后者不应让当前阶段多走
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GapPolicy {
+    /// 休眠时长计入计时
+    Keep,
+    /// 休眠时长不计入计时
+    #[default]
+    Discount,
+    /// 休眠时长不计入计时，并从休眠开始时起进入暂停
+    Pause,
+}
+
+pub const DEFAULT_JUMP_TOLERANCE_MS: u64 = 2_000;
+pub const DEFAULT_SUSPEND_THRESHOLD_MS: u64 = 120_000;
+
+/// 记录上一次观测到的单调/墙上时间，用来发现时钟跳变和休眠间隔
+#[derive(Clone, Debug)]
+pub struct ClockWatch {
+    last_mono: u64,
+    last_wall: u64,
+    jump_tolerance_ms: u64,
+    suspend_threshold_ms: u64,
+}
+
+impl ClockWatch {
+    pub fn new<C: Clock>(clock: &C) -> ClockWatch {
+        ClockWatch {
+            last_mono: clock.now_ms(),
+            last_wall: clock.wall_ms(),
+            jump_tolerance_ms: DEFAULT_JUMP_TOLERANCE_MS,
+            suspend_threshold_ms: DEFAULT_SUSPEND_THRESHOLD_MS,
+        }
+    }
+
+    pub fn last_mono(&self) -> u64 {
+        self.last_mono
+    }
+
+    pub fn set_thresholds(&mut self, jump_tolerance_ms: u64, suspend_threshold_ms: u64) {
+        self.jump_tolerance_ms = jump_tolerance_ms;
+        self.suspend_threshold_ms = suspend_threshold_ms;
+    }
+
+    /// 记录新的观测点并返回期间发生的异常
+    pub fn observe<C: Clock>(&mut self, clock: &C) -> Option<ClockEvent> {
+        let mono = clock.now_ms();
+        let wall = clock.wall_ms();
+        let mono_delta = mono.saturating_sub(self.last_mono);
+        let wall_delta = wall as i64 - self.last_wall as i64;
+        self.last_mono = mono;
+        self.last_wall = wall;
+
+        // 只看两个时钟的差异：观测间隔再长，只要两者同步前进就没有异常
+        let drift = wall_delta - mono_delta as i64;
+        if drift > 0 && drift as u64 >= self.suspend_threshold_ms {
+            return Some(ClockEvent::SuspendGap { gap_ms: drift as u64 });
+        }
+        if drift.unsigned_abs() > self.jump_tolerance_ms {
+            return Some(ClockEvent::ClockJump { delta_ms: drift });
+        }
+        None
+    }
+
+    /// 不做检测，直接以当前时间为新的观测起点
+    pub fn rebase<C: Clock>(&mut self, clock: &C) {
+        self.last_mono = clock.now_ms();
+        self.last_wall = clock.wall_ms();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_poll_interval_without_suspend_is_not_an_event() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut watch = ClockWatch::new(&clock);
+        clock.advance(DEFAULT_SUSPEND_THRESHOLD_MS * 10);
+        assert_eq!(watch.observe(&clock), None);
+    }
+
+    #[test]
+    fn suspend_with_monotonic_paused_is_a_gap() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut watch = ClockWatch::new(&clock);
+        clock.advance(5_000);
+        clock.suspend(3_600_000);
+        assert_eq!(watch.observe(&clock), Some(ClockEvent::SuspendGap { gap_ms: 3_600_000 }));
+
+        // 低于休眠阈值的正向偏移仍是时钟跳变
+        clock.suspend(60_000);
+        assert_eq!(watch.observe(&clock), Some(ClockEvent::ClockJump { delta_ms: 60_000 }));
+    }
+}
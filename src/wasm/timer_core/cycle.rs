@@ -0,0 +1,125 @@
+// 循环策略
+// 经典（番茄钟式）模式下的阶段切换与时长建议
+
+use serde::{Deserialize, Serialize};
+
+use super::timer::TimerPhase;
+
+/// 一次阶段切换的结果
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transition {
+    pub phase: TimerPhase,
+    // 下一阶段时长（秒）
+    pub duration: u32,
+}
+
+/// 循环策略，时长单位为秒；`long_break_interval`、`forced_break_threshold` 为 0 表示关闭对应规则
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CyclePolicy {
+    pub focus_duration: u32,
+    pub break_duration: u32,
+    pub long_break_duration: u32,
+    // 每完成 N 个专注段进入一次长休息
+    pub long_break_interval: u32,
+    // 连续专注达到该秒数后进入强制休息
+    pub forced_break_threshold: u32,
+    pub forced_break_duration: u32,
+}
+
+impl CyclePolicy {
+    /// 经典番茄钟：25 分钟专注、5 分钟休息，每 4 轮一次 15 分钟长休息
+    pub fn pomodoro() -> CyclePolicy {
+        CyclePolicy {
+            long_break_interval: 4,
+            ..CyclePolicy::default()
+        }
+    }
+
+    /// 完成 `completed_focus` 个专注段、连续专注 `continuous_focus` 秒后，当前阶段结束时的下一阶段
+    pub fn next_phase(
+        &self,
+        current: TimerPhase,
+        completed_focus: u32,
+        continuous_focus: u32,
+    ) -> TimerPhase {
+        match current {
+            TimerPhase::Focus => {
+                if self.forced_break_threshold > 0 && continuous_focus >= self.forced_break_threshold {
+                    TimerPhase::ForcedBreak
+                } else if self.long_break_interval > 0 && completed_focus % self.long_break_interval == 0 {
+                    TimerPhase::LongBreak
+                } else {
+                    TimerPhase::Break
+                }
+            }
+            TimerPhase::Break
+            | TimerPhase::LongBreak
+            | TimerPhase::ForcedBreak
+            | TimerPhase::MicroBreak
+            | TimerPhase::Idle => TimerPhase::Focus,
+        }
+    }
+
+    /// 阶段的建议时长（秒）；微休息与空闲由调用方决定，返回 0
+    pub fn duration_for(&self, phase: TimerPhase) -> u32 {
+        match phase {
+            TimerPhase::Focus => self.focus_duration,
+            TimerPhase::Break => self.break_duration,
+            TimerPhase::LongBreak => self.long_break_duration,
+            TimerPhase::ForcedBreak => self.forced_break_duration,
+            TimerPhase::MicroBreak | TimerPhase::Idle => 0,
+        }
+    }
+
+    /// 当前处于本组的第几轮（从 1 开始）。专注中是正在进行的一轮，
+    /// 休息中仍算刚完成的那一轮；未开启长休息时返回累计轮数
+    pub fn cycle_number(&self, phase: TimerPhase, completed_focus: u32) -> u32 {
+        let current = match phase {
+            TimerPhase::Focus => completed_focus + 1,
+            _ => completed_focus.max(1),
+        };
+        if self.long_break_interval == 0 { return current; }
+        (current - 1) % self.long_break_interval + 1
+    }
+}
+
+impl Default for CyclePolicy {
+    fn default() -> Self {
+        CyclePolicy {
+            focus_duration: 25 * 60,
+            break_duration: 5 * 60,
+            long_break_duration: 15 * 60,
+            long_break_interval: 0,
+            forced_break_threshold: 0,
+            forced_break_duration: 30 * 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pomodoro_long_break_every_fourth_cycle() {
+        let policy = CyclePolicy::pomodoro();
+        let phases: Vec<_> = (1..=8).map(|n| policy.next_phase(TimerPhase::Focus, n, 0)).collect();
+        assert_eq!(phases[3], TimerPhase::LongBreak);
+        assert_eq!(phases[7], TimerPhase::LongBreak);
+        assert_eq!(phases.iter().filter(|p| **p == TimerPhase::Break).count(), 6);
+        assert_eq!(policy.duration_for(TimerPhase::LongBreak), 900);
+    }
+
+    #[test]
+    fn cycle_number_wraps_per_set() {
+        let policy = CyclePolicy::pomodoro();
+        assert_eq!(policy.cycle_number(TimerPhase::Focus, 0), 1);
+        assert_eq!(policy.cycle_number(TimerPhase::Focus, 2), 3);
+        assert_eq!(policy.cycle_number(TimerPhase::Break, 3), 3);
+        assert_eq!(policy.cycle_number(TimerPhase::LongBreak, 4), 4);
+        assert_eq!(policy.cycle_number(TimerPhase::Focus, 4), 1);
+        assert_eq!(CyclePolicy::default().cycle_number(TimerPhase::Focus, 6), 7);
+    }
+}
@@ -0,0 +1,163 @@
+// 截止时间模式
+// "专注到 11:30 站会" 而不是 "专注 47 分钟"：由绝对时间推出计时窗口，并把专注/休息循环排进窗口
+
+use super::calendar::{DayCalendar, TimeZone};
+use super::cycle::{CyclePolicy, Transition};
+use super::timer::TimerPhase;
+
+// 缩短后的最后一个专注段不少于 5 分钟，更短的剩余时间留作空闲
+const MIN_SHORTENED_FOCUS: u32 = 5 * 60;
+
+/// 窗口放不下整数个循环时的处理方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FitStrategy {
+    /// 只安排完整的专注段，剩余时间空闲
+    #[default]
+    WholeCycles,
+    /// 最后一个专注段缩短到恰好在截止时间结束
+    ShortenLast,
+}
+
+/// 截止时间前的阶段安排
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeadlinePlan {
+    pub blocks: Vec<Transition>,
+    // 安排之后到截止时间还剩的秒数
+    pub slack: u32,
+}
+
+impl DeadlinePlan {
+    pub fn focus_time(&self) -> u32 {
+        self.blocks
+            .iter()
+            .filter(|b| b.phase == TimerPhase::Focus)
+            .map(|b| b.duration)
+            .sum()
+    }
+}
+
+/// `now_ms` 之后本地时间第一次到达 `hour:minute` 的 Unix 毫秒时间，本地时间按 `zone`（含夏令时）换算。
+/// 今天的该时刻已过（或正是现在）时取次日，因此 23:30 设定 00:15 得到 45 分钟后；
+/// 该时刻被夏令时跳过时取跳过后的第一刻
+pub fn next_local_time<Z: TimeZone>(now_ms: u64, hour: u8, minute: u8, zone: Z) -> u64 {
+    // 以目标时刻作为"一天的开始"，下一次到达即下一天的日界
+    let minutes = hour.min(23) as u32 * 60 + minute.min(59) as u32;
+    let calendar = DayCalendar::new(zone, minutes);
+    calendar.day_start_utc(calendar.day_of(now_ms as i64) + 1).max(0) as u64
+}
+
+/// 从 `now_ms` 到 `deadline_ms` 的整秒数，截止时间已过时为 0
+pub fn window_secs(now_ms: u64, deadline_ms: u64) -> u32 {
+    (deadline_ms.saturating_sub(now_ms) / 1000).min(u32::MAX as u64) as u32
+}
+
+/// 按循环策略把 `window` 秒排满专注与休息，最后一段总是专注。
+/// `completed_focus` 为已完成的专注段数量，用来决定长休息出现的位置；
+/// 窗口连一个完整专注段都放不下时，整个窗口作为一个专注段
+pub fn plan_until(window: u32, policy: &CyclePolicy, strategy: FitStrategy, completed_focus: u32) -> DeadlinePlan {
+    let focus = policy.focus_duration;
+    if window == 0 {
+        return DeadlinePlan { blocks: Vec::new(), slack: 0 };
+    }
+    if focus == 0 || window <= focus {
+        return DeadlinePlan {
+            blocks: vec![Transition { phase: TimerPhase::Focus, duration: window }],
+            slack: 0,
+        };
+    }
+
+    let mut blocks = vec![Transition { phase: TimerPhase::Focus, duration: focus }];
+    let mut remaining = window - focus;
+    let mut completed = completed_focus + 1;
+    loop {
+        let rest = policy.next_phase(TimerPhase::Focus, completed, 0);
+        let rest_duration = policy.duration_for(rest);
+        let after_rest = remaining.saturating_sub(rest_duration);
+        if remaining < rest_duration || after_rest == 0 { break; }
+
+        let next_focus = if after_rest >= focus {
+            focus
+        } else if strategy == FitStrategy::ShortenLast && after_rest >= MIN_SHORTENED_FOCUS {
+            after_rest
+        } else {
+            break;
+        };
+        blocks.push(Transition { phase: rest, duration: rest_duration });
+        blocks.push(Transition { phase: TimerPhase::Focus, duration: next_focus });
+        remaining = after_rest - next_focus;
+        completed += 1;
+    }
+
+    DeadlinePlan { blocks, slack: remaining }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer_core::calendar::{FixedOffset, PosixZone};
+
+    // 2023-11-14T22:13:20Z
+    const NOW: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn next_local_time_crosses_midnight() {
+        // 东八区此时为 11-15 06:13:20
+        let standup = next_local_time(NOW, 11, 30, FixedOffset(480));
+        assert_eq!(window_secs(NOW, standup), 5 * 3600 + 16 * 60 + 40);
+
+        // UTC 22:13 设定 00:15，应为次日凌晨
+        let late = next_local_time(NOW, 0, 15, FixedOffset(0));
+        assert_eq!(window_secs(NOW, late), 2 * 3600 + 60 + 40);
+
+        // 刚刚过去的时刻顺延一天
+        let passed = next_local_time(NOW, 22, 13, FixedOffset(0));
+        assert_eq!(window_secs(NOW, passed), 24 * 3600 - 20);
+        assert_eq!(window_secs(passed, NOW), 0);
+    }
+
+    #[test]
+    fn next_local_time_follows_dst_transitions() {
+        let berlin = PosixZone::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        // 2024-03-30T22:00:00Z，柏林本地 23:00（CET），当晚切换到夏令时，只有 23 小时
+        let before_spring = 1_711_836_000_000;
+        let standup = next_local_time(before_spring, 9, 0, &berlin);
+        // 次日 09:00 CEST = 07:00Z，相隔 9 小时而不是按切换前偏移算出的 10 小时
+        assert_eq!(window_secs(before_spring, standup), 9 * 3600);
+
+        // 02:30 当天不存在，取切换后的第一刻 03:00 CEST = 01:00Z
+        let skipped = next_local_time(before_spring, 2, 30, &berlin);
+        assert_eq!(window_secs(before_spring, skipped), 3 * 3600);
+
+        // 2024-10-26T22:00:00Z，本地 00:00（CEST），当天回拨一小时
+        let before_fall = 1_729_980_000_000;
+        let evening = next_local_time(before_fall, 18, 0, &berlin);
+        // 18:00 CET = 17:00Z，比固定偏移多一小时
+        assert_eq!(window_secs(before_fall, evening), 19 * 3600);
+    }
+
+    #[test]
+    fn fits_whole_cycles_or_shortens_last() {
+        let policy = CyclePolicy::pomodoro();
+        // 100 分钟：25+5+25+5+25 = 85，剩 15 分钟
+        let whole = plan_until(100 * 60, &policy, FitStrategy::WholeCycles, 0);
+        assert_eq!(whole.blocks.len(), 5);
+        assert_eq!(whole.focus_time(), 75 * 60);
+        assert_eq!(whole.slack, 15 * 60);
+
+        let shortened = plan_until(100 * 60, &policy, FitStrategy::ShortenLast, 0);
+        assert_eq!(shortened.blocks.len(), 7);
+        assert_eq!(shortened.blocks[6], Transition { phase: TimerPhase::Focus, duration: 10 * 60 });
+        assert_eq!(shortened.slack, 0);
+    }
+
+    #[test]
+    fn long_break_follows_policy_and_short_windows_are_one_block() {
+        let policy = CyclePolicy::pomodoro();
+        let plan = plan_until(4 * 3600, &policy, FitStrategy::WholeCycles, 2);
+        assert_eq!(plan.blocks[1].phase, TimerPhase::Break);
+        assert_eq!(plan.blocks[3], Transition { phase: TimerPhase::LongBreak, duration: 900 });
+
+        let plan = plan_until(20 * 60, &policy, FitStrategy::WholeCycles, 0);
+        assert_eq!(plan.blocks, vec![Transition { phase: TimerPhase::Focus, duration: 1200 }]);
+    }
+}
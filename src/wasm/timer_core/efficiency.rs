@@ -0,0 +1,161 @@
+// 效率评分
+// 综合完成度、暂停、休息、微休息执行情况与手动评分，得到 0-100 的分数和各项明细，
+// Web 与桌面端使用同一实现，保证统计页看到的数字一致
+
+use serde::{Deserialize, Serialize};
+
+/// 一次专注会话的原始数据，时长单位为秒
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionMetrics {
+    pub planned_focus: u32,
+    pub completed_focus: u32,
+    pub paused_time: u32,
+    pub pause_count: u32,
+    pub breaks_taken: u32,
+    pub breaks_skipped: u32,
+    pub micro_breaks_planned: u32,
+    pub micro_breaks_taken: u32,
+    // 用户手动评分（1-5），未评分时为 None
+    pub rating: Option<f64>,
+}
+
+/// 各项的权重；缺少数据的项不参与计算，其余项按权重重新归一
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScoreWeights {
+    pub completion: f64,
+    pub pauses: f64,
+    pub breaks: f64,
+    pub micro_breaks: f64,
+    pub rating: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            completion: 0.35,
+            pauses: 0.15,
+            breaks: 0.15,
+            micro_breaks: 0.10,
+            rating: 0.25,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScoreFactor {
+    Completion,
+    Pauses,
+    Breaks,
+    MicroBreaks,
+    Rating,
+}
+
+/// 单项得分：`score` 为该项 0-100 的分数，`weight` 为归一后的权重，
+/// `contribution` = score × weight，各项之和即总分
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactorScore {
+    pub factor: ScoreFactor,
+    pub score: f64,
+    pub weight: f64,
+    pub contribution: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EfficiencyScore {
+    pub score: u32,
+    pub breakdown: Vec<FactorScore>,
+}
+
+// 每次暂停扣 5 分；暂停时长占比每 1% 扣 2 分
+const PAUSE_PENALTY: f64 = 5.0;
+const PAUSED_RATIO_PENALTY: f64 = 200.0;
+
+pub fn score_session(metrics: &SessionMetrics, weights: &ScoreWeights) -> EfficiencyScore {
+    let mut factors: Vec<(ScoreFactor, f64, f64)> = Vec::new();
+
+    if metrics.planned_focus > 0 {
+        let ratio = (metrics.completed_focus as f64 / metrics.planned_focus as f64).min(1.0);
+        factors.push((ScoreFactor::Completion, ratio * 100.0, weights.completion));
+    }
+    let active = metrics.completed_focus + metrics.paused_time;
+    if active > 0 {
+        let paused_ratio = metrics.paused_time as f64 / active as f64;
+        let score = 100.0 - metrics.pause_count as f64 * PAUSE_PENALTY - paused_ratio * PAUSED_RATIO_PENALTY;
+        factors.push((ScoreFactor::Pauses, score, weights.pauses));
+    }
+    let breaks = metrics.breaks_taken + metrics.breaks_skipped;
+    if breaks > 0 {
+        factors.push((ScoreFactor::Breaks, metrics.breaks_taken as f64 / breaks as f64 * 100.0, weights.breaks));
+    }
+    if metrics.micro_breaks_planned > 0 {
+        let ratio = (metrics.micro_breaks_taken as f64 / metrics.micro_breaks_planned as f64).min(1.0);
+        factors.push((ScoreFactor::MicroBreaks, ratio * 100.0, weights.micro_breaks));
+    }
+    if let Some(rating) = metrics.rating {
+        factors.push((ScoreFactor::Rating, (rating - 1.0) / 4.0 * 100.0, weights.rating));
+    }
+
+    let total_weight: f64 = factors.iter().map(|(_, _, w)| w.max(0.0)).sum();
+    if total_weight <= 0.0 {
+        return EfficiencyScore { score: 0, breakdown: Vec::new() };
+    }
+
+    let breakdown: Vec<FactorScore> = factors
+        .into_iter()
+        .map(|(factor, score, weight)| {
+            let score = score.clamp(0.0, 100.0);
+            let weight = weight.max(0.0) / total_weight;
+            FactorScore { factor, score, weight, contribution: score * weight }
+        })
+        .collect();
+    let score = breakdown.iter().map(|f| f.contribution).sum::<f64>().round() as u32;
+    EfficiencyScore { score: score.min(100), breakdown }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perfect_session_scores_full_marks() {
+        let metrics = SessionMetrics {
+            planned_focus: 1500,
+            completed_focus: 1500,
+            breaks_taken: 1,
+            micro_breaks_planned: 2,
+            micro_breaks_taken: 2,
+            rating: Some(5.0),
+            ..SessionMetrics::default()
+        };
+        let result = score_session(&metrics, &ScoreWeights::default());
+        assert_eq!(result.score, 100);
+        assert_eq!(result.breakdown.len(), 5);
+        let weight: f64 = result.breakdown.iter().map(|f| f.weight).sum();
+        assert!((weight - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_factors_are_renormalized() {
+        // 只有完成度（60%）和评分（3 分 = 50）
+        let metrics = SessionMetrics {
+            planned_focus: 1500,
+            completed_focus: 900,
+            pause_count: 2,
+            paused_time: 100,
+            rating: Some(3.0),
+            ..SessionMetrics::default()
+        };
+        let result = score_session(&metrics, &ScoreWeights { pauses: 0.0, ..ScoreWeights::default() });
+        assert_eq!(result.score, 56);
+        let pauses = result.breakdown.iter().find(|f| f.factor == ScoreFactor::Pauses).unwrap();
+        assert_eq!(pauses.weight, 0.0);
+        assert!((pauses.score - 70.0).abs() < 1e-9);
+
+        assert_eq!(score_session(&SessionMetrics::default(), &ScoreWeights::default()).score, 0);
+    }
+}
@@ -0,0 +1,50 @@
+// 计时器事件
+// 计时器在状态变化时产生事件，调用方统一取走，无需再根据 remaining == 0 自行推断
+
+use super::clock::ClockEvent;
+use super::timer::TimerPhase;
+
+// 未被取走的事件上限，超出时丢弃最早的事件
+const MAX_PENDING_EVENTS: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimerEvent {
+    /// 开始了一个新阶段
+    Started { state: TimerPhase },
+    Paused,
+    Resumed,
+    /// 阶段倒计时归零，每个阶段只触发一次
+    Completed { state: TimerPhase },
+    /// 正计时的专注段到达软目标，之后继续计时；每个阶段只触发一次
+    TargetReached,
+    /// 专注段归零后仍在计时，按超时规则的间隔重复提醒；`overtime` 为已超时秒数
+    OvertimeReminder { overtime: u32 },
+    StateChanged { from: TimerPhase, to: TimerPhase },
+    /// 到达计划中的微休息时间，`duration` 为建议时长（秒）
+    MicroBreakDue { duration: u32 },
+    /// 连续专注达到强制休息阈值，每个专注段只触发一次
+    ForcedBreakDue,
+    ClockJumpDetected(ClockEvent),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EventQueue {
+    events: Vec<TimerEvent>,
+}
+
+impl EventQueue {
+    pub fn push(&mut self, event: TimerEvent) {
+        if self.events.len() >= MAX_PENDING_EVENTS {
+            self.events.remove(0);
+        }
+        self.events.push(event);
+    }
+
+    pub fn drain(&mut self) -> Vec<TimerEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
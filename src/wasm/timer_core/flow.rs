@@ -0,0 +1,59 @@
+// 心流（正计时）模式
+// 专注没有固定终点，只设一个软目标；结束时按专注时长的比例建议休息时长
+
+use serde::{Deserialize, Serialize};
+
+/// 计时方向
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CountMode {
+    /// 从阶段时长倒数到 0
+    #[default]
+    Down = 0,
+    /// 从 0 正计时，阶段时长只作为软目标，到达后继续计时
+    Up = 1,
+}
+
+/// 心流模式的休息建议规则，时长单位为秒
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FlowPolicy {
+    // 休息时长占专注时长的比例
+    pub break_ratio: f64,
+    pub min_break: u32,
+    pub max_break: u32,
+}
+
+impl FlowPolicy {
+    /// 专注 `focused` 秒后建议的休息时长，按 `break_ratio` 换算后限制在 [min_break, max_break]，取整到分钟
+    pub fn break_for(&self, focused: u32) -> u32 {
+        let raw = (focused as f64 * self.break_ratio.max(0.0)).round() as u32;
+        let minutes = (raw + 30) / 60 * 60;
+        minutes.clamp(self.min_break, self.max_break.max(self.min_break))
+    }
+}
+
+impl Default for FlowPolicy {
+    fn default() -> Self {
+        FlowPolicy {
+            break_ratio: 0.2,
+            min_break: 5 * 60,
+            max_break: 30 * 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn break_is_proportional_and_clamped() {
+        let policy = FlowPolicy::default();
+        assert_eq!(policy.break_for(50 * 60), 10 * 60);
+        assert_eq!(policy.break_for(62 * 60), 12 * 60);
+        assert_eq!(policy.break_for(10 * 60), 5 * 60);
+        assert_eq!(policy.break_for(4 * 3600), 30 * 60);
+    }
+}
@@ -0,0 +1,124 @@
+// 时长格式化
+// 计时器显示、统计页、托盘提示与通知共用的格式化实现
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DurationStyle {
+    /// 不足 1 小时为 `MM:SS`，否则为 `H:MM:SS`
+    Clock = 0,
+    /// 始终为 `MM:SS`，分钟不进位为小时
+    MinutesSeconds = 1,
+    /// 始终为 `H:MM:SS`
+    HoursMinutesSeconds = 2,
+    /// 紧凑格式，如 `1h 25m`、`25m 30s`
+    Compact = 3,
+    /// 本地化的完整格式，如 `1 hour 25 minutes`、`1 小时 25 分`
+    Verbose = 4,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Locale {
+    /// 解析 BCP 47 语言标签，未知语言回退为英文
+    pub fn from_tag(tag: &str) -> Locale {
+        if tag.to_ascii_lowercase().starts_with("zh") { Locale::Zh } else { Locale::En }
+    }
+}
+
+/// 计时器默认显示格式
+pub fn format_time(seconds: u32) -> String {
+    format_duration(seconds, DurationStyle::Clock, Locale::En)
+}
+
+pub fn format_duration(seconds: u32, style: DurationStyle, locale: Locale) -> String {
+    let hours = seconds / 3600;
+    let mins = (seconds % 3600) / 60;
+    let secs = seconds % 60;
+
+    match style {
+        DurationStyle::Clock if hours > 0 => format!("{}:{:02}:{:02}", hours, mins, secs),
+        DurationStyle::Clock | DurationStyle::MinutesSeconds => format!("{:02}:{:02}", seconds / 60, secs),
+        DurationStyle::HoursMinutesSeconds => format!("{}:{:02}:{:02}", hours, mins, secs),
+        DurationStyle::Compact => join_units(hours, mins, secs, |value, unit| {
+            let suffix = match unit {
+                Unit::Hour => "h",
+                Unit::Minute => "m",
+                Unit::Second => "s",
+            };
+            format!("{}{}", value, suffix)
+        }),
+        DurationStyle::Verbose => join_units(hours, mins, secs, |value, unit| match locale {
+            Locale::En => {
+                let name = match unit {
+                    Unit::Hour => "hour",
+                    Unit::Minute => "minute",
+                    Unit::Second => "second",
+                };
+                format!("{} {}{}", value, name, if value == 1 { "" } else { "s" })
+            }
+            Locale::Zh => {
+                let name = match unit {
+                    Unit::Hour => "小时",
+                    Unit::Minute => "分",
+                    Unit::Second => "秒",
+                };
+                format!("{} {}", value, name)
+            }
+        }),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Hour,
+    Minute,
+    Second,
+}
+
+// 只保留最大的两个单位：有小时时省略秒，零值单位省略
+fn join_units(hours: u32, mins: u32, secs: u32, render: impl Fn(u32, Unit) -> String) -> String {
+    let parts: Vec<(u32, Unit)> = if hours > 0 {
+        vec![(hours, Unit::Hour), (mins, Unit::Minute)]
+    } else if mins > 0 {
+        vec![(mins, Unit::Minute), (secs, Unit::Second)]
+    } else {
+        return render(secs, Unit::Second);
+    };
+
+    parts
+        .into_iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| render(value, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_styles() {
+        assert_eq!(format_time(1500), "25:00");
+        assert_eq!(format_time(9000), "2:30:00");
+        assert_eq!(format_duration(9000, DurationStyle::MinutesSeconds, Locale::En), "150:00");
+        assert_eq!(format_duration(65, DurationStyle::HoursMinutesSeconds, Locale::En), "0:01:05");
+    }
+
+    #[test]
+    fn compact_and_verbose() {
+        assert_eq!(format_duration(5130, DurationStyle::Compact, Locale::En), "1h 25m");
+        assert_eq!(format_duration(3600, DurationStyle::Compact, Locale::En), "1h");
+        assert_eq!(format_duration(90, DurationStyle::Compact, Locale::Zh), "1m 30s");
+        assert_eq!(format_duration(0, DurationStyle::Compact, Locale::En), "0s");
+        assert_eq!(format_duration(5130, DurationStyle::Verbose, Locale::En), "1 hour 25 minutes");
+        assert_eq!(format_duration(61, DurationStyle::Verbose, Locale::En), "1 minute 1 second");
+        assert_eq!(format_duration(5130, DurationStyle::Verbose, Locale::from_tag("zh-CN")), "1 小时 25 分");
+        assert_eq!(format_duration(45, DurationStyle::Verbose, Locale::Zh), "45 秒");
+    }
+}
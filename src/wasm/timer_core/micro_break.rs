@@ -0,0 +1,191 @@
+// 微休息调度
+// 使用带种子的伪随机数生成器，同样的种子和设置总能得到同样的微休息安排
+
+use serde::{Deserialize, Serialize};
+
+use super::smart::SmartSettings;
+
+/// 微休息间隔的分布，单位均为秒（以专注计时为准，不含微休息本身）
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distribution {
+    /// 在 [min, max] 内均匀分布
+    Uniform { min: u32, max: u32 },
+    /// 固定间隔，上下浮动不超过 jitter
+    Jittered { interval: u32, jitter: u32 },
+    /// 泊松过程（指数分布间隔），相邻两次至少间隔 min_gap，平均间隔为 mean
+    Poisson { mean: u32, min_gap: u32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MicroBreakConfig {
+    pub distribution: Distribution,
+    // 微休息时长范围（秒）
+    pub min_duration: u32,
+    pub max_duration: u32,
+    // 与主休息（专注段开始前和结束时）保持的最小距离（秒）
+    pub guard: u32,
+}
+
+impl MicroBreakConfig {
+    /// 按智能模式设置生成均匀分布配置，`guard_minutes` 为与主休息的最小距离
+    pub fn from_smart(settings: &SmartSettings, guard_minutes: u32) -> MicroBreakConfig {
+        MicroBreakConfig {
+            distribution: Distribution::Uniform {
+                min: settings.micro_break_min_interval.saturating_mul(60),
+                max: settings.micro_break_max_interval.saturating_mul(60),
+            },
+            min_duration: settings.micro_break_min_duration.saturating_mul(60),
+            max_duration: settings.micro_break_max_duration.saturating_mul(60),
+            guard: guard_minutes.saturating_mul(60),
+        }
+    }
+}
+
+/// 一次计划中的微休息
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MicroBreak {
+    // 距专注段开始的专注秒数
+    pub at: u32,
+    pub duration: u32,
+}
+
+/// SplitMix64：足够均匀、实现简单，且在 wasm 与原生平台上结果一致
+#[derive(Clone, Debug)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> SeededRng {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 内的浮点数
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [min, max] 内的整数；min > max 时返回 min
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        if max <= min { return min; }
+        let span = (max - min) as u64 + 1;
+        min + (self.next_u64() % span) as u32
+    }
+}
+
+pub struct MicroBreakScheduler {
+    config: MicroBreakConfig,
+    rng: SeededRng,
+}
+
+impl MicroBreakScheduler {
+    pub fn new(config: MicroBreakConfig, seed: u64) -> MicroBreakScheduler {
+        MicroBreakScheduler {
+            config,
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    pub fn config(&self) -> &MicroBreakConfig {
+        &self.config
+    }
+
+    /// 为一个专注段（秒）安排全部微休息，保证每次微休息都落在
+    /// [guard, focus_duration - guard] 之内
+    pub fn plan(&mut self, focus_duration: u32) -> Vec<MicroBreak> {
+        let guard = self.config.guard;
+        let latest_end = focus_duration.saturating_sub(guard);
+        let mut breaks = Vec::new();
+        let mut at = 0u32;
+
+        loop {
+            // 间隔至少 1 秒，避免配置为 0 时死循环
+            at = at.saturating_add(self.sample_interval().max(1));
+            if at >= latest_end { break; }
+            let duration = self.rng.range(self.config.min_duration, self.config.max_duration);
+            if at < guard || at.saturating_add(duration) > latest_end { continue; }
+            breaks.push(MicroBreak { at, duration });
+        }
+        breaks
+    }
+
+    fn sample_interval(&mut self) -> u32 {
+        match self.config.distribution {
+            Distribution::Uniform { min, max } => self.rng.range(min, max),
+            Distribution::Jittered { interval, jitter } => {
+                self.rng.range(interval.saturating_sub(jitter), interval.saturating_add(jitter))
+            }
+            Distribution::Poisson { mean, min_gap } => {
+                let rate_mean = mean.saturating_sub(min_gap) as f64;
+                // 1 - u 落在 (0, 1]，避免 ln(0)
+                let exp = -rate_mean * (1.0 - self.rng.next_f64()).ln();
+                min_gap.saturating_add(exp.round() as u32)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(distribution: Distribution) -> MicroBreakConfig {
+        MicroBreakConfig {
+            distribution,
+            min_duration: 180,
+            max_duration: 300,
+            guard: 10 * 60,
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_plan() {
+        let cfg = config(Distribution::Uniform { min: 600, max: 1800 });
+        let a = MicroBreakScheduler::new(cfg, 42).plan(90 * 60);
+        let b = MicroBreakScheduler::new(cfg, 42).plan(90 * 60);
+        let c = MicroBreakScheduler::new(cfg, 43).plan(90 * 60);
+        assert_eq!(a, b);
+        assert!(!a.is_empty());
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn breaks_respect_guard_and_distribution() {
+        let focus = 90 * 60;
+        let distributions = [
+            Distribution::Uniform { min: 600, max: 1800 },
+            Distribution::Jittered { interval: 1200, jitter: 120 },
+            Distribution::Poisson { mean: 900, min_gap: 300 },
+        ];
+        for distribution in distributions {
+            for seed in 0..50 {
+                let cfg = config(distribution);
+                let plan = MicroBreakScheduler::new(cfg, seed).plan(focus);
+                let mut last = 0;
+                for b in &plan {
+                    assert!(b.at >= cfg.guard);
+                    assert!(b.at + b.duration <= focus - cfg.guard);
+                    assert!((180..=300).contains(&b.duration));
+                    if let Distribution::Poisson { min_gap, .. } = distribution {
+                        assert!(b.at - last >= min_gap);
+                    }
+                    last = b.at;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn short_focus_has_no_micro_breaks() {
+        let cfg = config(Distribution::Uniform { min: 60, max: 120 });
+        assert!(MicroBreakScheduler::new(cfg, 7).plan(15 * 60).is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+// Timer Core
+// 与运行环境无关的计时器核心逻辑，供 wasm 模块与 Tauri 后端共用
+
+pub mod calendar;
+pub mod clock;
+pub mod cycle;
+pub mod deadline;
+pub mod efficiency;
+pub mod events;
+pub mod flow;
+pub mod format;
+pub mod micro_break;
+pub mod overtime;
+pub mod program;
+pub mod registry;
+pub mod smart;
+pub mod snapshot;
+pub mod stats;
+pub mod tick;
+pub mod timer;
+
+pub use calendar::{DayCalendar, FixedOffset, PosixZone, TimeZone, ZoneError};
+pub use clock::{Clock, ClockEvent, ClockWatch, FakeClock, GapPolicy, MonotonicClock};
+pub use micro_break::{Distribution, MicroBreak, MicroBreakConfig, MicroBreakScheduler};
+pub use cycle::{CyclePolicy, Transition};
+pub use deadline::{next_local_time, DeadlinePlan, FitStrategy};
+pub use efficiency::{score_session, EfficiencyScore, FactorScore, ScoreFactor, ScoreWeights, SessionMetrics};
+pub use events::TimerEvent;
+pub use flow::{CountMode, FlowPolicy};
+pub use format::{format_duration, format_time, DurationStyle, Locale};
+pub use overtime::{OvertimeMode, OvertimePolicy};
+pub use program::{Program, ProgramError, Step, PRESETS};
+pub use registry::TimerRegistry;
+pub use smart::{SmartScheduler, SmartSettings, SmartState};
+pub use snapshot::{SnapshotError, TimerSnapshot, SNAPSHOT_VERSION};
+pub use stats::{aggregate, PeriodTotals, SessionColumns, StatsError, StatsReport};
+pub use tick::{TickPrecision, Visibility};
+pub use timer::{Calculation, RunStatus, Timer, TimerPhase};
@@ -0,0 +1,69 @@
+// 超时计时
+// 专注段倒计时归零后继续计时，超出的时间计入会话或用来调整下一次休息
+
+use serde::{Deserialize, Serialize};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OvertimeMode {
+    /// 不记录超时，归零后的时间不计入统计
+    #[default]
+    Off = 0,
+    /// 超时计入本次专注（连续专注时间与会话时长）
+    CountTowardSession = 1,
+    /// 超时不计入专注，按 `break_factor` 调整下一次休息
+    AdjustBreak = 2,
+}
+
+/// 超时规则，时长单位为秒
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OvertimePolicy {
+    pub mode: OvertimeMode,
+    // 每超时 1 秒，下一次休息增加的秒数；为负时缩短休息
+    pub break_factor: f64,
+    // 超时提醒间隔，0 表示只在归零时提醒一次
+    pub reminder_interval: u32,
+}
+
+impl OvertimePolicy {
+    pub fn enabled(&self) -> bool {
+        self.mode != OvertimeMode::Off
+    }
+
+    /// 专注段超时 `overtime` 秒后，下一次休息的时长（不小于 0）
+    pub fn adjust_break(&self, base: u32, overtime: u32) -> u32 {
+        if self.mode != OvertimeMode::AdjustBreak { return base; }
+        let adjusted = base as f64 + overtime as f64 * self.break_factor;
+        adjusted.round().clamp(0.0, u32::MAX as f64) as u32
+    }
+}
+
+impl Default for OvertimePolicy {
+    fn default() -> Self {
+        OvertimePolicy {
+            mode: OvertimeMode::Off,
+            break_factor: 0.2,
+            reminder_interval: 5 * 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn break_adjusts_only_in_adjust_mode() {
+        let lengthen = OvertimePolicy { mode: OvertimeMode::AdjustBreak, ..OvertimePolicy::default() };
+        assert_eq!(lengthen.adjust_break(300, 600), 420);
+
+        let shorten = OvertimePolicy { break_factor: -0.5, ..lengthen };
+        assert_eq!(shorten.adjust_break(300, 240), 180);
+        assert_eq!(shorten.adjust_break(300, 3600), 0);
+
+        let counted = OvertimePolicy { mode: OvertimeMode::CountTowardSession, ..lengthen };
+        assert_eq!(counted.adjust_break(300, 600), 300);
+    }
+}
@@ -0,0 +1,376 @@
+// 计时程序
+// 用简短的文本描述自定义的阶段序列，例如 `repeat 4 { focus 25m; break 5m }; long_break 20m`，
+// 计时器按程序逐段推进，走完后从头开始
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::cycle::Transition;
+use super::timer::TimerPhase;
+
+// 展开后的最大段数，防止嵌套 repeat 生成过长的序列
+pub const MAX_PROGRAM_STEPS: usize = 10_000;
+const MAX_REPEAT: u32 = 1_000;
+
+/// 内置程序，名称可用于 `Program::preset`
+pub const PRESETS: &[(&str, &str)] = &[
+    ("pomodoro", "repeat 3 { focus 25m; break 5m }\nfocus 25m\nlong_break 15m"),
+    ("52-17", "focus 52m; break 17m"),
+    ("ultradian", "focus 90m; break 20m"),
+];
+
+/// 程序中的一步：单个阶段，或重复若干次的一组步骤
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Step {
+    Phase { phase: TimerPhase, duration: u32 },
+    Repeat { repeat: u32, steps: Vec<Step> },
+}
+
+/// 计时程序。JSON 形式为步骤数组，如 `[{"repeat": 4, "steps": [{"phase": "focus", "duration": 1500}]}]`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Program {
+    pub steps: Vec<Step>,
+}
+
+/// 解析或校验错误；`line` 从 1 开始，JSON 输入中的结构错误没有行号时为 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ProgramError {
+    fn new(line: usize, message: impl Into<String>) -> ProgramError {
+        ProgramError { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ProgramError {}
+
+impl Program {
+    /// 解析文本程序。每行（或以 `;` 分隔）一个步骤：`<阶段> <时长>` 或 `repeat <次数> { ... }`；
+    /// 阶段为 focus、break、long_break、forced_break、micro_break，时长如 `25m`、`90s`、`1h30m`；`#` 之后为注释
+    pub fn parse(source: &str) -> Result<Program, ProgramError> {
+        let tokens = tokenize(source);
+        let mut parser = Parser { tokens: &tokens, pos: 0 };
+        let steps = parser.block(None)?;
+        let program = Program { steps };
+        program.validate().map_err(|message| ProgramError::new(1, message))?;
+        Ok(program)
+    }
+
+    pub fn from_json(json: &str) -> Result<Program, ProgramError> {
+        let program: Program = serde_json::from_str(json)
+            .map_err(|e| ProgramError::new(e.line(), format!("invalid program JSON: {}", e)))?;
+        check_steps(&program.steps).map_err(|message| ProgramError::new(0, message))?;
+        program.validate().map_err(|message| ProgramError::new(0, message))?;
+        Ok(program)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("program serialization cannot fail")
+    }
+
+    /// 按名称取内置程序（忽略大小写）
+    pub fn preset(name: &str) -> Option<Program> {
+        PRESETS
+            .iter()
+            .find(|(preset, _)| preset.eq_ignore_ascii_case(name))
+            .map(|(_, source)| Program::parse(source).expect("built-in presets are valid"))
+    }
+
+    /// 展开 repeat 后的完整阶段序列
+    pub fn expand(&self) -> Vec<Transition> {
+        let mut blocks = Vec::new();
+        expand_into(&self.steps, &mut blocks);
+        blocks
+    }
+
+    /// 走完一遍程序的总秒数
+    pub fn total_duration(&self) -> u64 {
+        self.expand().iter().map(|b| b.duration as u64).sum()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let length = expanded_len(&self.steps);
+        if length == 0 {
+            return Err("program has no steps".to_string());
+        }
+        if length > MAX_PROGRAM_STEPS {
+            return Err(format!("program expands to more than {} steps", MAX_PROGRAM_STEPS));
+        }
+        if !self.expand().iter().any(|b| b.phase == TimerPhase::Focus) {
+            return Err("program needs at least one focus step".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn expand_into(steps: &[Step], blocks: &mut Vec<Transition>) {
+    for step in steps {
+        match step {
+            Step::Phase { phase, duration } => blocks.push(Transition { phase: *phase, duration: *duration }),
+            Step::Repeat { repeat, steps } => {
+                for _ in 0..*repeat {
+                    expand_into(steps, blocks);
+                }
+            }
+        }
+    }
+}
+
+// 饱和计算，避免在展开之前就因嵌套 repeat 溢出
+fn expanded_len(steps: &[Step]) -> usize {
+    steps
+        .iter()
+        .map(|step| match step {
+            Step::Phase { .. } => 1,
+            Step::Repeat { repeat, steps } => expanded_len(steps).saturating_mul(*repeat as usize),
+        })
+        .fold(0, usize::saturating_add)
+}
+
+// JSON 输入没有经过文本解析器，单独检查各步骤的取值
+fn check_steps(steps: &[Step]) -> Result<(), String> {
+    for step in steps {
+        match step {
+            Step::Phase { phase, duration } => {
+                if *phase == TimerPhase::Idle {
+                    return Err("idle is not a program phase".to_string());
+                }
+                if *duration == 0 {
+                    return Err("step duration must be positive".to_string());
+                }
+            }
+            Step::Repeat { repeat, steps } => {
+                if *repeat == 0 || *repeat > MAX_REPEAT {
+                    return Err(format!("repeat count must be between 1 and {}", MAX_REPEAT));
+                }
+                if steps.is_empty() {
+                    return Err("repeat block is empty".to_string());
+                }
+                check_steps(steps)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Open,
+    Close,
+    // `;` 或换行
+    Separator,
+    End,
+}
+
+fn tokenize(source: &str) -> Vec<(Token, usize)> {
+    let mut tokens = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let line_no = index + 1;
+        let code = line.split('#').next().unwrap_or("");
+        let mut word = String::new();
+        for ch in code.chars() {
+            let token = match ch {
+                '{' => Some(Token::Open),
+                '}' => Some(Token::Close),
+                ';' => Some(Token::Separator),
+                ch if ch.is_whitespace() => None,
+                ch => {
+                    word.push(ch);
+                    continue;
+                }
+            };
+            if !word.is_empty() {
+                tokens.push((Token::Word(std::mem::take(&mut word)), line_no));
+            }
+            if let Some(token) = token {
+                tokens.push((token, line_no));
+            }
+        }
+        if !word.is_empty() {
+            tokens.push((Token::Word(word), line_no));
+        }
+        tokens.push((Token::Separator, line_no));
+    }
+    let last_line = tokens.last().map_or(1, |(_, line)| *line);
+    tokens.push((Token::End, last_line));
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn next(&mut self) -> (Token, usize) {
+        let token = self.tokens[self.pos].clone();
+        if token.0 != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    // 解析到 `}`（repeat 内，`opened_at` 为 `{` 所在行）或输入结束
+    fn block(&mut self, opened_at: Option<usize>) -> Result<Vec<Step>, ProgramError> {
+        let mut steps = Vec::new();
+        loop {
+            while *self.peek() == Token::Separator {
+                self.next();
+            }
+            match (self.next(), opened_at) {
+                ((Token::End, _), None) => return Ok(steps),
+                ((Token::End, line), Some(opened)) => {
+                    return Err(ProgramError::new(line, format!("missing `}}` for block opened on line {}", opened)))
+                }
+                ((Token::Close, _), Some(_)) => return Ok(steps),
+                ((Token::Close, line), None) => return Err(ProgramError::new(line, "unexpected `}`")),
+                ((Token::Open, line), _) => return Err(ProgramError::new(line, "unexpected `{`")),
+                ((Token::Separator, _), _) => unreachable!("separators are skipped above"),
+                ((Token::Word(word), line), _) => steps.push(self.step(&word, line)?),
+            }
+            match self.peek() {
+                Token::Separator | Token::Close | Token::End => {}
+                _ => {
+                    let line = self.tokens[self.pos].1;
+                    return Err(ProgramError::new(line, "expected `;` or a new line between steps"));
+                }
+            }
+        }
+    }
+
+    fn step(&mut self, keyword: &str, line: usize) -> Result<Step, ProgramError> {
+        if keyword == "repeat" {
+            let count = match self.next() {
+                (Token::Word(count), _) => count,
+                _ => return Err(ProgramError::new(line, "`repeat` needs a count")),
+            };
+            let repeat = count
+                .parse::<u32>()
+                .ok()
+                .filter(|n| (1..=MAX_REPEAT).contains(n))
+                .ok_or_else(|| ProgramError::new(line, format!("repeat count must be between 1 and {}, got `{}`", MAX_REPEAT, count)))?;
+            while *self.peek() == Token::Separator {
+                self.next();
+            }
+            if self.next().0 != Token::Open {
+                return Err(ProgramError::new(line, "expected `{` after repeat count"));
+            }
+            let steps = self.block(Some(line))?;
+            if steps.is_empty() {
+                return Err(ProgramError::new(line, "repeat block is empty"));
+            }
+            return Ok(Step::Repeat { repeat, steps });
+        }
+
+        let phase = parse_phase(keyword).ok_or_else(|| {
+            ProgramError::new(
+                line,
+                format!("unknown step `{}`, expected focus, break, long_break, forced_break, micro_break or repeat", keyword),
+            )
+        })?;
+        let duration = match self.next() {
+            (Token::Word(text), _) => parse_duration(&text).ok_or_else(|| {
+                ProgramError::new(line, format!("invalid duration `{}`, expected e.g. 25m, 90s or 1h30m", text))
+            })?,
+            _ => return Err(ProgramError::new(line, format!("`{}` needs a duration", keyword))),
+        };
+        Ok(Step::Phase { phase, duration })
+    }
+}
+
+fn parse_phase(keyword: &str) -> Option<TimerPhase> {
+    match keyword {
+        "focus" => Some(TimerPhase::Focus),
+        "break" => Some(TimerPhase::Break),
+        "long_break" => Some(TimerPhase::LongBreak),
+        "forced_break" => Some(TimerPhase::ForcedBreak),
+        "micro_break" => Some(TimerPhase::MicroBreak),
+        _ => None,
+    }
+}
+
+// `1h30m`、`25m`、`90s` 等，至少一段，结果须大于 0
+fn parse_duration(text: &str) -> Option<u32> {
+    let mut total: u32 = 0;
+    let mut number = String::new();
+    for ch in text.chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        let unit = match ch {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        let value: u32 = std::mem::take(&mut number).parse().ok()?;
+        total = total.checked_add(value.checked_mul(unit)?)?;
+    }
+    (number.is_empty() && total > 0).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_expands_repeats() {
+        let program = Program::parse("repeat 4 { focus 25m; break 5m }; long_break 20m").unwrap();
+        let blocks = program.expand();
+        assert_eq!(blocks.len(), 9);
+        assert_eq!(blocks[0], Transition { phase: TimerPhase::Focus, duration: 1500 });
+        assert_eq!(blocks[8], Transition { phase: TimerPhase::LongBreak, duration: 1200 });
+        assert_eq!(program.total_duration(), 4 * 1800 + 1200);
+
+        let nested = Program::parse("# 上午\nrepeat 2 {\n  repeat 2 { focus 1h30m\n break 90s }\n}\n").unwrap();
+        assert_eq!(nested.expand().len(), 8);
+        assert_eq!(nested.expand()[0].duration, 5400);
+        assert_eq!(Program::from_json(&nested.to_json()), Ok(nested));
+    }
+
+    #[test]
+    fn reports_errors_with_line_numbers() {
+        let error = |source: &str| Program::parse(source).unwrap_err();
+        assert_eq!(error("focus 25m\nnap 5m").line, 2);
+        assert_eq!(error("focus 25m\nbreak 5x").to_string(), "line 2: invalid duration `5x`, expected e.g. 25m, 90s or 1h30m");
+        assert_eq!(error("repeat 2 {\n focus 25m\n").to_string(), "line 2: missing `}` for block opened on line 1");
+        assert_eq!(error("focus 25m break 5m").line, 1);
+        assert_eq!(error("\n\nrepeat 0 { focus 1m }").line, 3);
+        assert_eq!(error("break 5m").message, "program needs at least one focus step");
+        assert_eq!(error("repeat 1000 { repeat 1000 { focus 1m } }").message, "program expands to more than 10000 steps");
+        assert!(Program::from_json(r#"[{"phase": "focus", "duration": 0}]"#).is_err());
+    }
+
+    #[test]
+    fn presets_are_valid() {
+        for (name, _) in PRESETS {
+            assert!(Program::preset(name).is_some());
+        }
+        let pomodoro = Program::preset("Pomodoro").unwrap().expand();
+        assert_eq!(pomodoro.len(), 8);
+        assert_eq!(pomodoro[7].phase, TimerPhase::LongBreak);
+        assert_eq!(Program::preset("52-17").unwrap().total_duration(), 69 * 60);
+    }
+}
@@ -0,0 +1,155 @@
+// 多计时器注册表
+// 按名称管理相互独立的计时器（如与主专注循环并行的喝水提醒、单个任务的计时），一次 update_all 全部推进
+
+use super::clock::Clock;
+use super::events::TimerEvent;
+use super::timer::{Calculation, Timer, TimerPhase};
+
+/// 共享同一时钟的一组具名计时器，按加入顺序排列
+pub struct TimerRegistry<C: Clock + Clone> {
+    clock: C,
+    timers: Vec<(String, Timer<C>)>,
+}
+
+impl<C: Clock + Clone> TimerRegistry<C> {
+    pub fn new(clock: C) -> TimerRegistry<C> {
+        TimerRegistry {
+            clock,
+            timers: Vec::new(),
+        }
+    }
+
+    /// 以 `name` 开始一个新计时器；同名计时器已存在时被替换，位置不变
+    pub fn start(&mut self, name: &str, duration: u32, state: TimerPhase) {
+        let timer = Timer::new(self.clock.clone(), duration, state);
+        match self.position(name) {
+            Some(index) => self.timers[index].1 = timer,
+            None => self.timers.push((name.to_string(), timer)),
+        }
+    }
+
+    /// 暂停指定计时器，返回剩余秒数；不存在时返回 None
+    pub fn pause(&mut self, name: &str) -> Option<u32> {
+        self.get_mut(name).map(|timer| timer.pause())
+    }
+
+    pub fn resume(&mut self, name: &str) -> bool {
+        self.get_mut(name).map(|timer| timer.resume()).is_some()
+    }
+
+    pub fn reset(&mut self, name: &str, duration: u32, state: TimerPhase) -> bool {
+        self.get_mut(name).map(|timer| timer.reset(duration, state)).is_some()
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(index) => {
+                self.timers.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.timers.clear();
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Timer<C>> {
+        self.timers.iter().find(|(n, _)| n == name).map(|(_, timer)| timer)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Timer<C>> {
+        self.timers.iter_mut().find(|(n, _)| n == name).map(|(_, timer)| timer)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.timers.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// 推进全部计时器，按加入顺序返回各自的计算结果
+    pub fn update_all(&mut self) -> Vec<(String, Calculation)> {
+        self.timers
+            .iter_mut()
+            .map(|(name, timer)| (name.clone(), timer.update()))
+            .collect()
+    }
+
+    /// 取走全部计时器尚未处理的事件，附带所属计时器名称
+    pub fn drain_events(&mut self) -> Vec<(String, TimerEvent)> {
+        self.timers
+            .iter_mut()
+            .flat_map(|(name, timer)| {
+                let name = name.clone();
+                timer.drain_events().into_iter().map(move |event| (name.clone(), event))
+            })
+            .collect()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.timers.iter().position(|(n, _)| n == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer_core::clock::FakeClock;
+    use crate::timer_core::timer::RunStatus;
+
+    #[test]
+    fn timers_advance_independently() {
+        let clock = FakeClock::new(0);
+        let mut registry = TimerRegistry::new(clock.clone());
+        registry.start("focus", 1500, TimerPhase::Focus);
+        clock.advance(60_000);
+        registry.start("hydration", 1200, TimerPhase::Break);
+        registry.pause("focus");
+        clock.advance(30_000);
+
+        let calcs = registry.update_all();
+        assert_eq!(registry.names(), vec!["focus", "hydration"]);
+        assert_eq!(calcs[0].1.remaining, 1440);
+        assert_eq!(calcs[0].1.status, RunStatus::Paused);
+        assert_eq!(calcs[1].1.remaining, 1170);
+        assert_eq!(calcs[1].1.state, TimerPhase::Break);
+
+        assert!(registry.resume("focus"));
+        assert!(!registry.resume("missing"));
+        assert_eq!(registry.pause("missing"), None);
+    }
+
+    #[test]
+    fn restart_replaces_in_place_and_events_carry_names() {
+        let clock = FakeClock::new(0);
+        let mut registry = TimerRegistry::new(clock.clone());
+        registry.start("a", 10, TimerPhase::Focus);
+        registry.start("b", 60, TimerPhase::Focus);
+        registry.drain_events();
+
+        clock.advance(10_000);
+        registry.update_all();
+        assert_eq!(registry.drain_events(), vec![
+            ("a".to_string(), TimerEvent::Completed { state: TimerPhase::Focus }),
+        ]);
+        registry.start("a", 30, TimerPhase::Break);
+        assert_eq!(registry.names(), vec!["a", "b"]);
+        assert_eq!(registry.get("a").map(|t| t.duration()), Some(30));
+
+        assert!(registry.remove("a"));
+        assert!(!registry.contains("a"));
+        assert_eq!(registry.len(), 1);
+    }
+}
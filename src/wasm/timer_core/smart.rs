@@ -0,0 +1,312 @@
+// 智能模式调度器
+// 与 src/services/smartTimer.ts 相同的规则：自适应调整、生理节律优化与强制休息
+
+use serde::{Deserialize, Serialize};
+
+use super::cycle::Transition;
+use super::timer::TimerPhase;
+
+// 强制休息的最短时长（分钟）
+const MIN_FORCED_BREAK_MINUTES: f64 = 30.0;
+// 自适应调整保留的评分数量与最少评分数量
+const MAX_RECENT_SCORES: usize = 10;
+const MIN_SCORES_FOR_ADJUSTMENT: usize = 3;
+// 两次自适应调整的最短间隔
+const ADJUSTMENT_INTERVAL_MS: u64 = 24 * 60 * 60 * 1000;
+
+/// 智能模式设置，字段与 TS 端 `SmartTimerSettings` 一一对应，时长单位为分钟
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SmartSettings {
+    pub focus_duration: u32,
+    pub break_duration: u32,
+    pub enable_micro_breaks: bool,
+    pub micro_break_min_interval: u32,
+    pub micro_break_max_interval: u32,
+    pub micro_break_min_duration: u32,
+    pub micro_break_max_duration: u32,
+    pub enable_adaptive_adjustment: bool,
+    // 与 TS 端一样只保存不参与计算，自适应调整只看 `SmartState` 中的倍率
+    pub adaptive_factor_focus: f64,
+    pub adaptive_factor_break: f64,
+    pub enable_circadian_optimization: bool,
+    pub peak_focus_hours: Vec<u8>,
+    pub low_energy_hours: Vec<u8>,
+    pub max_continuous_focus_time: u32,
+    pub forced_break_threshold: u32,
+}
+
+impl Default for SmartSettings {
+    fn default() -> Self {
+        SmartSettings {
+            focus_duration: 90,
+            break_duration: 20,
+            enable_micro_breaks: true,
+            micro_break_min_interval: 10,
+            micro_break_max_interval: 30,
+            micro_break_min_duration: 3,
+            micro_break_max_duration: 5,
+            enable_adaptive_adjustment: true,
+            adaptive_factor_focus: 1.0,
+            adaptive_factor_break: 1.0,
+            enable_circadian_optimization: true,
+            peak_focus_hours: vec![9, 10, 11, 14, 15, 16],
+            low_energy_hours: vec![13, 14, 22, 23, 0, 1],
+            max_continuous_focus_time: 120,
+            forced_break_threshold: 150,
+        }
+    }
+}
+
+/// 调度器随会话积累的状态
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SmartState {
+    pub continuous_focus_minutes: u32,
+    pub today_focus_minutes: u32,
+    pub focus_multiplier: f64,
+    pub break_multiplier: f64,
+    pub last_adjustment_ms: u64,
+    pub recent_scores: Vec<f64>,
+}
+
+impl Default for SmartState {
+    fn default() -> Self {
+        SmartState {
+            continuous_focus_minutes: 0,
+            today_focus_minutes: 0,
+            focus_multiplier: 1.0,
+            break_multiplier: 1.0,
+            last_adjustment_ms: 0,
+            recent_scores: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SmartScheduler {
+    settings: SmartSettings,
+    state: SmartState,
+}
+
+impl SmartScheduler {
+    pub fn new(settings: SmartSettings) -> SmartScheduler {
+        SmartScheduler {
+            settings,
+            state: SmartState::default(),
+        }
+    }
+
+    pub fn with_state(settings: SmartSettings, state: SmartState) -> SmartScheduler {
+        SmartScheduler { settings, state }
+    }
+
+    pub fn settings(&self) -> &SmartSettings {
+        &self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: SmartSettings) {
+        self.settings = settings;
+    }
+
+    pub fn state(&self) -> &SmartState {
+        &self.state
+    }
+
+    /// 根据连续专注时间决定下一阶段
+    pub fn next_phase(&self, current: TimerPhase) -> TimerPhase {
+        let continuous = self.state.continuous_focus_minutes;
+
+        // 检查是否需要强制休息
+        if continuous >= self.settings.forced_break_threshold {
+            return TimerPhase::ForcedBreak;
+        }
+
+        match current {
+            TimerPhase::Focus if continuous >= self.settings.max_continuous_focus_time => TimerPhase::ForcedBreak,
+            TimerPhase::Focus => TimerPhase::Break,
+            TimerPhase::Break
+            | TimerPhase::LongBreak
+            | TimerPhase::ForcedBreak
+            | TimerPhase::MicroBreak
+            | TimerPhase::Idle => TimerPhase::Focus,
+        }
+    }
+
+    /// 指定阶段在给定小时（0-23）开始时的时长（秒）
+    pub fn duration_for(&self, phase: TimerPhase, hour: u8) -> u32 {
+        let minutes = match phase {
+            TimerPhase::Focus => self.adjusted_focus_minutes(hour),
+            // 智能模式没有单独的长休息设置，按普通休息处理
+            TimerPhase::Break | TimerPhase::LongBreak => self.adjusted_break_minutes(),
+            TimerPhase::ForcedBreak => self.adjusted_break_minutes().max(MIN_FORCED_BREAK_MINUTES),
+            TimerPhase::MicroBreak => {
+                (self.settings.micro_break_min_duration + self.settings.micro_break_max_duration) as f64 / 2.0
+            }
+            TimerPhase::Idle => 0.0,
+        };
+        (minutes * 60.0).round() as u32
+    }
+
+    /// 记录刚完成的阶段，返回下一阶段及其时长。
+    /// 与 TS 端按 `totalTime` 统计一致，`planned_secs` 为该阶段的计划时长而非实际用时
+    pub fn complete(&mut self, completed: TimerPhase, planned_secs: u32, hour: u8) -> Transition {
+        let minutes = (planned_secs as f64 / 60.0).round() as u32;
+        match completed {
+            TimerPhase::Focus => {
+                self.state.continuous_focus_minutes += minutes;
+                self.state.today_focus_minutes += minutes;
+            }
+            // 休息重置连续专注时间，微休息不重置
+            phase if phase.is_rest() => self.state.continuous_focus_minutes = 0,
+            _ => {}
+        }
+
+        let phase = self.next_phase(completed);
+        Transition {
+            phase,
+            duration: self.duration_for(phase, hour),
+        }
+    }
+
+    /// 提交效率评分（1-5），满足条件时每天最多做一次自适应调整
+    pub fn submit_efficiency_score(&mut self, score: f64, now_ms: u64) {
+        self.state.recent_scores.push(score);
+        if self.state.recent_scores.len() > MAX_RECENT_SCORES {
+            self.state.recent_scores.remove(0);
+        }
+
+        if self.settings.enable_adaptive_adjustment {
+            self.perform_adaptive_adjustment(now_ms);
+        }
+    }
+
+    /// 新的一天开始时清零当日统计
+    pub fn reset_daily(&mut self) {
+        self.state.today_focus_minutes = 0;
+        self.state.continuous_focus_minutes = 0;
+    }
+
+    fn perform_adaptive_adjustment(&mut self, now_ms: u64) {
+        let scores = &self.state.recent_scores;
+        if scores.len() < MIN_SCORES_FOR_ADJUSTMENT { return; }
+        if now_ms.saturating_sub(self.state.last_adjustment_ms) < ADJUSTMENT_INTERVAL_MS { return; }
+
+        let avg = scores.iter().sum::<f64>() / scores.len() as f64;
+        if avg >= 4.0 {
+            // 高效率，可以适当延长专注时间
+            self.state.focus_multiplier = (self.state.focus_multiplier + 0.05).min(1.2);
+            self.state.break_multiplier = (self.state.break_multiplier - 0.02).max(0.8);
+        } else if avg <= 2.0 {
+            // 低效率，缩短专注时间，延长休息时间
+            self.state.focus_multiplier = (self.state.focus_multiplier - 0.05).max(0.8);
+            self.state.break_multiplier = (self.state.break_multiplier + 0.05).min(1.2);
+        }
+        self.state.last_adjustment_ms = now_ms;
+    }
+
+    fn adjusted_focus_minutes(&self, hour: u8) -> f64 {
+        let mut minutes = self.settings.focus_duration as f64;
+        if self.settings.enable_adaptive_adjustment {
+            minutes *= self.state.focus_multiplier;
+        }
+        if self.settings.enable_circadian_optimization {
+            minutes *= self.circadian_multiplier(hour);
+        }
+        minutes.round()
+    }
+
+    fn adjusted_break_minutes(&self) -> f64 {
+        let mut minutes = self.settings.break_duration as f64;
+        if self.settings.enable_adaptive_adjustment {
+            minutes *= self.state.break_multiplier;
+        }
+        minutes.round()
+    }
+
+    fn circadian_multiplier(&self, hour: u8) -> f64 {
+        if self.settings.peak_focus_hours.contains(&hour) {
+            1.1 // 高峰期延长10%
+        } else if self.settings.low_energy_hours.contains(&hour) {
+            0.9 // 低能量期缩短10%
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circadian_and_adaptive_adjust_focus() {
+        let scheduler = SmartScheduler::new(SmartSettings::default());
+        assert_eq!(scheduler.duration_for(TimerPhase::Focus, 10), 99 * 60);
+        assert_eq!(scheduler.duration_for(TimerPhase::Focus, 23), 81 * 60);
+        assert_eq!(scheduler.duration_for(TimerPhase::Focus, 19), 90 * 60);
+        assert_eq!(scheduler.duration_for(TimerPhase::ForcedBreak, 19), 30 * 60);
+    }
+
+    #[test]
+    fn continuous_focus_triggers_forced_break() {
+        let mut scheduler = SmartScheduler::new(SmartSettings::default());
+        let next = scheduler.complete(TimerPhase::Focus, 90 * 60, 19);
+        assert_eq!(next, Transition { phase: TimerPhase::Break, duration: 20 * 60 });
+
+        // 微休息不重置连续专注时间
+        scheduler.complete(TimerPhase::MicroBreak, 180, 19);
+        let next = scheduler.complete(TimerPhase::Focus, 40 * 60, 19);
+        assert_eq!(next.phase, TimerPhase::ForcedBreak);
+
+        let next = scheduler.complete(TimerPhase::ForcedBreak, 30 * 60, 19);
+        assert_eq!(next.phase, TimerPhase::Focus);
+        assert_eq!(scheduler.state().continuous_focus_minutes, 0);
+        assert_eq!(scheduler.state().today_focus_minutes, 130);
+    }
+
+    #[test]
+    fn adaptive_adjustment_runs_at_most_daily() {
+        let mut scheduler = SmartScheduler::new(SmartSettings::default());
+        let day = ADJUSTMENT_INTERVAL_MS;
+        for _ in 0..3 {
+            scheduler.submit_efficiency_score(5.0, day);
+        }
+        assert!((scheduler.state().focus_multiplier - 1.05).abs() < 1e-9);
+
+        scheduler.submit_efficiency_score(5.0, day + 1000);
+        assert!((scheduler.state().focus_multiplier - 1.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn matches_ts_formulas_with_non_default_adaptive_factors() {
+        let settings = SmartSettings {
+            adaptive_factor_focus: 1.2,
+            adaptive_factor_break: 0.8,
+            ..SmartSettings::default()
+        };
+        let state = SmartState {
+            focus_multiplier: 1.05,
+            break_multiplier: 0.98,
+            ..SmartState::default()
+        };
+        let mut scheduler = SmartScheduler::with_state(settings, state);
+
+        // smartTimer.ts：Math.round(90 * 1.05 * 1.1) 与 Math.round(20 * 0.98)，调整因子不参与
+        assert_eq!(scheduler.duration_for(TimerPhase::Focus, 10), 104 * 60);
+        assert_eq!(scheduler.duration_for(TimerPhase::Break, 10), 20 * 60);
+
+        // 统计按计划时长 Math.round(totalTime / 60)
+        scheduler.complete(TimerPhase::Focus, 104 * 60, 10);
+        assert_eq!(scheduler.state().continuous_focus_minutes, 104);
+    }
+
+    #[test]
+    fn settings_parse_from_ts_json() {
+        let settings: SmartSettings =
+            serde_json::from_str(r#"{"focusDuration":52,"forcedBreakThreshold":100}"#).unwrap();
+        assert_eq!(settings.focus_duration, 52);
+        assert_eq!(settings.forced_break_threshold, 100);
+        assert_eq!(settings.break_duration, 20);
+    }
+}
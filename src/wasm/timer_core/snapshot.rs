@@ -0,0 +1,171 @@
+// 计时器快照
+// 把运行中的计时器导出为带版本号的 JSON，webview 重载、应用重启或在 wasm 与原生之间迁移后可原样恢复
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::clock::GapPolicy;
+use super::cycle::{CyclePolicy, Transition};
+use super::flow::{CountMode, FlowPolicy};
+use super::micro_break::MicroBreak;
+use super::overtime::OvertimePolicy;
+use super::timer::TimerPhase;
+
+/// 当前快照格式版本；字段含义变化时递增，并在 `from_json` 中处理旧版本
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// 计时器在某一时刻的完整状态。时间点均为墙上时间（Unix 毫秒），
+/// 单调时钟读数在进程之间没有意义，不写入快照
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerSnapshot {
+    pub version: u32,
+    // 拍摄快照时的墙上时间
+    pub taken_at: u64,
+    pub state: TimerPhase,
+    // 阶段时长（秒）
+    pub duration: u32,
+    // 截至拍摄时已计时的毫秒数，不含暂停时间
+    pub elapsed_ms: u64,
+    pub paused: bool,
+    pub paused_ms: u64,
+    // 本阶段开始时的墙上时间
+    pub started_at: u64,
+    pub policy: CyclePolicy,
+    #[serde(default)]
+    pub gap_policy: GapPolicy,
+    pub completed_focus: u32,
+    pub continuous_focus: u32,
+    pub completion_emitted: bool,
+    pub forced_break_emitted: bool,
+    #[serde(default)]
+    pub micro_breaks: Vec<MicroBreak>,
+    #[serde(default)]
+    pub next_micro_break: usize,
+    #[serde(default)]
+    pub count_mode: CountMode,
+    #[serde(default)]
+    pub flow: FlowPolicy,
+    #[serde(default)]
+    pub overtime: OvertimePolicy,
+    #[serde(default)]
+    pub overtime_reminders: u32,
+    // 展开后的计时程序，空表示按循环策略切换
+    #[serde(default)]
+    pub program: Vec<Transition>,
+    #[serde(default)]
+    pub program_position: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// 不是合法的快照 JSON
+    Parse(String),
+    /// 快照由更新版本的程序生成，无法安全解读
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Parse(message) => write!(f, "invalid timer snapshot: {}", message),
+            SnapshotError::UnsupportedVersion(version) => write!(
+                f,
+                "timer snapshot version {} is newer than supported version {}",
+                version, SNAPSHOT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+// 先只读版本号，避免用当前结构去解析未知版本时报出误导性的字段错误
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: u32,
+}
+
+impl TimerSnapshot {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("timer snapshot is always serializable")
+    }
+
+    pub fn from_json(json: &str) -> Result<TimerSnapshot, SnapshotError> {
+        let probe: VersionProbe =
+            serde_json::from_str(json).map_err(|e| SnapshotError::Parse(e.to_string()))?;
+        if probe.version == 0 || probe.version > SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(probe.version));
+        }
+        serde_json::from_str(json).map_err(|e| SnapshotError::Parse(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer_core::clock::FakeClock;
+    use crate::timer_core::timer::{RunStatus, Timer};
+
+    const START: u64 = 1_700_000_000_000;
+
+    #[test]
+    fn running_timer_survives_reload() {
+        let clock = FakeClock::new(START);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_policy(CyclePolicy::pomodoro());
+        clock.advance(600_000);
+        let json = timer.snapshot().to_json();
+
+        // 新进程的单调时钟从别的位置开始，只有墙上时间是连续的
+        let reloaded = FakeClock::new(5_000);
+        reloaded.jump_wall(START as i64 + 600_000 + 30_000 - 5_000);
+        let snapshot = TimerSnapshot::from_json(&json).unwrap();
+        let mut restored = Timer::restore(reloaded.clone(), snapshot);
+
+        let calc = restored.update();
+        assert_eq!(calc.elapsed_ms, 630_000);
+        assert_eq!(calc.started_at, START);
+        assert_eq!(calc.state, TimerPhase::Focus);
+        assert_eq!(restored.policy(), CyclePolicy::pomodoro());
+        assert_eq!(calc.clock_event, None);
+
+        reloaded.advance(10_000);
+        assert_eq!(restored.update().elapsed_ms, 640_000);
+    }
+
+    #[test]
+    fn paused_timer_stays_paused() {
+        let clock = FakeClock::new(START);
+        let mut timer = Timer::new(clock.clone(), 300, TimerPhase::Break);
+        clock.advance(100_000);
+        timer.pause();
+        clock.advance(20_000);
+        let snapshot = timer.snapshot();
+        assert!(snapshot.paused);
+
+        clock.advance(40_000);
+        let mut restored = Timer::restore(clock.clone(), snapshot);
+        let calc = restored.update();
+        assert_eq!(calc.status, RunStatus::Paused);
+        assert_eq!(calc.elapsed_ms, 100_000);
+        assert_eq!(calc.paused_time, 60);
+
+        restored.resume();
+        clock.advance(5_000);
+        assert_eq!(restored.update().remaining, 195);
+    }
+
+    #[test]
+    fn rejects_newer_or_malformed_snapshots() {
+        let clock = FakeClock::new(START);
+        let mut snapshot = Timer::new(clock, 60, TimerPhase::Focus).snapshot();
+        snapshot.version = SNAPSHOT_VERSION + 1;
+        assert_eq!(
+            TimerSnapshot::from_json(&snapshot.to_json()),
+            Err(SnapshotError::UnsupportedVersion(SNAPSHOT_VERSION + 1))
+        );
+        assert!(matches!(TimerSnapshot::from_json("{}"), Err(SnapshotError::Parse(_))));
+    }
+}
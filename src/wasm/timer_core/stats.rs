@@ -0,0 +1,348 @@
+// 统计聚合
+// 以列的形式接收会话数据（每列一个数组），一次遍历得到日/周/月汇总、连续天数、最佳时段和年度热力图
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::calendar::{civil_from_days, DayCalendar, TimeZone};
+
+pub const HEATMAP_DAYS: usize = 365;
+
+/// 会话数据列，各列长度必须相同。时长单位由调用方决定（通常为秒），聚合结果沿用同一单位
+#[derive(Clone, Copy, Debug)]
+pub struct SessionColumns<'a> {
+    // 会话开始与结束时间（Unix 毫秒）。`ended_at` 为空时不拆分跨日会话，全部计入开始的那天
+    pub started_at: &'a [f64],
+    pub ended_at: &'a [f64],
+    pub focus: &'a [u32],
+    pub break_time: &'a [u32],
+    pub micro_breaks: &'a [u32],
+    pub efficiency: &'a [f64],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StatsError {
+    /// 各列长度不一致
+    ColumnLengthMismatch { expected: usize, column: &'static str, found: usize },
+}
+
+impl fmt::Display for StatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatsError::ColumnLengthMismatch { expected, column, found } => write!(
+                f,
+                "column `{}` has {} rows, expected {}",
+                column, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StatsError {}
+
+/// 一个日/周/月的汇总。`key` 对日为自 1970-01-01 起的本地天数，
+/// 对周为该周周一的天数，对月为 `年 * 100 + 月`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PeriodTotals {
+    pub key: i64,
+    pub focus: u64,
+    pub break_time: u64,
+    pub micro_breaks: u64,
+    pub sessions: u32,
+    pub average_efficiency: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StatsReport {
+    pub daily: Vec<PeriodTotals>,
+    pub weekly: Vec<PeriodTotals>,
+    pub monthly: Vec<PeriodTotals>,
+    pub total_focus: u64,
+    pub total_sessions: u32,
+    // 有专注记录的日子平均每天的专注时长，以及平均每次会话的专注时长
+    pub average_daily_focus: f64,
+    pub average_session_focus: f64,
+    pub average_efficiency: f64,
+    // 截至今天（今天尚无记录时截至昨天）的连续专注天数，以及历史最长连续天数
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    // 按会话开始的本地小时累计的专注时长，及其中最多的小时
+    pub hourly_focus: [u64; 24],
+    pub best_hour: Option<u8>,
+    // 截至今天的 365 天每日专注时长，最早的一天在前；
+    // 排成按周分列的矩阵时，第一格是星期 `heatmap_start_weekday`（0 为周一）
+    pub heatmap: Vec<u64>,
+    pub heatmap_start_weekday: u8,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Accum {
+    focus: u64,
+    break_time: u64,
+    micro_breaks: u64,
+    sessions: u32,
+    efficiency_sum: f64,
+}
+
+impl Accum {
+    fn add_time(&mut self, focus: u64, break_time: u64, micro_breaks: u64) {
+        self.focus += focus;
+        self.break_time += break_time;
+        self.micro_breaks += micro_breaks;
+    }
+
+    fn add_session(&mut self, efficiency: f64) {
+        self.sessions += 1;
+        self.efficiency_sum += efficiency;
+    }
+
+    fn totals(&self, key: i64) -> PeriodTotals {
+        PeriodTotals {
+            key,
+            focus: self.focus,
+            break_time: self.break_time,
+            micro_breaks: self.micro_breaks,
+            sessions: self.sessions,
+            average_efficiency: if self.sessions == 0 { 0.0 } else { self.efficiency_sum / self.sessions as f64 },
+        }
+    }
+}
+
+/// 聚合全部会话。`now_ms` 决定"今天"，日期按 `calendar` 划分。
+/// 跨越日界的会话，其专注、休息时长和微休息次数按落在各天的实际时长拆分；会话数和效率计入开始的那天
+pub fn aggregate<Z: TimeZone>(columns: &SessionColumns, now_ms: f64, calendar: &DayCalendar<Z>) -> Result<StatsReport, StatsError> {
+    let rows = columns.started_at.len();
+    if !columns.ended_at.is_empty() {
+        check_len(rows, "endedAt", columns.ended_at.len())?;
+    }
+    check_len(rows, "focus", columns.focus.len())?;
+    check_len(rows, "breakTime", columns.break_time.len())?;
+    check_len(rows, "microBreaks", columns.micro_breaks.len())?;
+    check_len(rows, "efficiency", columns.efficiency.len())?;
+
+    let mut days: BTreeMap<i64, Accum> = BTreeMap::new();
+    let mut hourly_focus = [0u64; 24];
+    let mut all = Accum::default();
+
+    for i in 0..rows {
+        let start = columns.started_at[i] as i64;
+        let day = calendar.day_of(start);
+        let (focus, break_time, micro_breaks) =
+            (columns.focus[i] as u64, columns.break_time[i] as u64, columns.micro_breaks[i] as u64);
+
+        days.entry(day).or_default().add_session(columns.efficiency[i]);
+        all.add_session(columns.efficiency[i]);
+        all.add_time(focus, break_time, micro_breaks);
+        hourly_focus[calendar.local_hour(start) as usize] += focus;
+
+        let pieces = match columns.ended_at.get(i) {
+            Some(&end) => calendar.split(start, end as i64),
+            None => vec![0],
+        };
+        let span: i64 = pieces.iter().sum();
+        if pieces.len() == 1 || span <= 0 {
+            days.entry(day).or_default().add_time(focus, break_time, micro_breaks);
+            continue;
+        }
+        // 按累计比例取整，保证各天之和等于原值
+        let share = |total: u64, upto: i64| (total as f64 * upto as f64 / span as f64).round() as u64;
+        let mut elapsed = 0;
+        let mut assigned = (0, 0, 0);
+        for (offset, piece) in pieces.iter().enumerate() {
+            elapsed += piece;
+            let upto = (share(focus, elapsed), share(break_time, elapsed), share(micro_breaks, elapsed));
+            days.entry(day + offset as i64).or_default().add_time(upto.0 - assigned.0, upto.1 - assigned.1, upto.2 - assigned.2);
+            assigned = upto;
+        }
+    }
+
+    // 周、月由日汇总合并而来，效率按会话数加权
+    let mut weeks: BTreeMap<i64, Accum> = BTreeMap::new();
+    let mut months: BTreeMap<i64, Accum> = BTreeMap::new();
+    for (&day, acc) in &days {
+        for (map, key) in [(&mut weeks, week_start(day)), (&mut months, month_key(day))] {
+            let target = map.entry(key).or_default();
+            target.focus += acc.focus;
+            target.break_time += acc.break_time;
+            target.micro_breaks += acc.micro_breaks;
+            target.sessions += acc.sessions;
+            target.efficiency_sum += acc.efficiency_sum;
+        }
+    }
+
+    let today = calendar.day_of(now_ms as i64);
+    let active_days: Vec<i64> = days.iter().filter(|(_, a)| a.focus > 0).map(|(&d, _)| d).collect();
+    let (current_streak, longest_streak) = streaks(&active_days, today);
+
+    let first_day = today - HEATMAP_DAYS as i64 + 1;
+    let heatmap = (first_day..=today).map(|d| days.get(&d).map_or(0, |a| a.focus)).collect();
+
+    let best_hour = hourly_focus
+        .iter()
+        .enumerate()
+        .filter(|(_, &focus)| focus > 0)
+        .max_by_key(|(_, &focus)| focus)
+        .map(|(hour, _)| hour as u8);
+
+    Ok(StatsReport {
+        daily: days.iter().map(|(&k, a)| a.totals(k)).collect(),
+        weekly: weeks.iter().map(|(&k, a)| a.totals(k)).collect(),
+        monthly: months.iter().map(|(&k, a)| a.totals(k)).collect(),
+        total_focus: all.focus,
+        total_sessions: all.sessions,
+        average_daily_focus: if active_days.is_empty() { 0.0 } else { all.focus as f64 / active_days.len() as f64 },
+        average_session_focus: if all.sessions == 0 { 0.0 } else { all.focus as f64 / all.sessions as f64 },
+        average_efficiency: all.totals(0).average_efficiency,
+        current_streak,
+        longest_streak,
+        hourly_focus,
+        best_hour,
+        heatmap,
+        heatmap_start_weekday: weekday(first_day),
+    })
+}
+
+fn check_len(expected: usize, column: &'static str, found: usize) -> Result<(), StatsError> {
+    if expected == found { Ok(()) } else { Err(StatsError::ColumnLengthMismatch { expected, column, found }) }
+}
+
+// active_days 升序且不重复
+fn streaks(active_days: &[i64], today: i64) -> (u32, u32) {
+    let mut longest = 0;
+    let mut run = 0;
+    let mut prev: Option<i64> = None;
+    for &day in active_days {
+        run = if prev == Some(day - 1) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        prev = Some(day);
+    }
+    // 最后一段连续记录必须延续到今天或昨天才算当前连续
+    let current = match prev {
+        Some(last) if last == today || last == today - 1 => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+/// 星期几，0 为周一（1970-01-01 是周四）
+pub fn weekday(day: i64) -> u8 {
+    (day + 3).rem_euclid(7) as u8
+}
+
+/// 所在周周一的天数
+pub fn week_start(day: i64) -> i64 {
+    day - weekday(day) as i64
+}
+
+/// `年 * 100 + 月`
+pub fn month_key(day: i64) -> i64 {
+    let (year, month, _) = civil_from_days(day);
+    year * 100 + month as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer_core::calendar::FixedOffset;
+
+    const DAY: f64 = 86_400_000.0;
+    const HOUR: f64 = 3_600_000.0;
+    // 2024-01-15（周一）00:00 UTC
+    const MONDAY: f64 = 1_705_276_800_000.0;
+
+    fn report(started_at: &[f64], focus: &[u32], now: f64) -> StatsReport {
+        let n = started_at.len();
+        let columns = SessionColumns {
+            started_at,
+            ended_at: &[],
+            focus,
+            break_time: &vec![300; n],
+            micro_breaks: &vec![1; n],
+            efficiency: &(0..n).map(|i| 60.0 + i as f64 * 10.0).collect::<Vec<_>>(),
+        };
+        aggregate(&columns, now, &DayCalendar::new(FixedOffset(0), 0)).unwrap()
+    }
+
+    #[test]
+    fn calendar_helpers() {
+        assert_eq!(month_key(19_782), 202402);
+        assert_eq!(weekday(19_737), 0);
+        assert_eq!(week_start(19_743), 19_737);
+    }
+
+    #[test]
+    fn totals_streaks_and_best_hour() {
+        let hour = HOUR;
+        let started = [
+            MONDAY - 20.0 * DAY + 9.0 * hour,
+            MONDAY - DAY + 9.0 * hour,
+            MONDAY + 10.0 * hour,
+            MONDAY + 14.0 * hour,
+            MONDAY + DAY + 10.0 * hour,
+        ];
+        let focus = [1500, 1500, 3000, 1500, 1500];
+        let stats = report(&started, &focus, MONDAY + DAY + 20.0 * hour);
+
+        assert_eq!(stats.total_focus, 9000);
+        assert_eq!(stats.total_sessions, 5);
+        assert_eq!(stats.daily.len(), 4);
+        assert_eq!(stats.daily[2].focus, 4500);
+        assert_eq!(stats.daily[2].sessions, 2);
+        assert!((stats.daily[2].average_efficiency - 85.0).abs() < 1e-9);
+        assert_eq!(stats.weekly.last().map(|w| (w.key, w.focus)), Some((19_737, 6000)));
+        assert_eq!(stats.monthly.iter().map(|m| m.key).collect::<Vec<_>>(), vec![202312, 202401]);
+        assert_eq!((stats.current_streak, stats.longest_streak), (3, 3));
+        assert_eq!(stats.best_hour, Some(10));
+        assert!((stats.average_daily_focus - 2250.0).abs() < 1e-9);
+        assert!((stats.average_session_focus - 1800.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn heatmap_covers_a_year_ending_today() {
+        let stats = report(&[MONDAY - 364.0 * DAY, MONDAY - 365.0 * DAY, MONDAY], &[60, 60, 120], MONDAY + 1000.0);
+        assert_eq!(stats.heatmap.len(), HEATMAP_DAYS);
+        assert_eq!(stats.heatmap[0], 60);
+        assert_eq!(stats.heatmap[HEATMAP_DAYS - 1], 120);
+        assert_eq!(stats.heatmap.iter().sum::<u64>(), 180);
+        // 364 天恰为 52 周，第一天与今天同为周一
+        assert_eq!(stats.heatmap_start_weekday, 0);
+        // 今天之前两天没有记录，当前连续天数中断
+        assert_eq!(report(&[MONDAY - 2.0 * DAY], &[60], MONDAY).current_streak, 0);
+    }
+
+    #[test]
+    fn splits_sessions_across_day_start() {
+        // 凌晨 4 点换日：03:00-05:00 的会话前后两天各一半，会话数计入开始的那天
+        let start = MONDAY + 3.0 * HOUR;
+        let columns = SessionColumns {
+            started_at: &[start],
+            ended_at: &[start + 2.0 * HOUR],
+            focus: &[6001],
+            break_time: &[600],
+            micro_breaks: &[3],
+            efficiency: &[80.0],
+        };
+        let stats = aggregate(&columns, start + 2.0 * HOUR, &DayCalendar::new(FixedOffset(0), 4 * 60)).unwrap();
+        let days: Vec<_> = stats.daily.iter().map(|d| (d.focus, d.break_time, d.micro_breaks, d.sessions)).collect();
+        assert_eq!(days, vec![(3001, 300, 2, 1), (3000, 300, 1, 0)]);
+        assert_eq!(stats.total_focus, 6001);
+        assert_eq!(stats.best_hour, Some(3));
+        assert_eq!(stats.current_streak, 2);
+    }
+
+    #[test]
+    fn rejects_mismatched_columns() {
+        let columns = SessionColumns {
+            started_at: &[0.0, 1.0],
+            ended_at: &[],
+            focus: &[1],
+            break_time: &[],
+            micro_breaks: &[],
+            efficiency: &[],
+        };
+        assert_eq!(
+            aggregate(&columns, 0.0, &DayCalendar::new(FixedOffset(0), 0)),
+            Err(StatsError::ColumnLengthMismatch { expected: 2, column: "focus", found: 1 })
+        );
+    }
+}
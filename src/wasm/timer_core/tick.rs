@@ -0,0 +1,104 @@
+// 刷新调度
+// 计算到下一次显示变化的精确延迟，让前端每次可见变化只唤醒一次，且唤醒点对齐到秒（或其他精度）边界
+
+use super::flow::CountMode;
+
+/// 显示精度：显示内容在已计时长跨过该粒度的边界时变化
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TickPrecision {
+    Tenths = 0,
+    #[default]
+    Seconds = 1,
+    Minutes = 2,
+}
+
+impl TickPrecision {
+    pub fn unit_ms(self) -> u64 {
+        match self {
+            TickPrecision::Tenths => 100,
+            TickPrecision::Seconds => 1_000,
+            TickPrecision::Minutes => 60_000,
+        }
+    }
+}
+
+/// 页面可见性。不可见时只需要更新标题、托盘等分钟级显示，以及按时触发阶段事件
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Visibility {
+    #[default]
+    Visible,
+    Hidden,
+}
+
+/// 不可见时的最细刷新粒度
+pub const HIDDEN_PRECISION: TickPrecision = TickPrecision::Minutes;
+
+/// 在给定可见性下实际使用的精度
+pub fn effective_precision(precision: TickPrecision, visibility: Visibility) -> TickPrecision {
+    match visibility {
+        Visibility::Visible => precision,
+        Visibility::Hidden => HIDDEN_PRECISION,
+    }
+}
+
+/// 已计时 `elapsed_ms` 时，到下一个 `unit_ms` 边界的毫秒数（恰在边界上时为一个完整单位）
+pub fn delay_to_boundary(elapsed_ms: u64, unit_ms: u64) -> u64 {
+    if unit_ms == 0 { return 1; }
+    unit_ms - elapsed_ms % unit_ms
+}
+
+/// 无状态版本：只考虑显示精度与阶段结束时刻。倒计时显示剩余时长，边界按剩余时长对齐；
+/// 正计时显示已计时长，按已计时长对齐。`duration_ms` 之后仍按精度继续刷新（超时或正计时）
+pub fn next_tick_delay(
+    elapsed_ms: u64,
+    duration_ms: u64,
+    precision: TickPrecision,
+    visibility: Visibility,
+    count: CountMode,
+) -> u64 {
+    let unit = effective_precision(precision, visibility).unit_ms();
+    match duration_ms.checked_sub(elapsed_ms) {
+        // 剩余时长到下一个整单位的距离，不会越过归零时刻
+        Some(remaining) if remaining > 0 && count == CountMode::Down => match remaining % unit {
+            0 => unit.min(remaining),
+            partial => partial,
+        },
+        Some(remaining) if remaining > 0 => delay_to_boundary(elapsed_ms, unit).min(remaining),
+        _ => delay_to_boundary(elapsed_ms, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOWN: CountMode = CountMode::Down;
+
+    #[test]
+    fn aligns_to_display_boundaries() {
+        assert_eq!(next_tick_delay(1_250, 60_000, TickPrecision::Seconds, Visibility::Visible, DOWN), 750);
+        assert_eq!(next_tick_delay(2_000, 60_000, TickPrecision::Seconds, Visibility::Visible, DOWN), 1_000);
+        assert_eq!(next_tick_delay(1_250, 60_000, TickPrecision::Tenths, Visibility::Visible, DOWN), 50);
+        assert_eq!(next_tick_delay(61_000, 600_000, TickPrecision::Seconds, Visibility::Hidden, DOWN), 59_000);
+    }
+
+    #[test]
+    fn wakes_exactly_at_completion() {
+        // 时长不是整分钟时，不可见状态也要在归零时唤醒
+        assert_eq!(next_tick_delay(60_000, 90_500, TickPrecision::Seconds, Visibility::Hidden, DOWN), 30_500);
+        assert_eq!(next_tick_delay(90_500, 90_500, TickPrecision::Seconds, Visibility::Visible, DOWN), 500);
+    }
+
+    #[test]
+    fn minutes_follow_the_displayed_value() {
+        // 25 分钟倒计时走了 90 秒，剩余 23:30，显示在剩余 23:00 时变化
+        let duration = 25 * 60_000;
+        assert_eq!(next_tick_delay(90_000, duration, TickPrecision::Minutes, Visibility::Visible, DOWN), 30_000);
+        // 时长不是整分钟时按剩余时长对齐，而不是按已计时长
+        assert_eq!(next_tick_delay(10_000, 90_500, TickPrecision::Minutes, Visibility::Visible, DOWN), 20_500);
+        // 正计时显示已计时长，按已计时长对齐
+        assert_eq!(next_tick_delay(90_000, duration, TickPrecision::Minutes, Visibility::Visible, CountMode::Up), 30_000);
+        assert_eq!(next_tick_delay(10_000, 90_500, TickPrecision::Minutes, Visibility::Visible, CountMode::Up), 50_000);
+    }
+}
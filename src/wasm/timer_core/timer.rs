@@ -0,0 +1,1055 @@
+// 倒计时核心
+// 纯 Rust 实现的计时数学，时间全部来自注入的 Clock
+
+use serde::{Deserialize, Serialize};
+
+use super::clock::{Clock, ClockEvent, ClockWatch, GapPolicy};
+use super::cycle::{CyclePolicy, Transition};
+use super::deadline::{self, DeadlinePlan, FitStrategy};
+use super::events::{EventQueue, TimerEvent};
+use super::flow::{CountMode, FlowPolicy};
+use super::format::format_time;
+use super::micro_break::MicroBreak;
+use super::overtime::{OvertimeMode, OvertimePolicy};
+use super::program::Program;
+use super::snapshot::{TimerSnapshot, SNAPSHOT_VERSION};
+use super::tick::{self, TickPrecision, Visibility};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TimerPhase {
+    Focus = 0,
+    Break = 1,
+    MicroBreak = 2,
+    ForcedBreak = 3,
+    LongBreak = 4,
+    Idle = 5,
+}
+
+impl TimerPhase {
+    /// 完成后会重置连续专注时间的休息阶段（微休息除外）
+    pub fn is_rest(self) -> bool {
+        matches!(self, TimerPhase::Break | TimerPhase::LongBreak | TimerPhase::ForcedBreak)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunStatus {
+    Running = 0,
+    Paused = 1,
+}
+
+/// 一次 `update` 的计算结果
+#[derive(Clone, Debug, PartialEq)]
+pub struct Calculation {
+    pub time: u32,
+    pub formatted_time: String,
+    pub progress: f64,
+    pub remaining: u32,
+    pub state: TimerPhase,
+    pub status: RunStatus,
+    // 本次计时累计暂停时长（秒）
+    pub paused_time: u32,
+    // 自上次计算以来检测到的时钟跳变或休眠
+    pub clock_event: Option<ClockEvent>,
+    // 本组中的第几轮，以及每组轮数（未开启长休息时为 0）
+    pub cycle: u32,
+    pub cycles_per_set: u32,
+    // 毫秒精度的计时数据，`started_at`/`ends_at` 为 Unix 毫秒
+    pub remaining_ms: u64,
+    pub elapsed_ms: u64,
+    pub started_at: u64,
+    pub ends_at: u64,
+    // 专注段归零后继续计时的毫秒数，未开启超时规则时为 0
+    pub overtime_ms: u64,
+}
+
+pub struct Timer<C: Clock> {
+    clock: C,
+    // 当前运行段的起点；暂停期间无意义
+    run_started_at: u64,
+    // 本阶段开始时的墙上时间
+    started_wall: u64,
+    // 之前各运行段累计的计时毫秒数
+    accumulated_ms: u64,
+    paused_at: Option<u64>,
+    paused_ms: u64,
+    watch: ClockWatch,
+    gap_policy: GapPolicy,
+    // pause/resume/reset 中检测到、尚未随 update 报告的异常
+    pending_event: Option<ClockEvent>,
+    policy: CyclePolicy,
+    // 已完成的专注段数量
+    completed_focus: u32,
+    // 自上次休息以来累计的专注秒数
+    continuous_focus: u32,
+    events: EventQueue,
+    // 本阶段是否已触发 Completed / ForcedBreakDue
+    completion_emitted: bool,
+    forced_break_emitted: bool,
+    // 本专注段计划的微休息，以及下一个尚未触发的序号
+    micro_breaks: Vec<MicroBreak>,
+    next_micro_break: usize,
+    // 正计时模式下只有专注段正计时，休息段仍然倒计时
+    count_mode: CountMode,
+    flow: FlowPolicy,
+    overtime: OvertimePolicy,
+    // 本阶段已发出的超时提醒次数
+    overtime_reminders: u32,
+    // 展开后的计时程序及当前所在的步骤；程序为空时按循环策略切换
+    program: Vec<Transition>,
+    program_position: usize,
+    duration: u32,
+    // 显示的时间：倒计时为剩余秒数，正计时为已计时秒数
+    current_time: u32,
+    state: TimerPhase,
+}
+
+impl<C: Clock> Timer<C> {
+    pub fn new(clock: C, duration: u32, state: TimerPhase) -> Timer<C> {
+        let now = clock.now_ms();
+        let watch = ClockWatch::new(&clock);
+        let mut timer = Timer {
+            run_started_at: now,
+            started_wall: clock.wall_ms(),
+            accumulated_ms: 0,
+            paused_at: None,
+            paused_ms: 0,
+            watch,
+            gap_policy: GapPolicy::default(),
+            pending_event: None,
+            policy: CyclePolicy::default(),
+            completed_focus: 0,
+            continuous_focus: 0,
+            events: EventQueue::default(),
+            completion_emitted: false,
+            forced_break_emitted: false,
+            micro_breaks: Vec::new(),
+            next_micro_break: 0,
+            count_mode: CountMode::default(),
+            flow: FlowPolicy::default(),
+            overtime: OvertimePolicy::default(),
+            overtime_reminders: 0,
+            program: Vec::new(),
+            program_position: 0,
+            duration,
+            current_time: duration,
+            state,
+            clock,
+        };
+        timer.events.push(TimerEvent::Started { state });
+        timer
+    }
+
+    /// 从快照恢复。快照之后经过的墙上时间按原状态补上：运行中的计入计时，暂停中的计入暂停时长
+    pub fn restore(clock: C, snapshot: TimerSnapshot) -> Timer<C> {
+        let now = clock.now_ms();
+        let gap_ms = clock.wall_ms().saturating_sub(snapshot.taken_at);
+        let (accumulated_ms, paused_at, paused_ms) = if snapshot.paused {
+            (snapshot.elapsed_ms, Some(now), snapshot.paused_ms + gap_ms)
+        } else {
+            (snapshot.elapsed_ms + gap_ms, None, snapshot.paused_ms)
+        };
+        let watch = ClockWatch::new(&clock);
+        let mut timer = Timer {
+            run_started_at: now,
+            started_wall: snapshot.started_at,
+            accumulated_ms,
+            paused_at,
+            paused_ms,
+            watch,
+            gap_policy: snapshot.gap_policy,
+            pending_event: None,
+            policy: snapshot.policy,
+            completed_focus: snapshot.completed_focus,
+            continuous_focus: snapshot.continuous_focus,
+            events: EventQueue::default(),
+            completion_emitted: snapshot.completion_emitted,
+            forced_break_emitted: snapshot.forced_break_emitted,
+            micro_breaks: snapshot.micro_breaks,
+            next_micro_break: snapshot.next_micro_break,
+            count_mode: snapshot.count_mode,
+            flow: snapshot.flow,
+            overtime: snapshot.overtime,
+            overtime_reminders: snapshot.overtime_reminders,
+            program_position: snapshot.program_position.min(snapshot.program.len().saturating_sub(1)),
+            program: snapshot.program,
+            duration: snapshot.duration,
+            current_time: snapshot.duration,
+            state: snapshot.state,
+            clock,
+        };
+        timer.current_time = timer.display_time(timer.elapsed_secs());
+        timer
+    }
+
+    /// 导出当前状态，可用 `restore` 在其他进程或平台上继续
+    pub fn snapshot(&self) -> TimerSnapshot {
+        TimerSnapshot {
+            version: SNAPSHOT_VERSION,
+            taken_at: self.clock.wall_ms(),
+            state: self.state,
+            duration: self.duration,
+            elapsed_ms: self.elapsed_ms(),
+            paused: self.paused_at.is_some(),
+            paused_ms: self.total_paused_ms(),
+            started_at: self.started_wall,
+            policy: self.policy,
+            gap_policy: self.gap_policy,
+            completed_focus: self.completed_focus,
+            continuous_focus: self.continuous_focus,
+            completion_emitted: self.completion_emitted,
+            forced_break_emitted: self.forced_break_emitted,
+            micro_breaks: self.micro_breaks.clone(),
+            next_micro_break: self.next_micro_break,
+            count_mode: self.count_mode,
+            flow: self.flow,
+            overtime: self.overtime,
+            overtime_reminders: self.overtime_reminders,
+            program: self.program.clone(),
+            program_position: self.program_position,
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn duration(&self) -> u32 {
+        self.duration
+    }
+
+    pub fn current_time(&self) -> u32 {
+        self.current_time
+    }
+
+    pub fn state(&self) -> TimerPhase {
+        self.state
+    }
+
+    pub fn policy(&self) -> CyclePolicy {
+        self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: CyclePolicy) {
+        self.policy = policy;
+    }
+
+    /// 按程序推进阶段，`None` 恢复为循环策略。当前阶段视为程序中第一个同类阶段
+    pub fn set_program(&mut self, program: Option<&Program>) {
+        self.program = program.map(Program::expand).unwrap_or_default();
+        self.program_position = self.program.iter().position(|b| b.phase == self.state).unwrap_or(0);
+    }
+
+    /// 展开后的程序步骤，未设置程序时为空
+    pub fn program(&self) -> &[Transition] {
+        &self.program
+    }
+
+    pub fn program_position(&self) -> usize {
+        self.program_position
+    }
+
+    // 程序中的下一步，走完后回到开头
+    fn program_next(&self) -> Option<Transition> {
+        if self.program.is_empty() { return None; }
+        Some(self.program[(self.program_position + 1) % self.program.len()])
+    }
+
+    /// 取走尚未处理的事件
+    pub fn drain_events(&mut self) -> Vec<TimerEvent> {
+        self.events.drain()
+    }
+
+    pub fn has_events(&self) -> bool {
+        !self.events.is_empty()
+    }
+
+    /// 为当前专注段设置微休息计划（`at` 为专注秒数），到点时产生 MicroBreakDue
+    pub fn set_micro_breaks(&mut self, plan: Vec<MicroBreak>) {
+        let elapsed = self.elapsed_secs();
+        self.next_micro_break = plan.iter().take_while(|b| b.at < elapsed).count();
+        self.micro_breaks = plan;
+    }
+
+    /// 当前处于本组的第几轮（从 1 开始）；按程序运行时为本遍程序中的第几个专注段
+    pub fn cycle(&self) -> u32 {
+        if self.program.is_empty() {
+            return self.policy.cycle_number(self.state, self.completed_focus);
+        }
+        let focus_before = self.program[..self.program_position]
+            .iter()
+            .filter(|b| b.phase == TimerPhase::Focus)
+            .count() as u32;
+        match self.state {
+            TimerPhase::Focus => focus_before + 1,
+            _ => focus_before.max(1),
+        }
+    }
+
+    /// 每组的轮数：程序中的专注段数，或长休息间隔
+    pub fn cycles_per_set(&self) -> u32 {
+        if self.program.is_empty() {
+            return self.policy.long_break_interval;
+        }
+        self.program.iter().filter(|b| b.phase == TimerPhase::Focus).count() as u32
+    }
+
+    pub fn completed_focus(&self) -> u32 {
+        self.completed_focus
+    }
+
+    pub fn continuous_focus(&self) -> u32 {
+        self.continuous_focus
+    }
+
+    pub fn count_mode(&self) -> CountMode {
+        self.count_mode
+    }
+
+    /// 切换计时方向；正计时时 `duration` 作为专注段的软目标
+    pub fn set_count_mode(&mut self, mode: CountMode) {
+        self.count_mode = mode;
+        self.current_time = self.display_time(self.elapsed_secs());
+    }
+
+    pub fn flow_policy(&self) -> FlowPolicy {
+        self.flow
+    }
+
+    pub fn set_flow_policy(&mut self, flow: FlowPolicy) {
+        self.flow = flow;
+    }
+
+    pub fn overtime_policy(&self) -> OvertimePolicy {
+        self.overtime
+    }
+
+    pub fn set_overtime_policy(&mut self, overtime: OvertimePolicy) {
+        self.overtime = overtime;
+    }
+
+    /// 当前专注段超出时长的毫秒数；未开启超时规则或不在倒计时专注段时为 0
+    pub fn overtime_ms(&self) -> u64 {
+        if !self.tracks_overtime() { return 0; }
+        self.elapsed_ms().saturating_sub(self.duration_ms())
+    }
+
+    pub fn gap_policy(&self) -> GapPolicy {
+        self.gap_policy
+    }
+
+    pub fn set_gap_policy(&mut self, policy: GapPolicy) {
+        self.gap_policy = policy;
+    }
+
+    /// 调整时钟跳变容差与休眠判定阈值（毫秒）
+    pub fn set_clock_thresholds(&mut self, jump_tolerance_ms: u64, suspend_threshold_ms: u64) {
+        self.watch.set_thresholds(jump_tolerance_ms, suspend_threshold_ms);
+    }
+
+    pub fn status(&self) -> RunStatus {
+        if self.paused_at.is_some() { RunStatus::Paused } else { RunStatus::Running }
+    }
+
+    /// 已计时的毫秒数，不含暂停时间
+    pub fn elapsed_ms(&self) -> u64 {
+        match self.paused_at {
+            Some(_) => self.accumulated_ms,
+            None => self.accumulated_ms + self.clock.now_ms().saturating_sub(self.run_started_at),
+        }
+    }
+
+    /// 本次计时累计暂停的毫秒数，包括正在进行的暂停
+    pub fn total_paused_ms(&self) -> u64 {
+        match self.paused_at {
+            Some(at) => self.paused_ms + self.clock.now_ms().saturating_sub(at),
+            None => self.paused_ms,
+        }
+    }
+
+    pub fn update(&mut self) -> Calculation {
+        let clock_event = self.observe_clock().or_else(|| self.pending_event.take());
+        let elapsed_ms = self.elapsed_ms();
+        let remaining_ms = self.duration_ms().saturating_sub(elapsed_ms);
+        let elapsed = (elapsed_ms / 1000) as u32;
+        self.current_time = self.display_time(elapsed);
+        self.emit_due_events(elapsed);
+
+        Calculation {
+            time: self.current_time,
+            formatted_time: format_time(self.current_time),
+            progress: self.calculate_progress(elapsed_ms),
+            remaining: self.duration.saturating_sub(elapsed),
+            state: self.state,
+            status: self.status(),
+            paused_time: (self.total_paused_ms() / 1000) as u32,
+            clock_event,
+            cycle: self.cycle(),
+            cycles_per_set: self.cycles_per_set(),
+            remaining_ms,
+            elapsed_ms,
+            started_at: self.started_wall,
+            // 以当前墙上时间推算，暂停期间结束时间随之后移
+            ends_at: self.clock.wall_ms() + remaining_ms,
+            overtime_ms: if self.tracks_overtime() { elapsed_ms.saturating_sub(self.duration_ms()) } else { 0 },
+        }
+    }
+
+    /// 切换到新阶段；离开的阶段计入专注统计。按程序运行时，切换到程序的下一阶段即推进一步
+    pub fn reset(&mut self, new_duration: u32, new_state: TimerPhase) {
+        self.record_phase_end();
+        if self.program_next().map(|next| next.phase) == Some(new_state) {
+            self.program_position = (self.program_position + 1) % self.program.len();
+        }
+        if new_state != self.state {
+            self.events.push(TimerEvent::StateChanged { from: self.state, to: new_state });
+        }
+        self.events.push(TimerEvent::Started { state: new_state });
+        self.completion_emitted = false;
+        self.forced_break_emitted = false;
+        self.overtime_reminders = 0;
+        self.micro_breaks.clear();
+        self.next_micro_break = 0;
+        self.watch.rebase(&self.clock);
+        self.pending_event = None;
+        self.run_started_at = self.clock.now_ms();
+        self.started_wall = self.clock.wall_ms();
+        self.accumulated_ms = 0;
+        self.paused_at = None;
+        self.paused_ms = 0;
+        self.duration = new_duration;
+        self.state = new_state;
+        self.current_time = self.display_time(0);
+    }
+
+    /// 结束当前阶段并切换到建议的下一阶段，返回新阶段
+    pub fn advance(&mut self) -> Transition {
+        let next = self.suggest_next();
+        self.reset(next.duration, next.phase);
+        next
+    }
+
+    /// 切换到新阶段，时长取到截止时间（Unix 毫秒）为止，返回得到的秒数
+    pub fn reset_until(&mut self, deadline_ms: u64, new_state: TimerPhase) -> u32 {
+        let duration = deadline::window_secs(self.clock.wall_ms(), deadline_ms);
+        self.reset(duration, new_state);
+        duration
+    }
+
+    /// 按当前循环策略安排从现在到截止时间的专注与休息
+    pub fn plan_until(&self, deadline_ms: u64, strategy: FitStrategy) -> DeadlinePlan {
+        let window = deadline::window_secs(self.clock.wall_ms(), deadline_ms);
+        deadline::plan_until(window, &self.policy, strategy, self.completed_focus)
+    }
+
+    /// 暂停计时，返回暂停时显示的秒数（倒计时为剩余、正计时为已计时）；重复暂停不会改变状态
+    pub fn pause(&mut self) -> u32 {
+        self.stash_clock_event();
+        if self.paused_at.is_none() {
+            let now = self.clock.now_ms();
+            self.accumulated_ms += now.saturating_sub(self.run_started_at);
+            self.paused_at = Some(now);
+            self.events.push(TimerEvent::Paused);
+        }
+        self.current_time = self.display_time(self.elapsed_secs());
+        self.current_time
+    }
+
+    /// 从暂停处继续，进度仍以原始时长计算
+    pub fn resume(&mut self) {
+        self.stash_clock_event();
+        if let Some(at) = self.paused_at.take() {
+            let now = self.clock.now_ms();
+            self.paused_ms += now.saturating_sub(at);
+            self.run_started_at = now;
+            self.events.push(TimerEvent::Resumed);
+        }
+    }
+
+    pub fn progress_percentage(current: u32, total: u32) -> f64 {
+        if total == 0 { return 0.0; }
+        (current as f64 / total as f64) * 100.0
+    }
+
+    pub fn batch_progress(&self, times: &[u32]) -> Vec<f64> {
+        times.iter()
+            .map(|&time| Self::progress_percentage(time, self.duration))
+            .collect()
+    }
+
+    pub fn should_update_display(&self, last_update: u32) -> bool {
+        // 只在时间变化时更新显示，减少不必要的渲染
+        self.elapsed_secs() != last_update
+    }
+
+    pub fn next_state(&self, completed: bool) -> TimerPhase {
+        if !completed { return self.state; }
+        if let Some(next) = self.program_next() {
+            return next.phase;
+        }
+        let (completed_focus, continuous) = match self.state {
+            // 把正在结束的专注段计算在内
+            TimerPhase::Focus => (self.completed_focus + 1, self.continuous_focus + self.elapsed_secs()),
+            _ => (self.completed_focus, self.continuous_focus),
+        };
+        self.policy.next_phase(self.state, completed_focus, continuous)
+    }
+
+    /// 当前阶段完成后的下一阶段及其建议时长；按程序运行时取程序的下一步，
+    /// 否则正计时专注段之后的普通休息按心流规则计算；超时的专注段之后的休息按超时规则调整
+    pub fn suggest_next(&self) -> Transition {
+        if let Some(next) = self.program_next() {
+            let duration = if next.phase.is_rest() {
+                self.overtime.adjust_break(next.duration, (self.overtime_ms() / 1000) as u32)
+            } else {
+                next.duration
+            };
+            return Transition { phase: next.phase, duration };
+        }
+        let phase = self.next_state(true);
+        let duration = match phase {
+            TimerPhase::Break if self.counts_up() => self.suggested_break(),
+            phase if phase.is_rest() => {
+                let overtime = (self.overtime_ms() / 1000) as u32;
+                self.overtime.adjust_break(self.policy.duration_for(phase), overtime)
+            }
+            _ => self.policy.duration_for(phase),
+        };
+        Transition { phase, duration }
+    }
+
+    /// 按当前专注段已计时长建议的休息秒数
+    pub fn suggested_break(&self) -> u32 {
+        self.flow.break_for(self.elapsed_secs())
+    }
+
+    /// 到下一次需要刷新的毫秒数：显示按 `precision` 变化、阶段归零、微休息或强制休息到点，取最早者。
+    /// 暂停时显示不会变化，返回 None
+    pub fn next_tick_ms(&self, precision: TickPrecision, visibility: Visibility) -> Option<u64> {
+        if self.paused_at.is_some() { return None; }
+        let elapsed_ms = self.elapsed_ms();
+        let count = if self.counts_up() { CountMode::Up } else { CountMode::Down };
+        let mut delay = tick::next_tick_delay(elapsed_ms, self.duration_ms(), precision, visibility, count);
+
+        if self.state == TimerPhase::Focus {
+            let due_at = |secs: u32| (secs as u64 * 1000).checked_sub(elapsed_ms).filter(|d| *d > 0);
+            if let Some(d) = self.micro_breaks.get(self.next_micro_break).and_then(|b| due_at(b.at)) {
+                delay = delay.min(d);
+            }
+            let threshold = self.policy.forced_break_threshold;
+            if threshold > 0 && !self.forced_break_emitted {
+                if let Some(d) = due_at(threshold.saturating_sub(self.continuous_focus)) {
+                    delay = delay.min(d);
+                }
+            }
+        }
+        Some(delay)
+    }
+
+    /// 观测时钟并按 `gap_policy` 处理运行中出现的休眠。
+    /// 单调时钟在休眠期间停止，计时本身已不含休眠时长
+    fn observe_clock(&mut self) -> Option<ClockEvent> {
+        let last_mono = self.watch.last_mono();
+        let event = self.watch.observe(&self.clock);
+        if let Some(ClockEvent::SuspendGap { gap_ms }) = event {
+            // 暂停期间的休眠本来就不计时，无需报告
+            if self.paused_at.is_some() { return None; }
+            match self.gap_policy {
+                GapPolicy::Keep => self.accumulated_ms += gap_ms,
+                GapPolicy::Discount => {}
+                GapPolicy::Pause => {
+                    self.accumulated_ms += last_mono.saturating_sub(self.run_started_at);
+                    self.paused_at = Some(last_mono);
+                    self.paused_ms += gap_ms;
+                    self.events.push(TimerEvent::Paused);
+                }
+            }
+        }
+        if let Some(event) = event {
+            self.events.push(TimerEvent::ClockJumpDetected(event));
+        }
+        event
+    }
+
+    fn emit_due_events(&mut self, elapsed: u32) {
+        if self.state == TimerPhase::Focus {
+            while let Some(b) = self.micro_breaks.get(self.next_micro_break) {
+                if b.at > elapsed { break; }
+                self.events.push(TimerEvent::MicroBreakDue { duration: b.duration });
+                self.next_micro_break += 1;
+            }
+
+            let threshold = self.policy.forced_break_threshold;
+            if threshold > 0 && !self.forced_break_emitted && self.continuous_focus + elapsed >= threshold {
+                self.forced_break_emitted = true;
+                self.events.push(TimerEvent::ForcedBreakDue);
+            }
+        }
+
+        if !self.completion_emitted && elapsed >= self.duration {
+            self.completion_emitted = true;
+            if self.counts_up() {
+                self.events.push(TimerEvent::TargetReached);
+            } else {
+                self.events.push(TimerEvent::Completed { state: self.state });
+            }
+        }
+
+        let interval = self.overtime.reminder_interval;
+        if self.tracks_overtime() && interval > 0 {
+            let overtime = elapsed.saturating_sub(self.duration);
+            while (self.overtime_reminders + 1).saturating_mul(interval) <= overtime {
+                self.overtime_reminders += 1;
+                self.events.push(TimerEvent::OvertimeReminder { overtime: self.overtime_reminders * interval });
+            }
+        }
+    }
+
+    fn record_phase_end(&mut self) {
+        let elapsed = self.elapsed_secs();
+        // 正计时的专注段没有终点，结束即视为完成
+        let counts_up = self.counts_up();
+        let finished = counts_up || elapsed >= self.duration;
+        match self.state {
+            TimerPhase::Focus => {
+                let counted = counts_up || self.overtime.mode == OvertimeMode::CountTowardSession;
+                self.continuous_focus += if counted { elapsed } else { elapsed.min(self.duration) };
+                if finished { self.completed_focus += 1; }
+            }
+            phase if phase.is_rest() && finished => self.continuous_focus = 0,
+            _ => {}
+        }
+    }
+
+    fn stash_clock_event(&mut self) {
+        if let Some(event) = self.observe_clock() {
+            self.pending_event = Some(event);
+        }
+    }
+
+    fn counts_up(&self) -> bool {
+        self.count_mode == CountMode::Up && self.state == TimerPhase::Focus
+    }
+
+    fn tracks_overtime(&self) -> bool {
+        self.overtime.enabled() && self.state == TimerPhase::Focus && !self.counts_up()
+    }
+
+    fn display_time(&self, elapsed: u32) -> u32 {
+        if self.counts_up() { elapsed } else { self.duration.saturating_sub(elapsed) }
+    }
+
+    fn elapsed_secs(&self) -> u32 {
+        (self.elapsed_ms() / 1000) as u32
+    }
+
+    fn duration_ms(&self) -> u64 {
+        self.duration as u64 * 1000
+    }
+
+    fn calculate_progress(&self, elapsed_ms: u64) -> f64 {
+        if self.duration == 0 { return 0.0; }
+        let duration_ms = self.duration_ms();
+        (elapsed_ms.min(duration_ms) as f64 / duration_ms as f64) * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer_core::clock::{FakeClock, DEFAULT_SUSPEND_THRESHOLD_MS};
+
+    #[test]
+    fn counts_down_with_fake_clock() {
+        let clock = FakeClock::new(1_000_000);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+
+        clock.advance(60_000);
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 1440);
+        assert_eq!(calc.formatted_time, "24:00");
+        assert!((calc.progress - 4.0).abs() < 1e-9);
+
+        clock.advance(2_000_000);
+        assert_eq!(timer.update().remaining, 0);
+    }
+
+    #[test]
+    fn reset_restarts_from_clock() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 60, TimerPhase::Focus);
+        clock.advance(30_000);
+        timer.reset(300, TimerPhase::Break);
+        assert_eq!(timer.update().remaining, 300);
+        assert_eq!(timer.state(), TimerPhase::Break);
+    }
+
+    #[test]
+    fn display_update_tracks_whole_seconds() {
+        let clock = FakeClock::new(0);
+        let timer = Timer::new(clock.clone(), 60, TimerPhase::Focus);
+        clock.advance(999);
+        assert!(!timer.should_update_display(0));
+        clock.advance(1);
+        assert!(timer.should_update_display(0));
+    }
+
+    #[test]
+    fn pause_freezes_countdown_and_keeps_progress() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 100, TimerPhase::Focus);
+
+        clock.advance(20_000);
+        assert_eq!(timer.pause(), 80);
+        clock.advance(30_000);
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 80);
+        assert_eq!(calc.status, RunStatus::Paused);
+        assert_eq!(calc.paused_time, 30);
+
+        timer.resume();
+        clock.advance(30_000);
+        timer.pause();
+        clock.advance(10_000);
+        timer.resume();
+        clock.advance(10_000);
+
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 40);
+        assert!((calc.progress - 60.0).abs() < 1e-9);
+        assert_eq!(calc.status, RunStatus::Running);
+        assert_eq!(calc.paused_time, 40);
+    }
+
+    #[test]
+    fn reset_clears_paused_time() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 100, TimerPhase::Focus);
+        timer.pause();
+        clock.advance(5_000);
+        timer.reset(100, TimerPhase::Focus);
+        assert_eq!(timer.total_paused_ms(), 0);
+        assert_eq!(timer.status(), RunStatus::Running);
+    }
+
+    #[test]
+    fn wall_clock_jumps_do_not_affect_elapsed() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 100, TimerPhase::Focus);
+        clock.advance(10_000);
+        clock.jump_wall(-3_600_000);
+
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 90);
+        assert_eq!(calc.clock_event, Some(ClockEvent::ClockJump { delta_ms: -3_600_000 }));
+        assert_eq!(timer.update().clock_event, None);
+    }
+
+    #[test]
+    fn suspend_gap_follows_policy() {
+        let gap = DEFAULT_SUSPEND_THRESHOLD_MS + 60_000;
+
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        timer.set_gap_policy(GapPolicy::Keep);
+        clock.suspend(gap);
+        let calc = timer.update();
+        assert_eq!(calc.clock_event, Some(ClockEvent::SuspendGap { gap_ms: gap }));
+        assert_eq!(calc.remaining, 3600 - (gap / 1000) as u32);
+
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        timer.set_gap_policy(GapPolicy::Discount);
+        clock.advance(60_000);
+        timer.update();
+        clock.suspend(gap);
+        assert_eq!(timer.update().remaining, 3540);
+
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        timer.set_gap_policy(GapPolicy::Pause);
+        clock.advance(60_000);
+        timer.update();
+        clock.suspend(gap);
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 3540);
+        assert_eq!(calc.status, RunStatus::Paused);
+        assert_eq!(calc.paused_time, (gap / 1000) as u32);
+    }
+
+    #[test]
+    fn suspend_before_pause_is_reported_on_next_update() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        timer.set_gap_policy(GapPolicy::Discount);
+        clock.suspend(DEFAULT_SUSPEND_THRESHOLD_MS);
+        assert_eq!(timer.pause(), 3600);
+        assert!(matches!(timer.update().clock_event, Some(ClockEvent::SuspendGap { .. })));
+    }
+
+    #[test]
+    fn pending_event_survives_a_fresh_one() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        clock.suspend(DEFAULT_SUSPEND_THRESHOLD_MS);
+        timer.pause();
+        clock.jump_wall(-600_000);
+        assert_eq!(timer.update().clock_event, Some(ClockEvent::ClockJump { delta_ms: -600_000 }));
+        assert!(matches!(timer.update().clock_event, Some(ClockEvent::SuspendGap { .. })));
+        assert_eq!(timer.update().clock_event, None);
+    }
+
+    #[test]
+    fn manual_forward_change_is_not_counted_by_default() {
+        // 单调时钟照常前进，墙上时间被手动调快超过休眠阈值
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        clock.advance(60_000);
+        clock.jump_wall(2 * DEFAULT_SUSPEND_THRESHOLD_MS as i64);
+        let calc = timer.update();
+        assert!(matches!(calc.clock_event, Some(ClockEvent::SuspendGap { .. })));
+        assert_eq!(calc.remaining, 3540);
+        assert_eq!(calc.status, RunStatus::Running);
+    }
+
+    #[test]
+    fn long_break_every_nth_focus_and_forced_break_on_threshold() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_policy(CyclePolicy { long_break_interval: 2, ..CyclePolicy::default() });
+
+        clock.advance(1_500_000);
+        assert_eq!(timer.next_state(true), TimerPhase::Break);
+        timer.reset(300, TimerPhase::Break);
+        clock.advance(300_000);
+        timer.reset(1500, TimerPhase::Focus);
+        clock.advance(1_500_000);
+        assert_eq!(timer.next_state(true), TimerPhase::LongBreak);
+        assert_eq!(timer.next_state(false), TimerPhase::Focus);
+
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3000, TimerPhase::Focus);
+        timer.set_policy(CyclePolicy { forced_break_threshold: 5400, ..CyclePolicy::default() });
+        clock.advance(3_000_000);
+        timer.reset(180, TimerPhase::MicroBreak);
+        clock.advance(180_000);
+        timer.reset(3000, TimerPhase::Focus);
+        clock.advance(2_400_000);
+        assert_eq!(timer.next_state(true), TimerPhase::ForcedBreak);
+        assert_eq!(timer.continuous_focus(), 3000);
+    }
+
+    #[test]
+    fn calculation_reports_cycle_and_suggests_long_break() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_policy(CyclePolicy::pomodoro());
+
+        for _ in 0..3 {
+            clock.advance(1_500_000);
+            let next = timer.suggest_next();
+            timer.reset(next.duration, next.phase);
+            clock.advance(300_000);
+            let next = timer.suggest_next();
+            timer.reset(next.duration, next.phase);
+        }
+        let calc = timer.update();
+        assert_eq!((calc.cycle, calc.cycles_per_set), (4, 4));
+
+        clock.advance(1_500_000);
+        assert_eq!(timer.suggest_next(), Transition { phase: TimerPhase::LongBreak, duration: 900 });
+    }
+
+    #[test]
+    fn program_drives_phase_sequence() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 3120, TimerPhase::Focus);
+        let program = Program::parse("repeat 2 { focus 52m; break 17m }; long_break 30m").unwrap();
+        timer.set_program(Some(&program));
+
+        let mut phases = Vec::new();
+        for _ in 0..5 {
+            clock.advance(timer.duration() as u64 * 1000);
+            phases.push(timer.advance());
+        }
+        assert_eq!(phases.iter().map(|t| t.duration / 60).collect::<Vec<_>>(), vec![17, 52, 17, 30, 52]);
+        assert_eq!(timer.program_position(), 0);
+        let calc = timer.update();
+        assert_eq!((calc.cycle, calc.cycles_per_set), (1, 2));
+
+        // 快照保留程序位置；清除程序后恢复循环策略
+        let mut restored = Timer::restore(clock.clone(), timer.snapshot());
+        assert_eq!(restored.suggest_next(), Transition { phase: TimerPhase::Break, duration: 17 * 60 });
+        restored.set_program(None);
+        assert_eq!(restored.suggest_next(), Transition { phase: TimerPhase::Break, duration: 300 });
+    }
+
+    #[test]
+    fn millisecond_fields_and_absolute_end_time() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 60, TimerPhase::Focus);
+        clock.advance(1_250);
+
+        let calc = timer.update();
+        assert_eq!(calc.elapsed_ms, 1_250);
+        assert_eq!(calc.remaining_ms, 58_750);
+        assert_eq!(calc.remaining, 59);
+        assert_eq!(calc.started_at, 1_700_000_000_000);
+        assert_eq!(calc.ends_at, 1_700_000_060_000);
+        assert!((calc.progress - 1_250.0 / 600.0).abs() < 1e-9);
+
+        timer.pause();
+        clock.advance(10_000);
+        assert_eq!(timer.update().ends_at, 1_700_000_070_000);
+
+        clock.advance(120_000_000);
+        timer.resume();
+        assert!((timer.update().progress - 100.0 * 1_250.0 / 60_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn emits_lifecycle_events_once() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 60, TimerPhase::Focus);
+        timer.set_micro_breaks(vec![MicroBreak { at: 20, duration: 30 }]);
+
+        clock.advance(10_000);
+        timer.pause();
+        timer.pause();
+        timer.resume();
+        clock.advance(15_000);
+        timer.update();
+        clock.advance(60_000);
+        timer.update();
+        timer.update();
+        timer.reset(300, TimerPhase::Break);
+
+        assert_eq!(timer.drain_events(), vec![
+            TimerEvent::Started { state: TimerPhase::Focus },
+            TimerEvent::Paused,
+            TimerEvent::Resumed,
+            TimerEvent::MicroBreakDue { duration: 30 },
+            TimerEvent::Completed { state: TimerPhase::Focus },
+            TimerEvent::StateChanged { from: TimerPhase::Focus, to: TimerPhase::Break },
+            TimerEvent::Started { state: TimerPhase::Break },
+        ]);
+        assert!(timer.drain_events().is_empty());
+    }
+
+    #[test]
+    fn count_up_focus_runs_past_target_and_suggests_proportional_break() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 25 * 60, TimerPhase::Focus);
+        timer.set_count_mode(CountMode::Up);
+        timer.drain_events();
+
+        clock.advance(10 * 60_000);
+        let calc = timer.update();
+        assert_eq!(calc.time, 600);
+        assert_eq!(calc.formatted_time, "10:00");
+        assert_eq!(calc.remaining, 900);
+        assert!((calc.progress - 40.0).abs() < 1e-9);
+
+        clock.advance(40 * 60_000);
+        let calc = timer.update();
+        assert_eq!(calc.time, 3000);
+        assert!((calc.progress - 100.0).abs() < 1e-9);
+        assert_eq!(timer.drain_events(), vec![TimerEvent::TargetReached]);
+        assert_eq!(timer.suggest_next(), Transition { phase: TimerPhase::Break, duration: 600 });
+
+        // 休息段照常倒计时
+        timer.reset(600, TimerPhase::Break);
+        assert_eq!(timer.continuous_focus(), 3000);
+        assert_eq!(timer.completed_focus(), 1);
+        clock.advance(60_000);
+        assert_eq!(timer.update().time, 540);
+    }
+
+    #[test]
+    fn overtime_keeps_counting_and_reminds_periodically() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_overtime_policy(OvertimePolicy {
+            mode: OvertimeMode::AdjustBreak,
+            break_factor: 0.5,
+            reminder_interval: 120,
+        });
+        timer.drain_events();
+
+        clock.advance(1_500_000 + 250_000);
+        let calc = timer.update();
+        assert_eq!(calc.remaining, 0);
+        assert_eq!(calc.overtime_ms, 250_000);
+        assert_eq!(calc.elapsed_ms, 1_750_000);
+        assert_eq!(timer.drain_events(), vec![
+            TimerEvent::Completed { state: TimerPhase::Focus },
+            TimerEvent::OvertimeReminder { overtime: 120 },
+            TimerEvent::OvertimeReminder { overtime: 240 },
+        ]);
+        assert_eq!(timer.suggest_next(), Transition { phase: TimerPhase::Break, duration: 425 });
+
+        timer.reset(300, TimerPhase::Break);
+        assert_eq!(timer.continuous_focus(), 1500);
+        clock.advance(400_000);
+        assert_eq!(timer.update().overtime_ms, 0);
+
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_overtime_policy(OvertimePolicy { mode: OvertimeMode::CountTowardSession, ..OvertimePolicy::default() });
+        clock.advance(1_800_000);
+        timer.reset(300, TimerPhase::Break);
+        assert_eq!(timer.continuous_focus(), 1800);
+    }
+
+    #[test]
+    fn deadline_sets_duration_from_wall_clock() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        let deadline = 1_700_000_000_000 + 47 * 60_000 + 500;
+        assert_eq!(timer.reset_until(deadline, TimerPhase::Focus), 47 * 60);
+        assert_eq!(timer.update().ends_at, 1_700_000_000_000 + 47 * 60_000);
+
+        let plan = timer.plan_until(deadline, FitStrategy::ShortenLast);
+        assert_eq!(plan.blocks.len(), 3);
+        assert_eq!(plan.focus_time() + 300, 47 * 60);
+    }
+
+    #[test]
+    fn next_tick_aligns_to_seconds_and_due_events() {
+        let clock = FakeClock::new(0);
+        let mut timer = Timer::new(clock.clone(), 1500, TimerPhase::Focus);
+        timer.set_micro_breaks(vec![MicroBreak { at: 590, duration: 180 }]);
+
+        clock.advance(1_250);
+        assert_eq!(timer.next_tick_ms(TickPrecision::Seconds, Visibility::Visible), Some(750));
+
+        // 不可见时按分钟刷新，但微休息到点时仍要唤醒
+        clock.advance(570_000);
+        assert_eq!(timer.next_tick_ms(TickPrecision::Seconds, Visibility::Hidden), Some(18_750));
+
+        timer.pause();
+        assert_eq!(timer.next_tick_ms(TickPrecision::Seconds, Visibility::Visible), None);
+    }
+
+    #[test]
+    fn forced_break_due_and_clock_jump_events() {
+        let clock = FakeClock::new(1_700_000_000_000);
+        let mut timer = Timer::new(clock.clone(), 3600, TimerPhase::Focus);
+        timer.set_policy(CyclePolicy { forced_break_threshold: 1800, ..CyclePolicy::default() });
+        timer.drain_events();
+
+        clock.advance(60_000);
+        clock.jump_wall(60_000);
+        timer.update();
+        for _ in 0..29 {
+            clock.advance(60_000);
+            timer.update();
+        }
+        let events = timer.drain_events();
+        assert!(events.contains(&TimerEvent::ForcedBreakDue));
+        assert!(events.contains(&TimerEvent::ClockJumpDetected(ClockEvent::ClockJump { delta_ms: 60_000 })));
+    }
+}
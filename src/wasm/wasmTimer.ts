@@ -5,6 +5,72 @@ export enum TimerState {
   Focus = 0,
   Break = 1,
   MicroBreak = 2,
+  ForcedBreak = 3,
+  LongBreak = 4,
+  Idle = 5,
+}
+
+export enum TimerStatus {
+  Running = 0,
+  Paused = 1,
+}
+
+export enum ClockEventKind {
+  None = 0,
+  ClockJump = 1,
+  SuspendGap = 2,
+}
+
+export enum SuspendPolicy {
+  Keep = 0,
+  Discount = 1,
+  Pause = 2,
+}
+
+export enum DurationFormat {
+  Clock = 0,
+  MinutesSeconds = 1,
+  HoursMinutesSeconds = 2,
+  Compact = 3,
+  Verbose = 4,
+}
+
+export enum TimerEventKind {
+  Started = 0,
+  Paused = 1,
+  Resumed = 2,
+  Completed = 3,
+  StateChanged = 4,
+  MicroBreakDue = 5,
+  ForcedBreakDue = 6,
+  ClockJumpDetected = 7,
+  TargetReached = 8,
+  OvertimeReminder = 9,
+}
+
+export enum DisplayPrecision {
+  Tenths = 0,
+  Seconds = 1,
+  Minutes = 2,
+}
+
+export enum OvertimeRule {
+  Off = 0,
+  CountTowardSession = 1,
+  AdjustBreak = 2,
+}
+
+export enum CountDirection {
+  Down = 0,
+  Up = 1,
+}
+
+export interface TimerEvent {
+  kind: TimerEventKind;
+  state: TimerState;
+  fromState: TimerState;
+  clockEvent: ClockEventKind;
+  value: number;
 }
 
 export interface TimerCalculation {
@@ -13,12 +79,106 @@ export interface TimerCalculation {
   progress: number;
   remaining: number;
   state: TimerState;
+  status: TimerStatus;
+  pausedTime: number;
+  clockEvent: ClockEventKind;
+  clockEventMs: number;
+  cycle: number;
+  cyclesPerSet: number;
+  remainingMs: number;
+  elapsedMs: number;
+  startedAt: number;
+  endsAt: number;
+  overtimeMs: number;
+}
+
+export interface NamedTimerCalculation {
+  name: string;
+  calculation: TimerCalculation;
+}
+
+// 会话原始数据，时长单位为秒，rating 为 1-5 的手动评分
+export interface SessionMetrics {
+  plannedFocus: number;
+  completedFocus: number;
+  pausedTime?: number;
+  pauseCount?: number;
+  breaksTaken?: number;
+  breaksSkipped?: number;
+  microBreaksPlanned?: number;
+  microBreaksTaken?: number;
+  rating?: number | null;
+}
+
+export interface ScoreWeights {
+  completion: number;
+  pauses: number;
+  breaks: number;
+  microBreaks: number;
+  rating: number;
+}
+
+export type ScoreFactor = 'completion' | 'pauses' | 'breaks' | 'microBreaks' | 'rating';
+
+export interface EfficiencyScore {
+  score: number; // 0-100
+  breakdown: {
+    factor: ScoreFactor;
+    score: number;
+    weight: number;
+    contribution: number;
+  }[];
+}
+
+// 会话历史的列式数据，各列等长；时长单位由调用方决定，统计结果沿用同一单位
+export interface SessionColumns {
+  startedAt: Float64Array; // Unix 毫秒
+  endedAt?: Float64Array; // 提供时跨日会话按时长拆分到各天
+  focus: Uint32Array;
+  breakTime: Uint32Array;
+  microBreaks: Uint32Array;
+  efficiency: Float64Array;
+}
+
+// 日/周/月汇总。keys 对日为自 1970-01-01 起的本地天数，对周为该周周一的天数，对月为 年*100+月
+export interface PeriodSeries {
+  keys: Float64Array;
+  focus: Float64Array;
+  breakTime: Float64Array;
+  microBreaks: Uint32Array;
+  sessions: Uint32Array;
+  averageEfficiency: Float64Array;
+}
+
+export interface SessionStats {
+  daily: PeriodSeries;
+  weekly: PeriodSeries;
+  monthly: PeriodSeries;
+  totalFocus: number;
+  totalSessions: number;
+  averageDailyFocus: number;
+  averageSessionFocus: number;
+  averageEfficiency: number;
+  currentStreak: number;
+  longestStreak: number;
+  bestHour: number | null;
+  hourlyFocus: Float64Array; // 24 项
+  heatmap: Float64Array; // 365 天，最早的一天在前
+  heatmapStartWeekday: number; // 0 为周一
+}
+
+// 计时程序的校验结果，line 从 1 开始（没有行号时为 0）
+export interface ProgramDiagnostic {
+  line: number;
+  message: string;
 }
 
 export interface WasmTimerConfig {
   duration: number;
   state: TimerState;
   enableOptimization?: boolean;
+  // Up 时专注段正计时（心流模式），duration 作为软目标
+  direction?: CountDirection;
 }
 
 // WebAssembly 模块接口
@@ -31,23 +191,43 @@ declare global {
 export class WasmTimer {
   private wasmModule: any = null;
   private calculator: any = null;
+  private registry: any = null;
+  private calendar: any = null;
   private isInitialized = false;
+  private loading: Promise<void> | null = null;
 
 
   constructor() {
     this.initializeWasm();
   }
 
-  private async initializeWasm() {
-    try {
-      // 动态导入WASM模块
-      const wasm = await import('../wasm/pkg/timer_calculation');
-      this.wasmModule = wasm;
-      this.isInitialized = true;
-      console.log('WebAssembly Timer initialized successfully');
-    } catch (error) {
-      console.warn('WebAssembly Timer initialization failed:', error);
-      this.isInitialized = false;
+  private initializeWasm(): Promise<void> {
+    if (!this.loading) {
+      this.loading = (async () => {
+        try {
+          const wasm = await import('./pkg/timer_calculation');
+          // web 目标需要先加载 .wasm 文件；Jest 使用的 nodejs 目标在导入时已同步加载
+          if (typeof wasm.default === 'function') {
+            await wasm.default();
+          }
+          this.wasmModule = wasm;
+          this.isInitialized = true;
+          console.log('WebAssembly Timer initialized successfully');
+        } catch (error) {
+          console.warn('WebAssembly Timer initialization failed:', error);
+          this.isInitialized = false;
+          this.loading = null;
+        }
+      })();
+    }
+    return this.loading;
+  }
+
+  // 等待 WASM 加载完成，失败时抛出错误。应用启动时等待，之后格式化等同步接口即可直接使用
+  public async ready(): Promise<void> {
+    await this.initializeWasm();
+    if (!this.wasmModule) {
+      throw new Error('WebAssembly timer module failed to load');
     }
   }
 
@@ -66,11 +246,9 @@ export class WasmTimer {
         config.duration,
         config.state
       );
-      
-      // 根据配置优化更新频率
-      if (config.enableOptimization) {
-        const optimalInterval = this.calculator.get_optimal_update_interval();
-        console.log(`Optimal update interval: ${optimalInterval}ms`);
+
+      if (config.direction !== undefined) {
+        this.calculator.set_count_direction(config.direction);
       }
       
       return true;
@@ -80,20 +258,45 @@ export class WasmTimer {
     }
   }
 
+  // 导出计时器快照（带版本号的 JSON），用于在重载或重启后恢复
+  public snapshot(): string | null {
+    if (!this.calculator || !this.isInitialized) {
+      return null;
+    }
+
+    try {
+      return this.calculator.snapshot();
+    } catch (error) {
+      console.error('WASM timer snapshot failed:', error);
+      return null;
+    }
+  }
+
+  public async restoreTimer(snapshotJson: string): Promise<boolean> {
+    if (!this.isInitialized) {
+      await this.initializeWasm();
+    }
+
+    if (!this.wasmModule) {
+      return false;
+    }
+
+    try {
+      this.calculator = this.wasmModule.TimerCalculator.restore(snapshotJson);
+      return true;
+    } catch (error) {
+      console.error('Failed to restore WASM timer:', error);
+      return false;
+    }
+  }
+
   public update(): TimerCalculation | null {
     if (!this.calculator || !this.isInitialized) {
       return null;
     }
 
     try {
-      const result = this.calculator.update();
-      return {
-        time: result.time,
-        formattedTime: result.formatted_time,
-        progress: result.progress,
-        remaining: result.remaining,
-        state: result.state,
-      };
+      return this.toTimerCalculation(this.calculator.update());
     } catch (error) {
       console.error('WASM timer update failed:', error);
       return null;
@@ -127,13 +330,13 @@ export class WasmTimer {
     }
   }
 
-  public resume(remainingTime: number): boolean {
+  public resume(): boolean {
     if (!this.calculator || !this.isInitialized) {
       return false;
     }
 
     try {
-      this.calculator.resume(remainingTime);
+      this.calculator.resume();
       return true;
     } catch (error) {
       console.error('WASM timer resume failed:', error);
@@ -141,6 +344,236 @@ export class WasmTimer {
     }
   }
 
+  // 心流模式下按当前专注时长建议的休息秒数
+  public suggestedBreak(): number {
+    if (!this.calculator || !this.isInitialized) {
+      return 0;
+    }
+
+    try {
+      return this.calculator.suggested_break();
+    } catch (error) {
+      return 0;
+    }
+  }
+
+  // 专注 focusedSeconds 秒后按心流设置（分钟）建议的休息秒数，即 FlowPolicy::break_for，不依赖当前计时器。
+  // 桌面端的休息时长由后端计时器决定，这里只用于浏览器计时与预览；WASM 未加载时取休息下限
+  public suggestFlowBreak(
+    focusedSeconds: number,
+    breakRatio: number,
+    minBreakMinutes: number,
+    maxBreakMinutes: number
+  ): number {
+    if (!this.isInitialized || !this.wasmModule) {
+      return minBreakMinutes * 60;
+    }
+
+    return this.wasmModule.suggest_flow_break(
+      Math.max(0, Math.floor(focusedSeconds)),
+      breakRatio,
+      minBreakMinutes * 60,
+      maxBreakMinutes * 60
+    );
+  }
+
+  // 截止时间模式：计时到指定时刻（如 "11:30"），时区沿用 setDayCalendar 的设置，跨零点时自动取次日，返回计时秒数
+  public focusUntil(hour: number, minute: number, state: TimerState = TimerState.Focus): number {
+    if (!this.calculator || !this.isInitialized) {
+      return 0;
+    }
+
+    try {
+      const deadline = this.wasmModule.next_local_time(Date.now(), hour, minute, this.getCalendar());
+      return this.calculator.reset_until(deadline, state);
+    } catch (error) {
+      console.error('WASM timer deadline failed:', error);
+      return 0;
+    }
+  }
+
+  public planUntil(deadlineMs: number, shortenLast: boolean = true): { state: TimerState; duration: number }[] {
+    if (!this.calculator || !this.isInitialized) {
+      return [];
+    }
+
+    try {
+      return this.calculator.plan_until(deadlineMs, shortenLast).map((block: any) => ({
+        state: block.state,
+        duration: block.duration,
+      }));
+    } catch (error) {
+      return [];
+    }
+  }
+
+  // 结束当前阶段并切换到下一阶段（按程序或循环策略）
+  public advance(): { state: TimerState; duration: number } | null {
+    if (!this.calculator || !this.isInitialized) {
+      return null;
+    }
+
+    try {
+      const next = this.calculator.advance();
+      return { state: next.state, duration: next.duration };
+    } catch (error) {
+      console.error('WASM timer advance failed:', error);
+      return null;
+    }
+  }
+
+  // 校验计时程序文本，合法时返回 null
+  public checkProgram(source: string): ProgramDiagnostic | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return { line: 0, message: 'WASM module not initialized' };
+    }
+
+    const diagnostic = this.wasmModule.check_program(source);
+    if (!diagnostic) {
+      return null;
+    }
+    const result = { line: diagnostic.line, message: diagnostic.message };
+    diagnostic.free();
+    return result;
+  }
+
+  // 按文本程序推进阶段，如 "repeat 4 { focus 25m; break 5m }; long_break 20m"；
+  // 传入 null 恢复经典循环。程序有误时返回诊断信息且不改变当前设置
+  public setProgram(source: string | null): ProgramDiagnostic | null {
+    if (!this.calculator || !this.isInitialized) {
+      return { line: 0, message: 'WASM timer not initialized' };
+    }
+
+    if (source === null) {
+      this.calculator.clear_program();
+      return null;
+    }
+    const diagnostic = this.checkProgram(source);
+    if (diagnostic) {
+      return diagnostic;
+    }
+    try {
+      this.calculator.set_program(source);
+      return null;
+    } catch (error) {
+      return { line: 0, message: String(error) };
+    }
+  }
+
+  // 使用内置程序：pomodoro、52-17、ultradian
+  public usePreset(name: string): boolean {
+    if (!this.calculator || !this.isInitialized) {
+      return false;
+    }
+
+    try {
+      return this.calculator.use_preset(name);
+    } catch (error) {
+      console.error('WASM timer preset failed:', error);
+      return false;
+    }
+  }
+
+  public getProgramPresets(): { name: string; source: string }[] {
+    if (!this.isInitialized || !this.wasmModule) {
+      return [];
+    }
+
+    return this.wasmModule.program_presets().map((name: string) => ({
+      name,
+      source: this.wasmModule.preset_source(name) ?? '',
+    }));
+  }
+
+  public setOvertimePolicy(rule: OvertimeRule, breakFactor: number = 0.2, reminderInterval: number = 300): boolean {
+    if (!this.calculator || !this.isInitialized) {
+      return false;
+    }
+
+    try {
+      this.calculator.set_overtime_policy(rule, breakFactor, reminderInterval);
+      return true;
+    } catch (error) {
+      console.error('WASM timer set overtime policy failed:', error);
+      return false;
+    }
+  }
+
+  public setSuspendPolicy(policy: SuspendPolicy): boolean {
+    if (!this.calculator || !this.isInitialized) {
+      return false;
+    }
+
+    try {
+      this.calculator.set_suspend_policy(policy);
+      return true;
+    } catch (error) {
+      console.error('WASM timer set suspend policy failed:', error);
+      return false;
+    }
+  }
+
+  public onEvent(listener: ((event: TimerEvent) => void) | null): boolean {
+    if (!this.calculator || !this.isInitialized) {
+      return false;
+    }
+
+    try {
+      if (listener) {
+        this.calculator.set_event_listener((record: any) => listener(this.toTimerEvent(record)));
+      } else {
+        this.calculator.clear_event_listener();
+      }
+      return true;
+    } catch (error) {
+      console.error('WASM timer event listener failed:', error);
+      return false;
+    }
+  }
+
+  public drainEvents(): TimerEvent[] {
+    if (!this.calculator || !this.isInitialized) {
+      return [];
+    }
+
+    try {
+      return this.calculator.drain_events().map((record: any) => this.toTimerEvent(record));
+    } catch (error) {
+      return [];
+    }
+  }
+
+  private toTimerCalculation(result: any): TimerCalculation {
+    return {
+      time: result.time,
+      formattedTime: result.formatted_time,
+      progress: result.progress,
+      remaining: result.remaining,
+      state: result.state,
+      status: result.status,
+      pausedTime: result.paused_time,
+      clockEvent: result.clock_event,
+      clockEventMs: result.clock_event_ms,
+      cycle: result.cycle,
+      cyclesPerSet: result.cycles_per_set,
+      remainingMs: result.remaining_ms,
+      elapsedMs: result.elapsed_ms,
+      startedAt: result.started_at,
+      endsAt: result.ends_at,
+      overtimeMs: result.overtime_ms,
+    };
+  }
+
+  private toTimerEvent(record: any): TimerEvent {
+    return {
+      kind: record.kind,
+      state: record.state,
+      fromState: record.from_state,
+      clockEvent: record.clock_event,
+      value: record.value,
+    };
+  }
+
   public shouldUpdateDisplay(lastUpdate: number): boolean {
     if (!this.calculator || !this.isInitialized) {
       return true;
@@ -154,18 +587,21 @@ export class WasmTimer {
   }
 
   public calculateFormattedTime(seconds: number): string {
+    return this.formatDuration(seconds, DurationFormat.Clock, 'en');
+  }
+
+  // 计时器、统计页、托盘与通知统一使用的时长格式化；负数与小数按向下取整的非负秒数处理。
+  // 应用启动时已等待 ready()，WASM 未加载时只显示占位符
+  public formatDuration(
+    seconds: number,
+    format: DurationFormat = DurationFormat.Clock,
+    locale: string = 'zh-CN'
+  ): string {
     if (!this.isInitialized || !this.wasmModule) {
-      return this.fallbackFormatTime(seconds);
+      return '--:--';
     }
 
-    try {
-      return this.wasmModule.TimerCalculator.prototype.calculate_formatted_time.call(
-        null,
-        seconds
-      );
-    } catch (error) {
-      return this.fallbackFormatTime(seconds);
-    }
+    return this.wasmModule.format_duration(Math.max(0, Math.floor(seconds || 0)), format, locale);
   }
 
   public calculateProgress(current: number, total: number): number {
@@ -196,37 +632,294 @@ export class WasmTimer {
     }
   }
 
-  public async calculateMultipleTimers(durations: number[]): Promise<TimerCalculation[]> {
-    if (!this.isInitialized || !this.wasmModule) {
-      return this.fallbackCalculateMultiple(durations);
+  // 具名计时器：与主计时器相互独立，由 updateAllTimers 统一推进
+  public async startNamedTimer(name: string, duration: number, state: TimerState): Promise<boolean> {
+    if (!this.isInitialized) {
+      await this.initializeWasm();
+    }
+
+    if (!this.wasmModule) {
+      return false;
     }
 
     try {
-      const results = this.wasmModule.calculate_multiple_timers(durations);
-      return results.map((result: any) => ({
-        time: result.time,
-        formattedTime: result.formatted_time,
-        progress: result.progress,
-        remaining: result.remaining,
-        state: result.state,
+      if (!this.registry) {
+        this.registry = new this.wasmModule.TimerRegistry();
+      }
+      this.registry.start(name, duration, state);
+      return true;
+    } catch (error) {
+      console.error('Failed to start named WASM timer:', error);
+      return false;
+    }
+  }
+
+  public pauseNamedTimer(name: string): number | null {
+    if (!this.registry) {
+      return null;
+    }
+
+    try {
+      return this.registry.pause(name) ?? null;
+    } catch (error) {
+      return null;
+    }
+  }
+
+  public resumeNamedTimer(name: string): boolean {
+    if (!this.registry) {
+      return false;
+    }
+
+    try {
+      return this.registry.resume(name);
+    } catch (error) {
+      return false;
+    }
+  }
+
+  public removeNamedTimer(name: string): boolean {
+    if (!this.registry) {
+      return false;
+    }
+
+    try {
+      return this.registry.remove(name);
+    } catch (error) {
+      return false;
+    }
+  }
+
+  public updateAllTimers(): NamedTimerCalculation[] {
+    if (!this.registry) {
+      return [];
+    }
+
+    try {
+      return this.registry.update_all().map((entry: any) => ({
+        name: entry.name,
+        calculation: this.toTimerCalculation(entry.calculation),
       }));
     } catch (error) {
-      return this.fallbackCalculateMultiple(durations);
+      console.error('WASM timer registry update failed:', error);
+      return [];
     }
   }
 
-  public getOptimalUpdateInterval(): number {
+  // 到下一次可见变化的毫秒数；暂停时返回 null，不需要唤醒
+  public nextTickDelay(
+    precision: DisplayPrecision = DisplayPrecision.Seconds,
+    visible: boolean = typeof document === 'undefined' || document.visibilityState === 'visible'
+  ): number | null {
     if (!this.calculator || !this.isInitialized) {
       return 1000;
     }
 
     try {
-      return this.calculator.get_optimal_update_interval();
+      return this.calculator.next_tick_delay(precision, visible) ?? null;
     } catch (error) {
       return 1000;
     }
   }
 
+  // 每次可见变化只唤醒一次；页面可见性变化时立即重新计算。
+  // 暂停后不再唤醒，恢复计时后需重新调用。返回停止函数
+  public startTicking(
+    onTick: (calculation: TimerCalculation) => void,
+    precision: DisplayPrecision = DisplayPrecision.Seconds
+  ): () => void {
+    let handle: ReturnType<typeof setTimeout> | null = null;
+
+    const schedule = () => {
+      if (handle !== null) clearTimeout(handle);
+      handle = null;
+      const delay = this.nextTickDelay(precision);
+      if (delay === null) return;
+      handle = setTimeout(() => {
+        const calculation = this.update();
+        if (calculation) onTick(calculation);
+        schedule();
+      }, delay);
+    };
+
+    const onVisibilityChange = () => {
+      const calculation = this.update();
+      if (calculation) onTick(calculation);
+      schedule();
+    };
+
+    if (typeof document !== 'undefined') {
+      document.addEventListener('visibilitychange', onVisibilityChange);
+    }
+    schedule();
+
+    return () => {
+      if (handle !== null) clearTimeout(handle);
+      if (typeof document !== 'undefined') {
+        document.removeEventListener('visibilitychange', onVisibilityChange);
+      }
+    };
+  }
+
+  // 与桌面端共用的效率评分，WASM 不可用时返回 null
+  public computeEfficiencyScore(metrics: SessionMetrics, weights?: Partial<ScoreWeights>): EfficiencyScore | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    try {
+      const result = this.wasmModule.compute_efficiency_score(
+        JSON.stringify(metrics),
+        weights ? JSON.stringify(weights) : ''
+      );
+      return JSON.parse(result);
+    } catch (error) {
+      console.error('WASM efficiency score failed:', error);
+      return null;
+    }
+  }
+
+  // 设置日期划分规则：timeZone 为 IANA 时区名（空字符串为系统时区），
+  // dayStartMinutes 为每天开始的本地时刻（如 240 表示凌晨 4 点）
+  public setDayCalendar(timeZone: string = '', dayStartMinutes: number = 0): boolean {
+    if (!this.isInitialized || !this.wasmModule) {
+      return false;
+    }
+
+    try {
+      const calendar = new this.wasmModule.DayCalendar(timeZone, dayStartMinutes);
+      this.calendar?.free();
+      this.calendar = calendar;
+      return true;
+    } catch (error) {
+      console.error('WASM day calendar failed:', error);
+      return false;
+    }
+  }
+
+  private getCalendar(): any {
+    if (!this.calendar) {
+      this.calendar = new this.wasmModule.DayCalendar('', 0);
+    }
+    return this.calendar;
+  }
+
+  // 时刻所属日期（YYYY-MM-DD），考虑时区、夏令时和每天的开始时刻
+  public dateKey(timestampMs: number = Date.now()): string | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    try {
+      return this.getCalendar().date_key(timestampMs);
+    } catch (error) {
+      console.error('WASM date key failed:', error);
+      return null;
+    }
+  }
+
+  // nowMs 所属日期往前 days 天的日期（YYYY-MM-DD），按日历天数计算，不受夏令时天长影响
+  public dateKeyDaysAgo(days: number, nowMs: number = Date.now()): string | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    try {
+      const calendar = this.getCalendar();
+      const day = calendar.day_of(nowMs) - Math.floor(days);
+      return calendar.date_key(calendar.day_start(day));
+    } catch (error) {
+      console.error('WASM date key failed:', error);
+      return null;
+    }
+  }
+
+  // nowMs 所在日、周、月在 aggregateSessions 结果中的 key，以及第 day 天的日期（YYYY-MM-DD）
+  public periodKeys(nowMs: number = Date.now()): { day: number; week: number; month: number } | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    try {
+      const calendar = this.getCalendar();
+      const day = calendar.day_of(nowMs);
+      return { day, week: calendar.week_of(day), month: calendar.month_of(day) };
+    } catch (error) {
+      console.error('WASM period keys failed:', error);
+      return null;
+    }
+  }
+
+  public dayDateKey(day: number): string | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    try {
+      const calendar = this.getCalendar();
+      return calendar.date_key(calendar.day_start(day));
+    } catch (error) {
+      console.error('WASM date key failed:', error);
+      return null;
+    }
+  }
+
+  // 在 WASM 中一次性聚合会话历史，日期按 setDayCalendar 的规则划分；WASM 不可用时返回 null
+  public aggregateSessions(columns: SessionColumns, nowMs: number = Date.now()): SessionStats | null {
+    if (!this.isInitialized || !this.wasmModule) {
+      return null;
+    }
+
+    let stats: any = null;
+    try {
+      stats = this.wasmModule.aggregate_sessions(
+        columns.startedAt,
+        columns.endedAt ?? new Float64Array(0),
+        columns.focus,
+        columns.breakTime,
+        columns.microBreaks,
+        columns.efficiency,
+        nowMs,
+        this.getCalendar()
+      );
+      const series = (period: any): PeriodSeries => {
+        try {
+          return {
+            keys: period.keys(),
+            focus: period.focus(),
+            breakTime: period.break_time(),
+            microBreaks: period.micro_breaks(),
+            sessions: period.sessions(),
+            averageEfficiency: period.average_efficiency(),
+          };
+        } finally {
+          period.free();
+        }
+      };
+      return {
+        daily: series(stats.daily()),
+        weekly: series(stats.weekly()),
+        monthly: series(stats.monthly()),
+        totalFocus: stats.total_focus,
+        totalSessions: stats.total_sessions,
+        averageDailyFocus: stats.average_daily_focus,
+        averageSessionFocus: stats.average_session_focus,
+        averageEfficiency: stats.average_efficiency,
+        currentStreak: stats.current_streak,
+        longestStreak: stats.longest_streak,
+        bestHour: stats.best_hour ?? null,
+        hourlyFocus: stats.hourly_focus(),
+        heatmap: stats.heatmap(),
+        heatmapStartWeekday: stats.heatmap_start_weekday,
+      };
+    } catch (error) {
+      console.error('WASM stats aggregation failed:', error);
+      return null;
+    } finally {
+      stats?.free();
+    }
+  }
+
   public optimizeMemoryUsage(currentMemory: number): number {
     if (!this.isInitialized || !this.wasmModule) {
       return currentMemory;
@@ -259,12 +952,6 @@ export class WasmTimer {
     return this.isInitialized && this.wasmModule !== null;
   }
 
-  private fallbackFormatTime(seconds: number): string {
-    const mins = Math.floor(seconds / 60);
-    const secs = seconds % 60;
-    return `${mins.toString().padStart(2, '0')}:${secs.toString().padStart(2, '0')}`;
-  }
-
   private fallbackBenchmark(iterations: number): number {
     const start = performance.now();
     let result = 0;
@@ -274,16 +961,6 @@ export class WasmTimer {
     return performance.now() - start;
   }
 
-  private fallbackCalculateMultiple(durations: number[]): TimerCalculation[] {
-    return durations.map(duration => ({
-      time: duration,
-      formattedTime: this.fallbackFormatTime(duration),
-      progress: duration === 0 ? 0 : (duration / 3600) * 100,
-      remaining: duration,
-      state: TimerState.Focus,
-    }));
-  }
-
   private fallbackNextState(currentState: TimerState, completed: boolean): TimerState {
     if (!completed) return currentState;
     
@@ -294,6 +971,10 @@ export class WasmTimer {
         return TimerState.Focus;
       case TimerState.MicroBreak:
         return TimerState.Focus;
+      case TimerState.ForcedBreak:
+      case TimerState.LongBreak:
+      case TimerState.Idle:
+        return TimerState.Focus;
       default:
         return TimerState.Focus;
     }
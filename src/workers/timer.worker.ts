@@ -14,8 +14,59 @@ interface TimerCalculation {
   remaining: number;
 }
 
+// 显示精度对应的刷新粒度（毫秒）
+type TickPrecision = 'tenths' | 'seconds' | 'minutes';
+
+const PRECISION_UNIT_MS: Record<TickPrecision, number> = {
+  tenths: 100,
+  seconds: 1000,
+  minutes: 60000,
+};
+
+// 页面不可见时只按分钟刷新
+const HIDDEN_UNIT_MS = PRECISION_UNIT_MS.minutes;
+
+// 与 WASM DisplayPrecision 的取值对应
+const PRECISION_CODE: Record<TickPrecision, number> = {
+  tenths: 0,
+  seconds: 1,
+  minutes: 2,
+};
+
+// WASM CountDirection.Down，Worker 中的计时器都是倒计时
+const COUNT_DOWN = 0;
+
+type NextTickDelay = (
+  elapsedMs: number,
+  durationMs: number,
+  precision: number,
+  pageVisible: boolean,
+  direction: number
+) => number;
+
+// 刷新调度由 timer_core::tick::next_tick_delay 计算；WASM 加载前或加载失败时按粒度固定间隔刷新
+let wasmNextTickDelay: NextTickDelay | null = null;
+
+import('../wasm/pkg/timer_calculation')
+  .then(async (wasm) => {
+    await wasm.default();
+    wasmNextTickDelay = wasm.next_tick_delay;
+  })
+  .catch((error) => {
+    console.warn('WASM tick scheduling unavailable in worker:', error);
+  });
+
+interface WorkerTimer {
+  startTime: number;
+  duration: number;
+  isActive: boolean;
+  precision: TickPrecision;
+  tickHandle: ReturnType<typeof setTimeout> | null;
+}
+
 class TimerWorker {
-  private timers: Map<string, { startTime: number; duration: number; isActive: boolean }> = new Map();
+  private timers: Map<string, WorkerTimer> = new Map();
+  private pageVisible = true;
 
 
   constructor() {
@@ -28,7 +79,10 @@ class TimerWorker {
 
       switch (type) {
         case 'START_TIMER':
-          this.startTimer(id!, payload.duration, payload.startTime);
+          this.startTimer(id!, payload.duration, payload.startTime, payload.precision);
+          break;
+        case 'SET_VISIBILITY':
+          this.setVisibility(payload.visible);
           break;
         case 'PAUSE_TIMER':
           this.pauseTimer(id!);
@@ -54,30 +108,74 @@ class TimerWorker {
     });
   }
 
-  private startTimer(id: string, duration: number, startTime?: number) {
+  private startTimer(id: string, duration: number, startTime?: number, precision: TickPrecision = 'seconds') {
     const start = startTime || Date.now();
+    this.cancelTick(id);
     this.timers.set(id, {
       startTime: start,
       duration: duration,
-      isActive: true
+      isActive: true,
+      precision,
+      tickHandle: null
     });
 
     this.postMessage('TIMER_STARTED', { id, startTime: start, duration });
+    this.scheduleTick(id);
   }
 
   private pauseTimer(id: string) {
     const timer = this.timers.get(id);
     if (timer) {
       timer.isActive = false;
+      this.cancelTick(id);
       this.postMessage('TIMER_PAUSED', { id, pausedAt: Date.now() });
     }
   }
 
   private resetTimer(id: string, duration: number) {
+    this.cancelTick(id);
     this.timers.delete(id);
     this.postMessage('TIMER_RESET', { id, duration });
   }
 
+  // 页面可见性变化后立即推送一次当前值，并按新的粒度重新安排唤醒
+  private setVisibility(visible: boolean) {
+    this.pageVisible = visible;
+    for (const [id, timer] of this.timers.entries()) {
+      if (!timer.isActive) continue;
+      this.postMessage('TICK', { id, calculation: this.calculate(timer) });
+      this.scheduleTick(id);
+    }
+  }
+
+  // 每次可见变化只唤醒一次，唤醒点对齐到显示边界，避免数字跳过或重复
+  private scheduleTick(id: string) {
+    const timer = this.timers.get(id);
+    if (!timer || !timer.isActive) return;
+    this.cancelTick(id);
+
+    const elapsed = Math.max(0, Date.now() - timer.startTime);
+    const delay = wasmNextTickDelay
+      ? wasmNextTickDelay(elapsed, timer.duration, PRECISION_CODE[timer.precision], this.pageVisible, COUNT_DOWN)
+      : this.pageVisible ? PRECISION_UNIT_MS[timer.precision] : HIDDEN_UNIT_MS;
+    timer.tickHandle = setTimeout(() => {
+      timer.tickHandle = null;
+      const calculation = this.calculate(timer);
+      this.postMessage('TICK', { id, calculation });
+      if (calculation.remaining > 0) {
+        this.scheduleTick(id);
+      }
+    }, delay);
+  }
+
+  private cancelTick(id: string) {
+    const timer = this.timers.get(id);
+    if (timer && timer.tickHandle !== null) {
+      clearTimeout(timer.tickHandle);
+      timer.tickHandle = null;
+    }
+  }
+
   private getCurrentTime(id: string) {
     const timer = this.timers.get(id);
     if (!timer) {
@@ -85,19 +183,21 @@ class TimerWorker {
       return;
     }
 
+    this.postMessage('CURRENT_TIME', { id, calculation: this.calculate(timer) });
+  }
+
+  private calculate(timer: WorkerTimer): TimerCalculation {
     const now = Date.now();
     const elapsed = timer.isActive ? now - timer.startTime : 0;
     const remaining = Math.max(0, timer.duration - elapsed);
     const progress = (elapsed / timer.duration) * 100;
 
-    const calculation: TimerCalculation = {
+    return {
       time: Math.floor(remaining / 1000),
       formattedTime: this.formatTime(Math.floor(remaining / 1000)),
       progress: Math.min(100, progress),
       remaining: remaining
     };
-
-    this.postMessage('CURRENT_TIME', { id, calculation });
   }
 
   private calculateFormattedTime(time: number) {
@@ -154,4 +254,4 @@ const timerWorker = new TimerWorker();
 timerWorker.scheduleMemoryCleanup();
 
 // 导出类型供主线程使用
-export type { TimerWorkerMessage, TimerCalculation };
\ No newline at end of file
+export type { TimerWorkerMessage, TimerCalculation, TickPrecision };
\ No newline at end of file